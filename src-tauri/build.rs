@@ -14,12 +14,11 @@ fn generate_app_constants() {
     let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
     let config_path = Path::new(&manifest_dir).join("tauri.conf.json");
 
-    let config_content =
-        fs::read_to_string(&config_path).expect("Failed to read tauri.conf.json");
+    let config_content = fs::read_to_string(&config_path).expect("Failed to read tauri.conf.json");
 
     // Simple JSON parsing for productName (avoid adding serde_json as build dep)
-    let product_name =
-        extract_json_string(&config_content, "productName").unwrap_or_else(|| "deckmanager".to_string());
+    let product_name = extract_json_string(&config_content, "productName")
+        .unwrap_or_else(|| "deckmanager".to_string());
 
     // Convert to lowercase and remove spaces for paths/service names
     let app_name_lower = product_name.to_lowercase().replace(' ', "");
@@ -1,6 +1,26 @@
 use crate::capability::Capability;
+use crate::events::GestureKind;
 use crate::input_processor::LogicalEvent;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::BTreeMap;
+
+/// Default hold time before a long-press fires (milliseconds)
+pub const DEFAULT_LONG_PRESS_MS: u64 = 500;
+
+/// Default hold time before auto-repeat starts (milliseconds)
+pub const DEFAULT_REPEAT_DELAY_MS: u64 = 400;
+
+/// Default interval between auto-repeat events (milliseconds)
+pub const DEFAULT_REPEAT_INTERVAL_MS: u64 = 100;
+
+/// Default multiplier gained per extra tick in the linear acceleration curve
+pub const DEFAULT_LINEAR_GAIN: f32 = 0.5;
+
+/// Default growth factor per extra tick in the exponential acceleration curve
+pub const DEFAULT_EXPONENTIAL_BASE: f32 = 1.4;
+
+/// Default cap on the acceleration multiplier
+pub const DEFAULT_MAX_MULTIPLIER: f32 = 10.0;
 
 /// Reference to a specific input on the Stream Deck.
 ///
@@ -9,10 +29,229 @@ use serde::{Deserialize, Serialize};
 #[serde(tag = "type")]
 #[non_exhaustive]
 pub enum InputRef {
-    Button { index: usize },
-    Encoder { index: usize },
-    EncoderPress { index: usize },
+    Button {
+        index: usize,
+    },
+    Encoder {
+        index: usize,
+    },
+    EncoderPress {
+        index: usize,
+    },
     Swipe,
+    /// Tap on the LCD strip above an encoder
+    TouchTap {
+        section: usize,
+    },
+    /// Long-press on the LCD strip above an encoder
+    TouchLongPress {
+        section: usize,
+    },
+    /// Several buttons held together
+    Chord {
+        indices: Vec<usize>,
+    },
+    /// One of the three pedals on a Stream Deck Pedal
+    Pedal {
+        position: PedalPosition,
+    },
+}
+
+/// Pedal on a Stream Deck Pedal, which reports them as keys 0-2 left to right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PedalPosition {
+    Left,
+    Center,
+    Right,
+}
+
+impl PedalPosition {
+    /// Key index the pedal reports as.
+    pub fn index(self) -> usize {
+        match self {
+            PedalPosition::Left => 0,
+            PedalPosition::Center => 1,
+            PedalPosition::Right => 2,
+        }
+    }
+}
+
+/// Where a label sits on a key or LCD section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LabelPosition {
+    Top,
+    Middle,
+    Bottom,
+}
+
+/// How a binding's value is drawn over its key or LCD section (see
+/// `Plugin::value`). LCD sections add the readout and a title.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Gauge {
+    /// A 270° arc, open at the bottom
+    Arc,
+    /// A horizontal bar along the bottom
+    Bar,
+    /// A full ring, filled clockwise from the top
+    Ring,
+    /// A translucent level rising from the bottom (left to right on the LCD)
+    Fill,
+}
+
+/// How a binding looks in one of its named states (see `Plugin::state`).
+/// Anything left out falls back to the binding's own.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateStyle {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub button_image: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon_color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Built from `button_image_alt` and `icon_color_alt` when the binding
+    /// loaded, so it isn't saved (see `Binding::add_alt_states`)
+    #[serde(skip)]
+    pub from_alt: bool,
+}
+
+/// Whether none of `states` was configured under `states` itself.
+fn no_configured_states(states: &BTreeMap<String, StateStyle>) -> bool {
+    states.values().all(|style| style.from_alt)
+}
+
+/// Save only configured states; the alt fields rebuild the rest on load.
+fn serialize_configured_states<S: Serializer>(
+    states: &BTreeMap<String, StateStyle>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(states.iter().filter(|(_, style)| !style.from_alt))
+}
+
+/// Sort and deduplicate chord indices so `[3, 1]` and `[1, 3, 3]` compare equal.
+pub fn normalize_chord(indices: &[usize]) -> Vec<usize> {
+    let mut indices = indices.to_vec();
+    indices.sort_unstable();
+    indices.dedup();
+    indices
+}
+
+/// Gesture qualifier for button bindings.
+///
+/// Bindings without a gesture fire on the raw press/release edges. A single key
+/// can carry a `Tap`, `DoubleTap` and `LongPress` binding at the same time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Gesture {
+    /// Short press and release
+    Tap,
+    /// Two taps in quick succession
+    DoubleTap,
+    /// Key held past the threshold
+    LongPress {
+        #[serde(default = "default_long_press_ms")]
+        threshold_ms: u64,
+    },
+    /// Fires on press, then repeatedly while the key is held
+    HoldRepeat {
+        #[serde(default = "default_repeat_delay_ms")]
+        delay_ms: u64,
+        #[serde(default = "default_repeat_interval_ms")]
+        interval_ms: u64,
+    },
+}
+
+fn default_long_press_ms() -> u64 {
+    DEFAULT_LONG_PRESS_MS
+}
+
+fn default_repeat_delay_ms() -> u64 {
+    DEFAULT_REPEAT_DELAY_MS
+}
+
+fn default_repeat_interval_ms() -> u64 {
+    DEFAULT_REPEAT_INTERVAL_MS
+}
+
+impl Gesture {
+    /// The event kind emitted by the input processor for this gesture.
+    pub fn kind(&self) -> GestureKind {
+        match self {
+            Gesture::Tap => GestureKind::Tap,
+            Gesture::DoubleTap => GestureKind::DoubleTap,
+            Gesture::LongPress { .. } => GestureKind::LongPress,
+            Gesture::HoldRepeat { .. } => GestureKind::HoldRepeat,
+        }
+    }
+}
+
+/// Encoder acceleration curve.
+///
+/// Maps the turn speed (ticks in the last 100 ms) to a multiplier on the encoder
+/// delta. One tick per 100 ms is always 1x, so slow turns keep fine control.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Acceleration {
+    /// Multiplier grows by `gain` for every extra tick
+    Linear {
+        #[serde(default = "default_linear_gain")]
+        gain: f32,
+        #[serde(default = "default_max_multiplier")]
+        max: f32,
+    },
+    /// Multiplier grows by a factor of `base` for every extra tick
+    Exponential {
+        #[serde(default = "default_exponential_base")]
+        base: f32,
+        #[serde(default = "default_max_multiplier")]
+        max: f32,
+    },
+    /// `(ticks per 100 ms, multiplier)` pairs; the highest matching entry wins
+    Table { points: Vec<(u32, f32)> },
+}
+
+fn default_linear_gain() -> f32 {
+    DEFAULT_LINEAR_GAIN
+}
+
+fn default_exponential_base() -> f32 {
+    DEFAULT_EXPONENTIAL_BASE
+}
+
+fn default_max_multiplier() -> f32 {
+    DEFAULT_MAX_MULTIPLIER
+}
+
+impl Acceleration {
+    /// Delta multiplier for the given turn speed.
+    pub fn multiplier(&self, ticks_per_100ms: u32) -> f32 {
+        let extra = ticks_per_100ms.saturating_sub(1) as f32;
+        match self {
+            Acceleration::Linear { gain, max } => (1.0 + gain * extra).min(*max),
+            Acceleration::Exponential { base, max } => base.powf(extra).min(*max),
+            Acceleration::Table { points } => points
+                .iter()
+                .filter(|(ticks, _)| *ticks <= ticks_per_100ms)
+                .max_by_key(|(ticks, _)| *ticks)
+                .map(|(_, multiplier)| *multiplier)
+                .unwrap_or(1.0),
+        }
+    }
+
+    /// Scale an encoder delta. A non-zero delta never rounds down to zero.
+    pub fn apply(&self, delta: i8, ticks_per_100ms: u32) -> i8 {
+        if delta == 0 {
+            return 0;
+        }
+        let scaled = (delta as f32 * self.multiplier(ticks_per_100ms).max(0.0)).round();
+        let scaled = scaled.clamp(i8::MIN as f32, i8::MAX as f32) as i8;
+        if scaled == 0 {
+            delta.signum()
+        } else {
+            scaled
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,10 +261,28 @@ pub struct Binding {
     /// Which page this binding belongs to (0-indexed)
     #[serde(default)]
     pub page: usize,
-    /// Custom emoji or icon name for this binding (UI only)
+    /// Folder this binding's page belongs to (None = root pages)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+    /// Serial of the deck this binding is on (None = every deck)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    /// Gesture that triggers this binding (buttons only, None = raw press/release)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gesture: Option<Gesture>,
+    /// Acceleration curve for encoder rotation (None = raw delta)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acceleration: Option<Acceleration>,
+    /// Undo the action on release so it only lasts while held
+    /// (None = only pedals do this). Only keys, encoder presses and pedals
+    /// have a release; gesture, chord and touch bindings ignore it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub momentary: Option<bool>,
+    /// Custom emoji or icon name for this binding (emoji are drawn on keys
+    /// without an image)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub icon: Option<String>,
-    /// Custom display text for this binding (UI only)
+    /// Custom display text for this binding; `\n` starts a new line
     #[serde(skip_serializing_if = "Option::is_none")]
     pub label: Option<String>,
     /// File path or URL for hardware button image (default state)
@@ -43,16 +300,302 @@ pub struct Binding {
     /// Color for alternate SVG icons (hex format, e.g., "#ffffff")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub icon_color_alt: Option<String>,
+    /// Key color behind the image or text (hex format, default black)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<String>,
+    /// Label color on hardware (hex format, default white)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_color: Option<String>,
+    /// Label size on hardware in pixels (None = scaled to the key)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font_size: Option<u32>,
+    /// Where the label sits (None = below an image or emoji, centred otherwise)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label_position: Option<LabelPosition>,
+    /// Outline drawn around label text (hex format, None = drop shadow)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outline_color: Option<String>,
+    /// Show the capability's value (e.g., volume) as a gauge over the key, or
+    /// instead of the image on the LCD strip (None = no gauge)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gauge: Option<Gauge>,
+    /// Image, color and label per state key the plugin reports (e.g.,
+    /// "recording", "paused"). The only source of a binding's look per
+    /// state: the alt image and color are added here as it loads
+    #[serde(
+        default,
+        skip_serializing_if = "no_configured_states",
+        serialize_with = "serialize_configured_states"
+    )]
+    pub states: BTreeMap<String, StateStyle>,
 }
 
 impl Binding {
+    /// An unstyled binding on the first root page of every deck, for tests
+    /// to fill in with `..Binding::new(input, capability)`.
+    #[cfg(test)]
+    pub fn new(input: InputRef, capability: Capability) -> Self {
+        Self {
+            input,
+            capability,
+            page: 0,
+            folder: None,
+            device: None,
+            gesture: None,
+            acceleration: None,
+            momentary: None,
+            icon: None,
+            label: None,
+            button_image: None,
+            button_image_alt: None,
+            show_label: None,
+            icon_color: None,
+            icon_color_alt: None,
+            background_color: None,
+            text_color: None,
+            font_size: None,
+            label_position: None,
+            outline_color: None,
+            gauge: None,
+            states: BTreeMap::new(),
+        }
+    }
+
+    /// Style the capability's active states (e.g., "muted") with
+    /// `button_image_alt` and `icon_color_alt`, unless `states` already
+    /// styles them. Run on every binding as it loads or is set.
+    pub fn add_alt_states(&mut self) {
+        if self.button_image_alt.is_none() && self.icon_color_alt.is_none() {
+            return;
+        }
+        for key in self.capability.active_states() {
+            self.states
+                .entry(key.to_string())
+                .or_insert_with(|| StateStyle {
+                    button_image: self.button_image_alt.clone(),
+                    icon_color: self.icon_color_alt.clone(),
+                    from_alt: true,
+                    ..Default::default()
+                });
+        }
+    }
+
+    pub fn is_pedal(&self) -> bool {
+        matches!(self.input, InputRef::Pedal { .. })
+    }
+
+    /// Whether releasing the input undoes the action (see `Capability::reverse`).
+    /// Pedals are hold-to-activate unless turned off explicitly.
+    pub fn is_momentary(&self) -> bool {
+        self.momentary.unwrap_or_else(|| self.is_pedal())
+    }
+
     pub fn matches(&self, event: &LogicalEvent) -> bool {
         match (&self.input, event) {
-            (InputRef::Button { index }, LogicalEvent::Button(e)) => e.index == *index,
+            (InputRef::Button { index }, LogicalEvent::Button(e)) => {
+                self.gesture.is_none() && e.index == *index
+            }
+            (InputRef::Button { index }, LogicalEvent::Gesture(e)) => {
+                self.gesture.map(|g| g.kind()) == Some(e.gesture) && e.index == *index
+            }
             (InputRef::Encoder { index }, LogicalEvent::Encoder(e)) => e.index == *index,
             (InputRef::EncoderPress { index }, LogicalEvent::EncoderPress(e)) => e.index == *index,
             (InputRef::Swipe, LogicalEvent::Swipe(_)) => true,
+            (InputRef::TouchTap { section }, LogicalEvent::TouchTap(e)) => e.section == *section,
+            (InputRef::TouchLongPress { section }, LogicalEvent::TouchLongPress(e)) => {
+                e.section == *section
+            }
+            (InputRef::Chord { indices }, LogicalEvent::Chord(e)) => {
+                normalize_chord(indices) == e.indices
+            }
+            (InputRef::Pedal { position }, LogicalEvent::Pedal(e)) => e.index == position.index(),
             _ => false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slow_turns_are_not_accelerated() {
+        let curves = [
+            Acceleration::Linear {
+                gain: 0.5,
+                max: 10.0,
+            },
+            Acceleration::Exponential {
+                base: 1.4,
+                max: 10.0,
+            },
+            Acceleration::Table {
+                points: vec![(3, 2.0), (6, 5.0)],
+            },
+        ];
+        for curve in &curves {
+            assert_eq!(curve.apply(1, 1), 1);
+            assert_eq!(curve.apply(-1, 1), -1);
+        }
+    }
+
+    #[test]
+    fn linear_curve_grows_and_caps() {
+        let curve = Acceleration::Linear {
+            gain: 0.5,
+            max: 4.0,
+        };
+        assert_eq!(curve.multiplier(3), 2.0);
+        assert_eq!(curve.multiplier(20), 4.0);
+        assert_eq!(curve.apply(-1, 5), -3);
+    }
+
+    #[test]
+    fn exponential_curve_grows_and_caps() {
+        let curve = Acceleration::Exponential {
+            base: 2.0,
+            max: 16.0,
+        };
+        assert_eq!(curve.multiplier(4), 8.0);
+        assert_eq!(curve.multiplier(10), 16.0);
+    }
+
+    #[test]
+    fn table_curve_uses_highest_matching_entry() {
+        let curve = Acceleration::Table {
+            points: vec![(6, 5.0), (3, 2.0)],
+        };
+        assert_eq!(curve.multiplier(2), 1.0);
+        assert_eq!(curve.multiplier(4), 2.0);
+        assert_eq!(curve.multiplier(9), 5.0);
+    }
+
+    #[test]
+    fn accelerated_delta_saturates() {
+        let curve = Acceleration::Linear {
+            gain: 100.0,
+            max: 1000.0,
+        };
+        assert_eq!(curve.apply(10, 10), i8::MAX);
+        assert_eq!(curve.apply(-10, 10), i8::MIN);
+    }
+
+    #[test]
+    fn acceleration_parses_from_toml() {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            acceleration: Acceleration,
+        }
+
+        let w: Wrapper = toml::from_str(r#"acceleration = { type = "Exponential" }"#).unwrap();
+        assert_eq!(
+            w.acceleration,
+            Acceleration::Exponential {
+                base: DEFAULT_EXPONENTIAL_BASE,
+                max: DEFAULT_MAX_MULTIPLIER
+            }
+        );
+
+        let w: Wrapper =
+            toml::from_str(r#"acceleration = { type = "Table", points = [[3, 2.0], [6, 5.0]] }"#)
+                .unwrap();
+        assert_eq!(
+            w.acceleration,
+            Acceleration::Table {
+                points: vec![(3, 2.0), (6, 5.0)]
+            }
+        );
+    }
+
+    #[test]
+    fn pedal_matches_its_key() {
+        let binding: Binding = toml::from_str(
+            r#"
+            input = { type = "Pedal", position = "Center" }
+            capability = { type = "MicMute" }
+            page = 0
+            "#,
+        )
+        .unwrap();
+        let event = |index| crate::events::ButtonEvent {
+            index,
+            pressed: true,
+        };
+        let key = Binding::new(InputRef::Button { index: 1 }, binding.capability.clone());
+
+        assert!(binding.matches(&LogicalEvent::Pedal(event(1))));
+        assert!(!binding.matches(&LogicalEvent::Pedal(event(0))));
+        assert!(binding.is_momentary());
+        // Pedals and the keys of other decks stay apart
+        assert!(!binding.matches(&LogicalEvent::Button(event(1))));
+        assert!(!key.matches(&LogicalEvent::Pedal(event(1))));
+    }
+
+    #[test]
+    fn momentary_is_opt_in_for_buttons() {
+        let binding = |extra: &str| -> Binding {
+            toml::from_str(&format!(
+                "input = {{ type = \"Button\", index = 2 }}\ncapability = {{ type = \"MicMute\" }}\n{}",
+                extra
+            ))
+            .unwrap()
+        };
+
+        assert!(!binding("").is_momentary());
+        assert!(binding("momentary = true").is_momentary());
+    }
+
+    #[test]
+    fn states_parse_next_to_alt_image() {
+        let binding: Binding = toml::from_str(
+            r##"
+            input = { type = "Button", index = 0 }
+            capability = { type = "MediaPlayPause" }
+            page = 0
+            button_image = "idle.png"
+            button_image_alt = "active.png"
+
+            [states.recording]
+            button_image = "rec.png"
+            icon_color = "#ff0000"
+
+            [states.paused]
+            label = "Paused"
+            "##,
+        )
+        .unwrap();
+
+        assert_eq!(binding.button_image_alt.as_deref(), Some("active.png"));
+        assert_eq!(
+            binding.states["recording"].button_image.as_deref(),
+            Some("rec.png")
+        );
+        assert_eq!(binding.states["paused"].label.as_deref(), Some("Paused"));
+        assert_eq!(binding.states["paused"].button_image, None);
+    }
+
+    #[test]
+    fn alt_image_styles_the_active_state_without_being_saved() {
+        let mut binding: Binding = toml::from_str(
+            r##"
+            input = { type = "Button", index = 0 }
+            capability = { type = "MediaPlayPause" }
+            button_image_alt = "pause.png"
+            icon_color_alt = "#ff0000"
+
+            [states.paused]
+            label = "Paused"
+            "##,
+        )
+        .unwrap();
+        binding.add_alt_states();
+
+        let playing = &binding.states["playing"];
+        assert_eq!(playing.button_image.as_deref(), Some("pause.png"));
+        assert_eq!(playing.icon_color.as_deref(), Some("#ff0000"));
+
+        let saved: Binding = toml::from_str(&toml::to_string(&binding).unwrap()).unwrap();
+        assert_eq!(saved.states.keys().collect::<Vec<_>>(), ["paused"]);
+        assert_eq!(saved.button_image_alt.as_deref(), Some("pause.png"));
+    }
+}
@@ -1,10 +1,15 @@
-use crate::binding::Binding;
-use crate::image_cache;
-use ab_glyph::{FontRef, PxScale};
+use crate::binding::{Binding, Gauge, LabelPosition};
+use crate::gauge::{self, draw_arc, draw_bar, draw_fill_across, draw_fill_up, draw_ring};
+use crate::image_cache::{self, Frames};
+use crate::plugin::BindingValue;
+use crate::text_layout::{draw_text, draw_text_in, TextStyle};
+use ab_glyph::{Font, FontRef, FontVec, GlyphImageFormat, PxScale};
 use anyhow::{Context, Result};
 use image::{DynamicImage, Rgba, RgbaImage};
-use imageproc::drawing::draw_text_mut;
-use std::path::Path;
+use imageproc::drawing::{draw_filled_circle_mut, draw_polygon_mut, draw_text_mut};
+use imageproc::point::Point;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 /// Renders images for Stream Deck hardware buttons.
 pub struct ButtonRenderer {
@@ -15,6 +20,92 @@ pub struct ButtonRenderer {
 // Embedded font for text rendering (DejaVu Sans Mono Bold subset or similar)
 const EMBEDDED_FONT: &[u8] = include_bytes!("../assets/DejaVuSans-Bold.ttf");
 
+// System emoji fonts, tried in order (Arch, Debian/Ubuntu, Fedora layouts)
+const EMOJI_FONT_PATHS: &[&str] = &[
+    "/usr/share/fonts/noto/NotoColorEmoji.ttf",
+    "/usr/share/fonts/truetype/noto/NotoColorEmoji.ttf",
+    "/usr/share/fonts/google-noto-emoji/NotoColorEmoji.ttf",
+    "/usr/share/fonts/noto-emoji/NotoColorEmoji.ttf",
+    "/usr/share/fonts/TTF/NotoEmoji-Regular.ttf",
+    "/usr/share/fonts/truetype/noto/NotoEmoji-Regular.ttf",
+];
+
+const BLACK: Rgba<u8> = Rgba([0, 0, 0, 255]);
+const WHITE: Rgba<u8> = Rgba([255, 255, 255, 255]);
+
+/// Parse a hex color ("#rgb", "#rrggbb" or "#rrggbbaa").
+pub fn parse_color(hex: &str) -> Option<Rgba<u8>> {
+    let hex = hex.trim().trim_start_matches('#');
+    if !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize, len: usize| u8::from_str_radix(&hex[i * len..(i + 1) * len], 16).ok();
+    match hex.len() {
+        3 => {
+            let [r, g, b] = [channel(0, 1)?, channel(1, 1)?, channel(2, 1)?];
+            Some(Rgba([r * 17, g * 17, b * 17, 255]))
+        }
+        6 => Some(Rgba([channel(0, 2)?, channel(1, 2)?, channel(2, 2)?, 255])),
+        8 => Some(Rgba([
+            channel(0, 2)?,
+            channel(1, 2)?,
+            channel(2, 2)?,
+            channel(3, 2)?,
+        ])),
+        _ => None,
+    }
+}
+
+/// The first emoji font found on the system, loaded once.
+fn emoji_font() -> Option<&'static FontVec> {
+    static FONT: OnceLock<Option<FontVec>> = OnceLock::new();
+    FONT.get_or_init(|| {
+        let user_font = dirs::data_dir().map(|dir| dir.join("fonts/NotoColorEmoji.ttf"));
+        EMOJI_FONT_PATHS
+            .iter()
+            .map(PathBuf::from)
+            .chain(user_font)
+            .find_map(|path| FontVec::try_from_vec(std::fs::read(path).ok()?).ok())
+    })
+    .as_ref()
+}
+
+/// Render the first emoji of `icon` as a `size`-pixel square, if an emoji
+/// font with that glyph is installed.
+///
+/// Color fonts (Noto Color Emoji) carry PNG bitmaps; outline fonts are drawn
+/// in `color` like text.
+pub fn render_emoji(icon: &str, size: u32, color: Rgba<u8>) -> Option<RgbaImage> {
+    let c = icon.trim().chars().next().filter(|c| !c.is_ascii())?;
+    let font = emoji_font()?;
+    let id = font.glyph_id(c);
+    if id.0 == 0 {
+        return None;
+    }
+
+    if let Some(raster) = font.glyph_raster_image2(id, size as u16) {
+        if matches!(raster.format, GlyphImageFormat::Png) {
+            let img = image::load_from_memory(raster.data).ok()?;
+            return Some(
+                img.resize(size, size, image::imageops::FilterType::Lanczos3)
+                    .to_rgba8(),
+            );
+        }
+    }
+
+    let mut canvas = RgbaImage::new(size, size);
+    draw_text_mut(
+        &mut canvas,
+        color,
+        0,
+        0,
+        PxScale::from(size as f32),
+        font,
+        &c.to_string(),
+    );
+    Some(canvas)
+}
+
 impl ButtonRenderer {
     /// Create a new renderer for the given button dimensions.
     pub fn new(button_width: u32, button_height: u32) -> Result<Self> {
@@ -44,107 +135,233 @@ impl ButtonRenderer {
     }
 
     /// Resize an image to fit the button dimensions.
-    /// Uses aspect-preserving resize and centers the image.
-    pub fn resize(&self, img: DynamicImage) -> DynamicImage {
+    /// Uses aspect-preserving resize and centers the image on `background`,
+    /// which also shows through transparent areas.
+    pub fn resize(&self, img: DynamicImage, background: Rgba<u8>) -> RgbaImage {
         let (target_w, target_h) = self.button_size;
 
         // Resize to fit within button, preserving aspect ratio
         let resized = img.resize(target_w, target_h, image::imageops::FilterType::Lanczos3);
 
-        // If the resized image is smaller than target, center it on a black background
-        if resized.width() < target_w || resized.height() < target_h {
-            let mut canvas = RgbaImage::from_pixel(target_w, target_h, Rgba([0, 0, 0, 255]));
-            let x_offset = (target_w - resized.width()) / 2;
-            let y_offset = (target_h - resized.height()) / 2;
-
-            image::imageops::overlay(&mut canvas, &resized.to_rgba8(), x_offset as i64, y_offset as i64);
-            DynamicImage::ImageRgba8(canvas)
-        } else {
-            resized
-        }
+        let mut canvas = RgbaImage::from_pixel(target_w, target_h, background);
+        let x_offset = (target_w - resized.width()) / 2;
+        let y_offset = (target_h - resized.height()) / 2;
+        image::imageops::overlay(
+            &mut canvas,
+            &resized.to_rgba8(),
+            x_offset as i64,
+            y_offset as i64,
+        );
+        canvas
     }
 
     /// Add a label to the bottom of the image.
     pub fn add_label(&self, img: &mut RgbaImage, label: &str) {
-        if label.is_empty() {
-            return;
-        }
+        let style = TextStyle {
+            color: WHITE,
+            outline: None,
+            font_size: self.label_size(),
+            position: LabelPosition::Bottom,
+        };
+        self.draw_label_over(img, label, &style);
+    }
 
+    /// Draw a label over a key's image or emoji, fitted into a band along the
+    /// top or bottom third so wrapped lines don't grow over the picture.
+    /// Middle labels are placed over it on purpose and get the whole key.
+    fn draw_label_over(&self, img: &mut RgbaImage, label: &str, style: &TextStyle) {
         let (w, h) = (img.width(), img.height());
+        let band = h / 3;
+        let area = match style.position {
+            LabelPosition::Top => (0, 0, w, band),
+            LabelPosition::Middle => (0, 0, w, h),
+            LabelPosition::Bottom => (0, h - band, w, band),
+        };
+        draw_text_in(img, &self.font, label, style, area);
+    }
 
-        // Calculate font size based on button height (roughly 15% of height)
-        let font_size = (h as f32 * 0.15).max(10.0);
-        let scale = PxScale::from(font_size);
+    /// Default label size under an image, roughly 15% of the key height.
+    fn label_size(&self) -> f32 {
+        (self.button_size.1 as f32 * 0.15).max(10.0)
+    }
 
-        // Truncate label if too long
-        let max_chars = (w as usize / 8).max(4);
-        let display_label = if label.len() > max_chars {
-            format!("{}...", &label[..max_chars.saturating_sub(3)])
+    /// Draw a binding's value over a key image as a `gauge` in `color`
+    /// (default blue).
+    pub fn add_gauge(
+        &self,
+        img: &mut RgbaImage,
+        gauge: Gauge,
+        value: &BindingValue,
+        color: Option<&str>,
+    ) {
+        let color = color.and_then(parse_color).unwrap_or(gauge::ACCENT);
+        let (w, h) = (img.width(), img.height());
+        let thickness = (w.min(h) as f32 / 14.0).max(3.0);
+        let center = (w as f32 / 2.0, h as f32 / 2.0);
+        let radius = w.min(h) as f32 / 2.0 - thickness;
+        match gauge {
+            Gauge::Arc => draw_arc(img, center, radius, thickness, value.fraction(), color),
+            Gauge::Ring => draw_ring(img, center, radius, thickness, value.fraction(), color),
+            Gauge::Bar => {
+                let pad = thickness as u32;
+                draw_bar(
+                    img,
+                    pad,
+                    h.saturating_sub(pad * 2),
+                    w.saturating_sub(pad * 2),
+                    pad,
+                    value.fraction(),
+                    color,
+                );
+            }
+            Gauge::Fill => draw_fill_up(img, value.fraction(), color),
+        }
+    }
+
+    /// Draw a green checkmark or red cross over the middle of a key image.
+    pub fn add_result_badge(&self, img: &mut RgbaImage, ok: bool) {
+        let (cx, cy) = (img.width() as f32 / 2.0, img.height() as f32 / 2.0);
+        let r = img.width().min(img.height()) as f32 * 0.3;
+        let color = if ok {
+            Rgba([46, 160, 67, 255])
         } else {
-            label.to_string()
+            Rgba([218, 54, 51, 255])
         };
-
-        // Estimate text width for centering
-        let char_width = font_size * 0.6;
-        let text_width = display_label.len() as f32 * char_width;
-        let x = ((w as f32 - text_width) / 2.0).max(2.0) as i32;
-
-        // Position text near bottom with some padding
-        let y = (h as f32 - font_size - 4.0).max(0.0) as i32;
-
-        // Draw text shadow for visibility
-        draw_text_mut(
-            img,
-            Rgba([0, 0, 0, 200]),
-            x + 1,
-            y + 1,
-            scale,
-            &self.font,
-            &display_label,
-        );
-
-        // Draw main text in white
-        draw_text_mut(
-            img,
-            Rgba([255, 255, 255, 255]),
-            x,
-            y,
-            scale,
-            &self.font,
-            &display_label,
-        );
+        draw_filled_circle_mut(img, (cx as i32, cy as i32), r as i32, color);
+
+        let strokes = if ok {
+            let corner = (cx - r * 0.15, cy + r * 0.4);
+            [
+                ((cx - r * 0.5, cy), corner),
+                (corner, (cx + r * 0.5, cy - r * 0.35)),
+            ]
+        } else {
+            let d = r * 0.45;
+            [
+                ((cx - d, cy - d), (cx + d, cy + d)),
+                ((cx - d, cy + d), (cx + d, cy - d)),
+            ]
+        };
+        let half_width = (r / 10.0).max(1.0);
+        for (start, end) in strokes {
+            draw_thick_line(img, start, end, half_width, Rgba([255, 255, 255, 255]));
+        }
     }
 
-    /// Render a binding's button image, if configured.
-    /// Returns None if no button_image is set.
+    /// Render a binding's key: its image, or its emoji icon and label on a
+    /// solid background.
+    /// Returns None if the binding has nothing to draw.
     pub fn render_binding(&self, binding: &Binding) -> Result<Option<DynamicImage>> {
-        let image_source = match &binding.button_image {
-            Some(src) if !src.is_empty() => src,
-            _ => return Ok(None),
-        };
+        if let Some(image_source) = binding
+            .button_image
+            .as_deref()
+            .filter(|source| !source.is_empty())
+        {
+            // Load image with optional SVG colorization
+            let img = image_cache::load_cached_with_color(
+                image_source,
+                binding.icon_color.as_deref(),
+                self.button_size.0,
+            )?;
+            return Ok(Some(self.render_with_image(binding, img)));
+        }
 
-        // Load image with optional SVG colorization
-        let img = image_cache::load_cached_with_color(
-            image_source,
-            binding.icon_color.as_deref(),
-            self.button_size.0,
-        )?;
-        let resized = self.resize(img);
-        let mut rgba = resized.to_rgba8();
+        let (w, h) = self.button_size;
+        let background = binding.background_color.as_deref().and_then(parse_color);
+        let mut style = label_style(binding, self.label_size(), LabelPosition::Bottom);
+        let label = shown_label(binding);
+        let emoji = binding.icon.as_deref().and_then(|icon| {
+            // Leave room for the label below the emoji
+            let size = if label.is_some() {
+                h * 11 / 20
+            } else {
+                h * 7 / 10
+            };
+            render_emoji(icon, size, style.color)
+        });
+        if background.is_none() && emoji.is_none() && label.is_none() {
+            return Ok(None);
+        }
+
+        let mut canvas = RgbaImage::from_pixel(w, h, background.unwrap_or(BLACK));
+        let has_emoji = emoji.is_some();
+        if let Some(emoji) = emoji {
+            let x = (w.saturating_sub(emoji.width())) / 2;
+            let y = match (label, style.position) {
+                (None, _) => (h.saturating_sub(emoji.height())) / 2,
+                (Some(_), LabelPosition::Top) => h.saturating_sub(emoji.height() + h / 10),
+                (Some(_), _) => h / 10,
+            };
+            image::imageops::overlay(&mut canvas, &emoji, x as i64, y as i64);
+        } else if label.is_some() {
+            // Text-only key: larger text, centred unless placed explicitly
+            style = label_style(binding, (h as f32 * 0.2).max(12.0), LabelPosition::Middle);
+        }
 
         // Add label if requested
-        if binding.show_label.unwrap_or(false) {
-            if let Some(label) = &binding.label {
-                self.add_label(&mut rgba, label);
+        match label {
+            Some(label) if has_emoji => self.draw_label_over(&mut canvas, label, &style),
+            Some(label) => draw_text(&mut canvas, &self.font, label, &style),
+            None => {}
+        }
+
+        Ok(Some(DynamicImage::ImageRgba8(canvas)))
+    }
+
+    /// Frames of the binding's image if it is an animated GIF or APNG.
+    pub fn load_animation(&self, binding: &Binding) -> Result<Option<Frames>> {
+        match binding.button_image.as_deref() {
+            Some(source) if !source.is_empty() => {
+                image_cache::load_animation_cached(source, self.button_size.0)
             }
+            _ => Ok(None),
+        }
+    }
+
+    /// Render a binding's key around an already loaded image: a still, or
+    /// one frame of an animation.
+    pub fn render_with_image(&self, binding: &Binding, img: DynamicImage) -> DynamicImage {
+        let background = binding
+            .background_color
+            .as_deref()
+            .and_then(parse_color)
+            .unwrap_or(BLACK);
+        let mut rgba = self.resize(img, background);
+        if let Some(label) = shown_label(binding) {
+            let style = label_style(binding, self.label_size(), LabelPosition::Bottom);
+            self.draw_label_over(&mut rgba, label, &style);
         }
+        DynamicImage::ImageRgba8(rgba)
+    }
 
-        Ok(Some(DynamicImage::ImageRgba8(rgba)))
+    /// Render a page background, cropped to fill the button.
+    pub fn render_background(&self, source: &str) -> Result<DynamicImage> {
+        let (w, h) = self.button_size;
+        let img = image_cache::load_cached_with_color(source, None, w.max(h) * 2)?;
+        Ok(img.resize_to_fill(w, h, image::imageops::FilterType::Lanczos3))
+    }
+
+    /// Render the back key shown in the first slot while a folder is open.
+    pub fn create_back_button(&self) -> DynamicImage {
+        let (w, h) = self.button_size;
+        let mut img = RgbaImage::from_pixel(w, h, Rgba([40, 40, 40, 255]));
+
+        // Left-pointing arrow above the label
+        let (cx, cy) = (w as i32 / 2, h as i32 * 2 / 5);
+        let size = (w.min(h) as i32 / 5).max(4);
+        let arrow = [
+            Point::new(cx - size, cy),
+            Point::new(cx + size / 2, cy - size),
+            Point::new(cx + size / 2, cy + size),
+        ];
+        draw_polygon_mut(&mut img, &arrow, Rgba([255, 255, 255, 255]));
+
+        self.add_label(&mut img, "Back");
+        DynamicImage::ImageRgba8(img)
     }
 
     /// Create a simple colored background with optional text.
-    /// Useful for testing or fallback.
-    #[allow(dead_code)]
+    /// Used for text-only keys and as a fallback.
     pub fn create_solid_button(&self, color: Rgba<u8>, label: Option<&str>) -> DynamicImage {
         let (w, h) = self.button_size;
         let mut img = RgbaImage::from_pixel(w, h, color);
@@ -157,6 +374,55 @@ impl ButtonRenderer {
     }
 }
 
+/// A binding's label if it should be drawn on hardware.
+fn shown_label(binding: &Binding) -> Option<&str> {
+    binding
+        .label
+        .as_deref()
+        .filter(|label| binding.show_label.unwrap_or(false) && !label.trim().is_empty())
+}
+
+/// Text style for a binding's label; `font_size` and `position` apply where
+/// the binding doesn't set its own.
+fn label_style(binding: &Binding, font_size: f32, position: LabelPosition) -> TextStyle {
+    TextStyle {
+        color: binding
+            .text_color
+            .as_deref()
+            .and_then(parse_color)
+            .unwrap_or(WHITE),
+        outline: binding.outline_color.as_deref().and_then(parse_color),
+        font_size: binding.font_size.map_or(font_size, |size| size as f32),
+        position: binding.label_position.unwrap_or(position),
+    }
+}
+
+/// Draw a straight line `2 * half_width` pixels wide as a filled quad.
+fn draw_thick_line(
+    img: &mut RgbaImage,
+    start: (f32, f32),
+    end: (f32, f32),
+    half_width: f32,
+    color: Rgba<u8>,
+) {
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let len = (dx * dx + dy * dy).sqrt().max(f32::EPSILON);
+    let (nx, ny) = (-dy / len * half_width, dx / len * half_width);
+    let corner = |(x, y): (f32, f32), sign: f32| {
+        Point::new(
+            (x + sign * nx).round() as i32,
+            (y + sign * ny).round() as i32,
+        )
+    };
+    let quad = [
+        corner(start, 1.0),
+        corner(end, 1.0),
+        corner(end, -1.0),
+        corner(start, -1.0),
+    ];
+    draw_polygon_mut(img, &quad, color);
+}
+
 /// Get button size for a Stream Deck kind.
 pub fn button_size_for_kind(kind: elgato_streamdeck::info::Kind) -> (u32, u32) {
     let (w, h) = kind.key_image_format().size;
@@ -210,50 +476,65 @@ impl LcdRenderer {
             let x_offset = (target_w - resized.width()) / 2;
             let y_offset = (target_h - resized.height()) / 2;
 
-            image::imageops::overlay(&mut canvas, &resized.to_rgba8(), x_offset as i64, y_offset as i64);
+            image::imageops::overlay(
+                &mut canvas,
+                &resized.to_rgba8(),
+                x_offset as i64,
+                y_offset as i64,
+            );
             DynamicImage::ImageRgba8(canvas)
         } else {
             resized
         }
     }
 
-    /// Add a label to the bottom of the image.
-    pub fn add_label(&self, img: &mut RgbaImage, label: &str) {
-        if label.is_empty() {
-            return;
-        }
-
-        let (w, h) = (img.width(), img.height());
-        let font_size = (h as f32 * 0.18).max(12.0);
-        let scale = PxScale::from(font_size);
-
-        let max_chars = (w as usize / 10).max(4);
-        let display_label = if label.len() > max_chars {
-            format!("{}...", &label[..max_chars.saturating_sub(3)])
-        } else {
-            label.to_string()
-        };
+    /// Default label size under an image, roughly 18% of the section height.
+    fn label_size(&self) -> f32 {
+        (self.section_size.1 as f32 * 0.18).max(12.0)
+    }
 
-        let char_width = font_size * 0.6;
-        let text_width = display_label.len() as f32 * char_width;
-        let x = ((w as f32 - text_width) / 2.0).max(2.0) as i32;
-        let y = (h as f32 - font_size - 6.0).max(0.0) as i32;
+    /// Render a page's touch strip background across `sections` encoders,
+    /// cut into one image per section.
+    pub fn render_strip_background(&self, source: &str, sections: u32) -> Result<Vec<RgbaImage>> {
+        let (w, h) = self.section_size;
+        let strip_w = w * sections;
+        let img = image_cache::load_cached_with_color(source, None, strip_w)?;
+        let strip = img
+            .resize_to_fill(strip_w, h, image::imageops::FilterType::Lanczos3)
+            .to_rgba8();
+        Ok((0..sections)
+            .map(|i| image::imageops::crop_imm(&strip, i * w, 0, w, h).to_image())
+            .collect())
+    }
 
-        // Shadow
-        draw_text_mut(img, Rgba([0, 0, 0, 200]), x + 1, y + 1, scale, &self.font, &display_label);
-        // Main text
-        draw_text_mut(img, Rgba([255, 255, 255, 255]), x, y, scale, &self.font, &display_label);
+    /// A blank section: the binding's background color, else the strip
+    /// background behind it, else black.
+    fn canvas(&self, binding: &Binding, background: Option<&RgbaImage>) -> RgbaImage {
+        let (w, h) = self.section_size;
+        match (
+            binding.background_color.as_deref().and_then(parse_color),
+            background,
+        ) {
+            (Some(color), _) => RgbaImage::from_pixel(w, h, color),
+            (None, Some(background)) if background.dimensions() == (w, h) => background.clone(),
+            (None, _) => RgbaImage::from_pixel(w, h, BLACK),
+        }
     }
 
-    /// Render an encoder's LCD section from a binding.
-    pub fn render_binding(&self, binding: &Binding) -> Result<Option<DynamicImage>> {
+    /// Render an encoder's LCD section from a binding, over the strip
+    /// `background` if the page has one.
+    /// Sections without an image show their label centred instead.
+    pub fn render_binding(
+        &self,
+        binding: &Binding,
+        background: Option<&RgbaImage>,
+    ) -> Result<Option<DynamicImage>> {
+        let section_h = self.section_size.1;
         let image_source = match &binding.button_image {
             Some(src) if !src.is_empty() => src,
-            _ => return Ok(None),
+            _ => return Ok(self.render_text(binding, background)),
         };
 
-        let (section_w, section_h) = self.section_size;
-
         // SVGs get 70% height (with padding), PNGs/other images fill full height
         let is_svg = image_source.to_lowercase().ends_with(".svg")
             || (image_source.contains("/icons/") && image_source.contains("lucide"));
@@ -269,21 +550,165 @@ impl LcdRenderer {
             icon_size,
         )?;
 
-        // Center the icon on a black canvas (don't scale up)
-        let mut canvas = RgbaImage::from_pixel(section_w, section_h, Rgba([0, 0, 0, 255]));
-        let x_offset = (section_w.saturating_sub(img.width())) / 2;
-        let y_offset = (section_h.saturating_sub(img.height())) / 2;
-        image::imageops::overlay(&mut canvas, &img.to_rgba8(), x_offset as i64, y_offset as i64);
+        // Center the icon on the section (don't scale up)
+        let mut canvas = self.canvas(binding, background);
+        let x_offset = (canvas.width().saturating_sub(img.width())) / 2;
+        let y_offset = (canvas.height().saturating_sub(img.height())) / 2;
+        image::imageops::overlay(
+            &mut canvas,
+            &img.to_rgba8(),
+            x_offset as i64,
+            y_offset as i64,
+        );
 
-        if binding.show_label.unwrap_or(false) {
-            if let Some(label) = &binding.label {
-                self.add_label(&mut canvas, label);
-            }
+        if let Some(label) = shown_label(binding) {
+            let style = label_style(binding, self.label_size(), LabelPosition::Bottom);
+            draw_text(&mut canvas, &self.font, label, &style);
         }
 
         Ok(Some(DynamicImage::ImageRgba8(canvas)))
     }
 
+    /// Render a section showing only the binding's label, centred in large text.
+    fn render_text(
+        &self,
+        binding: &Binding,
+        background: Option<&RgbaImage>,
+    ) -> Option<DynamicImage> {
+        let label = shown_label(binding)?;
+        let h = self.section_size.1;
+        let style = label_style(binding, (h as f32 * 0.35).max(14.0), LabelPosition::Middle);
+
+        let mut canvas = self.canvas(binding, background);
+        draw_text(&mut canvas, &self.font, label, &style);
+        Some(DynamicImage::ImageRgba8(canvas))
+    }
+
+    /// Render a section as a gauge widget: the binding's `value` in its icon
+    /// color, the value's readout, and its label as a title.
+    pub fn render_gauge(
+        &self,
+        binding: &Binding,
+        value: &BindingValue,
+        gauge: Gauge,
+        background: Option<&RgbaImage>,
+    ) -> DynamicImage {
+        let (w, h) = self.section_size;
+        let mut canvas = self.canvas(binding, background);
+        let color = binding
+            .icon_color
+            .as_deref()
+            .and_then(parse_color)
+            .unwrap_or(gauge::ACCENT);
+        let title = shown_label(binding);
+        let readout = TextStyle {
+            position: LabelPosition::Middle,
+            ..label_style(binding, h as f32 * 0.24, LabelPosition::Middle)
+        };
+
+        match gauge {
+            Gauge::Arc | Gauge::Ring => {
+                // Dial in a square on the left, readout inside it, title beside it
+                let thickness = (h as f32 / 10.0).max(3.0);
+                let radius = h as f32 / 2.0 - thickness;
+                let center = (h as f32 / 2.0, h as f32 / 2.0);
+                if gauge == Gauge::Arc {
+                    draw_arc(
+                        &mut canvas,
+                        center,
+                        radius,
+                        thickness,
+                        value.fraction(),
+                        color,
+                    );
+                } else {
+                    draw_ring(
+                        &mut canvas,
+                        center,
+                        radius,
+                        thickness,
+                        value.fraction(),
+                        color,
+                    );
+                }
+
+                let inner = (radius * 1.3) as u32;
+                let offset = (h - inner) / 2;
+                draw_text_in(
+                    &mut canvas,
+                    &self.font,
+                    &value.display,
+                    &readout,
+                    (offset, offset, inner, inner),
+                );
+                if let Some(title) = title {
+                    let style = label_style(binding, self.label_size(), LabelPosition::Middle);
+                    draw_text_in(
+                        &mut canvas,
+                        &self.font,
+                        title,
+                        &style,
+                        (h, 0, w.saturating_sub(h), h),
+                    );
+                }
+            }
+            Gauge::Bar | Gauge::Fill => {
+                // Bar along the bottom or a level across the section, title
+                // and readout stacked in the space left
+                let text_h = if gauge == Gauge::Bar {
+                    let pad = h / 10;
+                    let bar_h = (h / 8).max(4);
+                    let bar_y = h - pad - bar_h;
+                    draw_bar(
+                        &mut canvas,
+                        pad,
+                        bar_y,
+                        w.saturating_sub(pad * 2),
+                        bar_h,
+                        value.fraction(),
+                        color,
+                    );
+                    bar_y
+                } else {
+                    draw_fill_across(&mut canvas, value.fraction(), color);
+                    h
+                };
+
+                match title {
+                    Some(title) => {
+                        let style = TextStyle {
+                            position: LabelPosition::Middle,
+                            ..label_style(binding, self.label_size(), LabelPosition::Middle)
+                        };
+                        draw_text_in(
+                            &mut canvas,
+                            &self.font,
+                            title,
+                            &style,
+                            (0, 0, w, text_h / 2),
+                        );
+                        draw_text_in(
+                            &mut canvas,
+                            &self.font,
+                            &value.display,
+                            &readout,
+                            (0, text_h / 2, w, text_h - text_h / 2),
+                        );
+                    }
+                    None => draw_text_in(
+                        &mut canvas,
+                        &self.font,
+                        &value.display,
+                        &readout,
+                        (0, 0, w, text_h),
+                    ),
+                }
+            }
+        }
+
+        DynamicImage::ImageRgba8(canvas)
+    }
+
     /// Create a black/empty section.
     pub fn create_empty(&self) -> DynamicImage {
         let (w, h) = self.section_size;
@@ -294,6 +719,8 @@ impl LcdRenderer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::binding::InputRef;
+    use crate::capability::Capability;
 
     #[test]
     fn test_url_detection() {
@@ -301,8 +728,7 @@ mod tests {
 
         // This just tests the detection logic, not actual loading
         assert!(
-            "https://example.com/img.png"
-                .starts_with("http://")
+            "https://example.com/img.png".starts_with("http://")
                 || "https://example.com/img.png".starts_with("https://")
         );
         assert!(!"./local/image.png".starts_with("http"));
@@ -353,6 +779,16 @@ mod tests {
         assert_eq!(img.height(), 72);
     }
 
+    #[test]
+    fn test_create_back_button() {
+        let renderer = ButtonRenderer::new(72, 72).unwrap();
+        let img = renderer.create_back_button().to_rgba8();
+
+        assert_eq!(img.dimensions(), (72, 72));
+        // Arrow is drawn in white near the centre
+        assert_eq!(img.get_pixel(30, 28), &Rgba([255, 255, 255, 255]));
+    }
+
     #[test]
     fn test_lcd_renderer_create_empty() {
         let renderer = LcdRenderer::new(200, 100).unwrap();
@@ -370,10 +806,11 @@ mod tests {
     #[test]
     fn test_resize_smaller_image() {
         // Create a small 10x10 image
-        let small_img = DynamicImage::ImageRgba8(RgbaImage::from_pixel(10, 10, Rgba([255, 0, 0, 255])));
+        let small_img =
+            DynamicImage::ImageRgba8(RgbaImage::from_pixel(10, 10, Rgba([255, 0, 0, 255])));
 
         let renderer = ButtonRenderer::new(72, 72).unwrap();
-        let resized = renderer.resize(small_img);
+        let resized = renderer.resize(small_img, BLACK);
 
         // Should be padded to target size
         assert_eq!(resized.width(), 72);
@@ -383,10 +820,11 @@ mod tests {
     #[test]
     fn test_resize_larger_image() {
         // Create a larger 200x200 image
-        let large_img = DynamicImage::ImageRgba8(RgbaImage::from_pixel(200, 200, Rgba([0, 255, 0, 255])));
+        let large_img =
+            DynamicImage::ImageRgba8(RgbaImage::from_pixel(200, 200, Rgba([0, 255, 0, 255])));
 
         let renderer = ButtonRenderer::new(72, 72).unwrap();
-        let resized = renderer.resize(large_img);
+        let resized = renderer.resize(large_img, BLACK);
 
         // Should be resized down
         assert!(resized.width() <= 72);
@@ -396,10 +834,11 @@ mod tests {
     #[test]
     fn test_resize_non_square_image() {
         // Create a wide image 100x50
-        let wide_img = DynamicImage::ImageRgba8(RgbaImage::from_pixel(100, 50, Rgba([0, 0, 255, 255])));
+        let wide_img =
+            DynamicImage::ImageRgba8(RgbaImage::from_pixel(100, 50, Rgba([0, 0, 255, 255])));
 
         let renderer = ButtonRenderer::new(72, 72).unwrap();
-        let resized = renderer.resize(wide_img);
+        let resized = renderer.resize(wide_img, BLACK);
 
         // Should preserve aspect ratio and fit within bounds
         assert!(resized.width() <= 72);
@@ -434,11 +873,39 @@ mod tests {
         let mut img = RgbaImage::from_pixel(72, 72, Rgba([0, 0, 0, 255]));
 
         // Long label should be truncated without crash
-        renderer.add_label(&mut img, "This is a very long label that should be truncated");
+        renderer.add_label(
+            &mut img,
+            "This is a very long label that should be truncated",
+        );
 
         assert_eq!(img.width(), 72);
     }
 
+    #[test]
+    fn test_add_label_multibyte_text() {
+        let renderer = ButtonRenderer::new(72, 72).unwrap();
+        let mut img = RgbaImage::from_pixel(72, 72, Rgba([0, 0, 0, 255]));
+
+        // Cut-off text must not split a multi-byte character
+        renderer.add_label(&mut img, "Lautstärke größer änderungsfähig 🎉 über alles");
+
+        // Shrunk to the smallest size, so no glyph pixel is fully covered
+        assert!(img.pixels().any(|p| p[0] > 200));
+    }
+
+    #[test]
+    fn test_bottom_label_stays_below_image() {
+        let renderer = ButtonRenderer::new(72, 72).unwrap();
+        let icon = DynamicImage::ImageRgba8(RgbaImage::from_pixel(72, 72, Rgba([200, 0, 0, 255])));
+        let binding = text_binding(Some("Start\nRecording"), None);
+
+        let img = renderer.render_with_image(&binding, icon).to_rgba8();
+
+        // Both lines fit the bottom third; the icon above is untouched
+        assert!((0..48).all(|y| (0..72).all(|x| *img.get_pixel(x, y) == Rgba([200, 0, 0, 255]))));
+        assert!((48..72).any(|y| (0..72).any(|x| img.get_pixel(x, y)[1] > 200)));
+    }
+
     #[test]
     fn test_url_http_detection() {
         let source = "http://example.com/image.png";
@@ -466,4 +933,112 @@ mod tests {
         let is_url = source.starts_with("http://") || source.starts_with("https://");
         assert!(!is_url);
     }
+
+    #[test]
+    fn test_result_badge_colors_the_key() {
+        let renderer = ButtonRenderer::new(72, 72).unwrap();
+        // Inside the badge, clear of the strokes
+        let probe = |ok| {
+            let mut img = RgbaImage::from_pixel(72, 72, Rgba([0, 0, 0, 255]));
+            renderer.add_result_badge(&mut img, ok);
+            *img.get_pixel(36, 24)
+        };
+        assert_eq!(probe(true), Rgba([46, 160, 67, 255]));
+        assert_eq!(probe(false), Rgba([218, 54, 51, 255]));
+    }
+
+    fn text_binding(label: Option<&str>, background_color: Option<&str>) -> Binding {
+        Binding {
+            label: label.map(String::from),
+            show_label: Some(true),
+            background_color: background_color.map(String::from),
+            ..Binding::new(InputRef::Button { index: 0 }, Capability::MediaPlayPause)
+        }
+    }
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("#1e90ff"), Some(Rgba([30, 144, 255, 255])));
+        assert_eq!(parse_color("f00"), Some(Rgba([255, 0, 0, 255])));
+        assert_eq!(parse_color("#00000080"), Some(Rgba([0, 0, 0, 128])));
+        assert_eq!(parse_color("#12345"), None);
+        assert_eq!(parse_color("#zzzzzz"), None);
+        assert_eq!(parse_color("#é12"), None);
+    }
+
+    #[test]
+    fn test_render_text_only_key() {
+        let renderer = ButtonRenderer::new(72, 72).unwrap();
+
+        // Nothing to draw leaves the key to the page background
+        assert!(renderer
+            .render_binding(&text_binding(None, None))
+            .unwrap()
+            .is_none());
+
+        let solid = renderer
+            .render_binding(&text_binding(None, Some("#1e90ff")))
+            .unwrap()
+            .unwrap()
+            .to_rgba8();
+        assert_eq!(solid.get_pixel(36, 36), &Rgba([30, 144, 255, 255]));
+
+        // Two lines of text, centred on the key
+        let text = renderer
+            .render_binding(&text_binding(Some("Git\nPull"), Some("#1e90ff")))
+            .unwrap()
+            .unwrap()
+            .to_rgba8();
+        let white_rows: Vec<u32> = (0..72)
+            .filter(|&y| (0..72).any(|x| text.get_pixel(x, y) == &Rgba([255, 255, 255, 255])))
+            .collect();
+        assert!(white_rows.first().unwrap() > &10 && white_rows.last().unwrap() < &62);
+        assert_eq!(text.get_pixel(1, 1), &Rgba([30, 144, 255, 255]));
+    }
+
+    #[test]
+    fn test_lcd_gauge_widget() {
+        let renderer = LcdRenderer::new(200, 100).unwrap();
+        let background = RgbaImage::from_pixel(200, 100, Rgba([0, 80, 0, 255]));
+        let binding = Binding {
+            icon_color: Some("#ff0000".to_string()),
+            ..text_binding(Some("Volume"), None)
+        };
+        let img = renderer
+            .render_gauge(
+                &binding,
+                &BindingValue::level(0.25),
+                Gauge::Bar,
+                Some(&background),
+            )
+            .to_rgba8();
+
+        // Bar filled a quarter of the way, over the strip background
+        let bar_y = 100 - 10 - 12 / 2;
+        assert_eq!(img.get_pixel(20, bar_y), &Rgba([255, 0, 0, 255]));
+        assert_eq!(img.get_pixel(150, bar_y), &gauge::TRACK);
+        assert_eq!(img.get_pixel(2, 2), &Rgba([0, 80, 0, 255]));
+        assert!(img.pixels().any(|p| p == &Rgba([255, 255, 255, 255])));
+    }
+
+    #[test]
+    fn test_add_gauge_over_key() {
+        let renderer = ButtonRenderer::new(72, 72).unwrap();
+        let mut img = RgbaImage::from_pixel(72, 72, BLACK);
+        renderer.add_gauge(
+            &mut img,
+            Gauge::Bar,
+            &BindingValue::level(0.5),
+            Some("#ff0000"),
+        );
+
+        // Half the bar along the bottom is filled, the key above it untouched
+        assert_eq!(img.get_pixel(10, 64), &Rgba([255, 0, 0, 255]));
+        assert_eq!(img.get_pixel(60, 64), &gauge::TRACK);
+        assert_eq!(img.get_pixel(36, 36), &BLACK);
+
+        // A key smaller than the padding just gets no bar
+        let mut tiny = RgbaImage::from_pixel(4, 4, BLACK);
+        renderer.add_gauge(&mut tiny, Gauge::Bar, &BindingValue::level(0.5), None);
+    }
 }
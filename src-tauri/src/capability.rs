@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Brightness change percentage per encoder tick for Key Lights
 pub const KEY_LIGHT_BRIGHTNESS_STEP: i32 = 2;
@@ -46,34 +47,114 @@ pub enum Capability {
     /// System audio control (for encoders)
     /// - Encoder rotation: adjust volume
     /// - Encoder press: toggle mute
-    SystemAudio { step: f32 },
+    SystemAudio {
+        step: f32,
+    },
     /// Toggle system mute (for buttons)
     Mute,
     /// Increase system volume (for buttons)
-    VolumeUp { step: f32 },
+    VolumeUp {
+        step: f32,
+    },
     /// Decrease system volume (for buttons)
-    VolumeDown { step: f32 },
+    VolumeDown {
+        step: f32,
+    },
     /// Microphone control (for encoders)
     /// - Encoder rotation: adjust mic volume
     /// - Encoder press: toggle mic mute
-    Microphone { step: f32 },
+    Microphone {
+        step: f32,
+    },
     /// Toggle mic mute (for buttons)
     MicMute,
     /// Increase mic volume (for buttons)
-    MicVolumeUp { step: f32 },
+    MicVolumeUp {
+        step: f32,
+    },
     /// Decrease mic volume (for buttons)
-    MicVolumeDown { step: f32 },
+    MicVolumeDown {
+        step: f32,
+    },
     MediaPlayPause,
     MediaNext,
     MediaPrevious,
     MediaStop,
     RunCommand {
+        #[serde(default)]
         command: String,
         #[serde(default)]
         toggle: bool,
+        /// Probe run by the state poller to decide whether the key is active
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        state_command: Option<String>,
+        /// Active when the probe prints this (trimmed) instead of when it exits 0
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        state_match: Option<String>,
+        /// Run instead of `command` when the key is inactive
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        on_command: Option<String>,
+        /// Run instead of `command` when the key is active
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        off_command: Option<String>,
+        /// Working directory (`~` is the home directory)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cwd: Option<String>,
+        /// Extra environment variables
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        env: BTreeMap<String, String>,
+        /// Kill the command if it is still running after this long (milliseconds)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_ms: Option<u64>,
+        /// Kill the command when the key is released
+        #[serde(default, skip_serializing_if = "is_false")]
+        kill_on_release: bool,
+    },
+    /// Show a command's output (first line) on the key, refreshed periodically and on press
+    CommandDisplay {
+        command: String,
+        /// Refresh interval in milliseconds (0 = only on press)
+        #[serde(default = "default_display_interval")]
+        interval_ms: u64,
+        /// The command is killed if it runs longer than this (milliseconds)
+        #[serde(default = "default_display_timeout")]
+        timeout_ms: u64,
+        /// Longer output is cut to this many characters, ending in an ellipsis
+        #[serde(default = "default_display_max_length")]
+        max_length: usize,
+    },
+    LaunchApp {
+        command: String,
+    },
+    OpenURL {
+        url: String,
+    },
+    /// Press a key combination like `ctrl+shift+t` on a virtual keyboard
+    SendHotkey {
+        keys: String,
+    },
+    /// Type text on a virtual keyboard
+    TypeText {
+        text: String,
+    },
+    /// Open a named folder of sub-pages
+    OpenFolder {
+        name: String,
+    },
+    /// Go to the next page (stops at the empty page after the last bound one)
+    NextPage,
+    /// Go to the previous page
+    PreviousPage,
+    /// Jump to a specific page (0-indexed)
+    GoToPage {
+        page: usize,
+    },
+    /// Scroll through pages by rotating an encoder
+    PageByEncoder,
+    /// Run several capabilities in order on one press
+    Macro {
+        steps: Vec<MacroStep>,
     },
-    LaunchApp { command: String },
-    OpenURL { url: String },
     ElgatoKeyLight {
         ip: String,
         #[serde(default = "default_key_light_port")]
@@ -172,6 +253,23 @@ pub enum Capability {
     },
 }
 
+/// One step of a `Capability::Macro`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MacroStep {
+    pub capability: Capability,
+    /// Pause before this step runs (milliseconds)
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub delay_ms: u64,
+}
+
+fn is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
 fn default_key_light_port() -> u16 {
     9123
 }
@@ -188,6 +286,18 @@ fn default_obs_audio_step() -> f32 {
     OBS_AUDIO_STEP
 }
 
+fn default_display_interval() -> u64 {
+    5000
+}
+
+fn default_display_timeout() -> u64 {
+    2000
+}
+
+fn default_display_max_length() -> usize {
+    10
+}
+
 /// Effects produced when a capability is triggered.
 ///
 /// These are the concrete actions to be executed by the effect handler.
@@ -205,29 +315,120 @@ pub enum CapabilityEffect {
     RunCommand(String),
     LaunchApp(String),
     OpenURL(String),
-    KeyLightToggle { ip: String, port: u16 },
-    KeyLightOn { ip: String, port: u16 },
-    KeyLightOff { ip: String, port: u16 },
-    KeyLightBrightness { ip: String, port: u16, delta: i32 },
+    KeyLightToggle {
+        ip: String,
+        port: u16,
+    },
+    KeyLightOn {
+        ip: String,
+        port: u16,
+    },
+    KeyLightOff {
+        ip: String,
+        port: u16,
+    },
+    KeyLightBrightness {
+        ip: String,
+        port: u16,
+        delta: i32,
+    },
     // OBS effects
-    OBSSetScene { host: String, port: u16, password: Option<String>, scene: String },
-    OBSStreamToggle { host: String, port: u16, password: Option<String> },
-    OBSStreamStart { host: String, port: u16, password: Option<String> },
-    OBSStreamStop { host: String, port: u16, password: Option<String> },
-    OBSRecordToggle { host: String, port: u16, password: Option<String> },
-    OBSRecordStart { host: String, port: u16, password: Option<String> },
-    OBSRecordStop { host: String, port: u16, password: Option<String> },
-    OBSRecordTogglePause { host: String, port: u16, password: Option<String> },
-    OBSToggleSourceVisibility { host: String, port: u16, password: Option<String>, scene: String, source: String },
-    OBSToggleMute { host: String, port: u16, password: Option<String>, input_name: String },
-    OBSVolumeDelta { host: String, port: u16, password: Option<String>, input_name: String, delta: f32 },
-    OBSToggleStudioMode { host: String, port: u16, password: Option<String> },
-    OBSReplayToggle { host: String, port: u16, password: Option<String> },
-    OBSReplayStart { host: String, port: u16, password: Option<String> },
-    OBSReplayStop { host: String, port: u16, password: Option<String> },
-    OBSReplaySave { host: String, port: u16, password: Option<String> },
-    OBSToggleVirtualCam { host: String, port: u16, password: Option<String> },
-    OBSTriggerTransition { host: String, port: u16, password: Option<String> },
+    OBSSetScene {
+        host: String,
+        port: u16,
+        password: Option<String>,
+        scene: String,
+    },
+    OBSStreamToggle {
+        host: String,
+        port: u16,
+        password: Option<String>,
+    },
+    OBSStreamStart {
+        host: String,
+        port: u16,
+        password: Option<String>,
+    },
+    OBSStreamStop {
+        host: String,
+        port: u16,
+        password: Option<String>,
+    },
+    OBSRecordToggle {
+        host: String,
+        port: u16,
+        password: Option<String>,
+    },
+    OBSRecordStart {
+        host: String,
+        port: u16,
+        password: Option<String>,
+    },
+    OBSRecordStop {
+        host: String,
+        port: u16,
+        password: Option<String>,
+    },
+    OBSRecordTogglePause {
+        host: String,
+        port: u16,
+        password: Option<String>,
+    },
+    OBSToggleSourceVisibility {
+        host: String,
+        port: u16,
+        password: Option<String>,
+        scene: String,
+        source: String,
+    },
+    OBSToggleMute {
+        host: String,
+        port: u16,
+        password: Option<String>,
+        input_name: String,
+    },
+    OBSVolumeDelta {
+        host: String,
+        port: u16,
+        password: Option<String>,
+        input_name: String,
+        delta: f32,
+    },
+    OBSToggleStudioMode {
+        host: String,
+        port: u16,
+        password: Option<String>,
+    },
+    OBSReplayToggle {
+        host: String,
+        port: u16,
+        password: Option<String>,
+    },
+    OBSReplayStart {
+        host: String,
+        port: u16,
+        password: Option<String>,
+    },
+    OBSReplayStop {
+        host: String,
+        port: u16,
+        password: Option<String>,
+    },
+    OBSReplaySave {
+        host: String,
+        port: u16,
+        password: Option<String>,
+    },
+    OBSToggleVirtualCam {
+        host: String,
+        port: u16,
+        password: Option<String>,
+    },
+    OBSTriggerTransition {
+        host: String,
+        port: u16,
+        password: Option<String>,
+    },
 }
 
 #[allow(dead_code)] // Reserved for future effect-based dispatch
@@ -248,7 +449,11 @@ impl Capability {
                     Some(CapabilityEffect::MicVolumeDelta(*step * delta as f32))
                 }
             }
-            Capability::ElgatoKeyLight { ip, port, action: KeyLightAction::SetBrightness } => {
+            Capability::ElgatoKeyLight {
+                ip,
+                port,
+                action: KeyLightAction::SetBrightness,
+            } => {
                 if delta == 0 {
                     None
                 } else {
@@ -259,7 +464,13 @@ impl Capability {
                     })
                 }
             }
-            Capability::OBSAudio { host, port, password, input_name, step } => {
+            Capability::OBSAudio {
+                host,
+                port,
+                password,
+                input_name,
+                step,
+            } => {
                 if delta == 0 {
                     None
                 } else {
@@ -281,11 +492,17 @@ impl Capability {
             Capability::SystemAudio { .. } if pressed => Some(CapabilityEffect::ToggleMute),
             Capability::Mute if pressed => Some(CapabilityEffect::ToggleMute),
             Capability::VolumeUp { step } if pressed => Some(CapabilityEffect::VolumeDelta(*step)),
-            Capability::VolumeDown { step } if pressed => Some(CapabilityEffect::VolumeDelta(-*step)),
+            Capability::VolumeDown { step } if pressed => {
+                Some(CapabilityEffect::VolumeDelta(-*step))
+            }
             Capability::Microphone { .. } if pressed => Some(CapabilityEffect::ToggleMicMute),
             Capability::MicMute if pressed => Some(CapabilityEffect::ToggleMicMute),
-            Capability::MicVolumeUp { step } if pressed => Some(CapabilityEffect::MicVolumeDelta(*step)),
-            Capability::MicVolumeDown { step } if pressed => Some(CapabilityEffect::MicVolumeDelta(-*step)),
+            Capability::MicVolumeUp { step } if pressed => {
+                Some(CapabilityEffect::MicVolumeDelta(*step))
+            }
+            Capability::MicVolumeDown { step } if pressed => {
+                Some(CapabilityEffect::MicVolumeDelta(-*step))
+            }
             Capability::MediaPlayPause if pressed => Some(CapabilityEffect::MediaPlayPause),
             Capability::MediaNext if pressed => Some(CapabilityEffect::MediaNext),
             Capability::MediaPrevious if pressed => Some(CapabilityEffect::MediaPrevious),
@@ -315,122 +532,251 @@ impl Capability {
                 }
             }
             // OBS capabilities
-            Capability::OBSScene { host, port, password, scene } if pressed => {
-                Some(CapabilityEffect::OBSSetScene {
+            Capability::OBSScene {
+                host,
+                port,
+                password,
+                scene,
+            } if pressed => Some(CapabilityEffect::OBSSetScene {
+                host: host.clone(),
+                port: *port,
+                password: password.clone(),
+                scene: scene.clone(),
+            }),
+            Capability::OBSStream {
+                host,
+                port,
+                password,
+                action,
+            } if pressed => match action {
+                OBSStreamAction::Toggle => Some(CapabilityEffect::OBSStreamToggle {
                     host: host.clone(),
                     port: *port,
                     password: password.clone(),
-                    scene: scene.clone(),
-                })
-            }
-            Capability::OBSStream { host, port, password, action } if pressed => {
-                match action {
-                    OBSStreamAction::Toggle => Some(CapabilityEffect::OBSStreamToggle {
-                        host: host.clone(),
-                        port: *port,
-                        password: password.clone(),
-                    }),
-                    OBSStreamAction::Start => Some(CapabilityEffect::OBSStreamStart {
-                        host: host.clone(),
-                        port: *port,
-                        password: password.clone(),
-                    }),
-                    OBSStreamAction::Stop => Some(CapabilityEffect::OBSStreamStop {
-                        host: host.clone(),
-                        port: *port,
-                        password: password.clone(),
-                    }),
-                }
-            }
-            Capability::OBSRecord { host, port, password, action } if pressed => {
-                match action {
-                    OBSRecordAction::Toggle => Some(CapabilityEffect::OBSRecordToggle {
-                        host: host.clone(),
-                        port: *port,
-                        password: password.clone(),
-                    }),
-                    OBSRecordAction::Start => Some(CapabilityEffect::OBSRecordStart {
-                        host: host.clone(),
-                        port: *port,
-                        password: password.clone(),
-                    }),
-                    OBSRecordAction::Stop => Some(CapabilityEffect::OBSRecordStop {
-                        host: host.clone(),
-                        port: *port,
-                        password: password.clone(),
-                    }),
-                    OBSRecordAction::TogglePause => Some(CapabilityEffect::OBSRecordTogglePause {
-                        host: host.clone(),
-                        port: *port,
-                        password: password.clone(),
-                    }),
-                }
-            }
-            Capability::OBSSourceVisibility { host, port, password, scene, source } if pressed => {
-                Some(CapabilityEffect::OBSToggleSourceVisibility {
+                }),
+                OBSStreamAction::Start => Some(CapabilityEffect::OBSStreamStart {
                     host: host.clone(),
                     port: *port,
                     password: password.clone(),
-                    scene: scene.clone(),
-                    source: source.clone(),
-                })
-            }
-            Capability::OBSAudio { host, port, password, input_name, .. } if pressed => {
-                Some(CapabilityEffect::OBSToggleMute {
+                }),
+                OBSStreamAction::Stop => Some(CapabilityEffect::OBSStreamStop {
                     host: host.clone(),
                     port: *port,
                     password: password.clone(),
-                    input_name: input_name.clone(),
-                })
-            }
-            Capability::OBSStudioMode { host, port, password } if pressed => {
-                Some(CapabilityEffect::OBSToggleStudioMode {
+                }),
+            },
+            Capability::OBSRecord {
+                host,
+                port,
+                password,
+                action,
+            } if pressed => match action {
+                OBSRecordAction::Toggle => Some(CapabilityEffect::OBSRecordToggle {
                     host: host.clone(),
                     port: *port,
                     password: password.clone(),
-                })
-            }
-            Capability::OBSReplayBuffer { host, port, password, action } if pressed => {
-                match action {
-                    OBSReplayAction::Toggle => Some(CapabilityEffect::OBSReplayToggle {
-                        host: host.clone(),
-                        port: *port,
-                        password: password.clone(),
-                    }),
-                    OBSReplayAction::Start => Some(CapabilityEffect::OBSReplayStart {
-                        host: host.clone(),
-                        port: *port,
-                        password: password.clone(),
-                    }),
-                    OBSReplayAction::Stop => Some(CapabilityEffect::OBSReplayStop {
-                        host: host.clone(),
-                        port: *port,
-                        password: password.clone(),
-                    }),
-                    OBSReplayAction::Save => Some(CapabilityEffect::OBSReplaySave {
-                        host: host.clone(),
-                        port: *port,
-                        password: password.clone(),
-                    }),
-                }
-            }
-            Capability::OBSVirtualCam { host, port, password } if pressed => {
-                Some(CapabilityEffect::OBSToggleVirtualCam {
+                }),
+                OBSRecordAction::Start => Some(CapabilityEffect::OBSRecordStart {
                     host: host.clone(),
                     port: *port,
                     password: password.clone(),
-                })
-            }
-            Capability::OBSTransition { host, port, password } if pressed => {
-                Some(CapabilityEffect::OBSTriggerTransition {
+                }),
+                OBSRecordAction::Stop => Some(CapabilityEffect::OBSRecordStop {
                     host: host.clone(),
                     port: *port,
                     password: password.clone(),
-                })
-            }
+                }),
+                OBSRecordAction::TogglePause => Some(CapabilityEffect::OBSRecordTogglePause {
+                    host: host.clone(),
+                    port: *port,
+                    password: password.clone(),
+                }),
+            },
+            Capability::OBSSourceVisibility {
+                host,
+                port,
+                password,
+                scene,
+                source,
+            } if pressed => Some(CapabilityEffect::OBSToggleSourceVisibility {
+                host: host.clone(),
+                port: *port,
+                password: password.clone(),
+                scene: scene.clone(),
+                source: source.clone(),
+            }),
+            Capability::OBSAudio {
+                host,
+                port,
+                password,
+                input_name,
+                ..
+            } if pressed => Some(CapabilityEffect::OBSToggleMute {
+                host: host.clone(),
+                port: *port,
+                password: password.clone(),
+                input_name: input_name.clone(),
+            }),
+            Capability::OBSStudioMode {
+                host,
+                port,
+                password,
+            } if pressed => Some(CapabilityEffect::OBSToggleStudioMode {
+                host: host.clone(),
+                port: *port,
+                password: password.clone(),
+            }),
+            Capability::OBSReplayBuffer {
+                host,
+                port,
+                password,
+                action,
+            } if pressed => match action {
+                OBSReplayAction::Toggle => Some(CapabilityEffect::OBSReplayToggle {
+                    host: host.clone(),
+                    port: *port,
+                    password: password.clone(),
+                }),
+                OBSReplayAction::Start => Some(CapabilityEffect::OBSReplayStart {
+                    host: host.clone(),
+                    port: *port,
+                    password: password.clone(),
+                }),
+                OBSReplayAction::Stop => Some(CapabilityEffect::OBSReplayStop {
+                    host: host.clone(),
+                    port: *port,
+                    password: password.clone(),
+                }),
+                OBSReplayAction::Save => Some(CapabilityEffect::OBSReplaySave {
+                    host: host.clone(),
+                    port: *port,
+                    password: password.clone(),
+                }),
+            },
+            Capability::OBSVirtualCam {
+                host,
+                port,
+                password,
+            } if pressed => Some(CapabilityEffect::OBSToggleVirtualCam {
+                host: host.clone(),
+                port: *port,
+                password: password.clone(),
+            }),
+            Capability::OBSTransition {
+                host,
+                port,
+                password,
+            } if pressed => Some(CapabilityEffect::OBSTriggerTransition {
+                host: host.clone(),
+                port: *port,
+                password: password.clone(),
+            }),
             _ => None,
         }
     }
+
+    /// Whether a button press flips something on or off, so pressing again
+    /// undoes it. Hold-to-activate inputs use this to switch back on release.
+    pub fn is_toggle(&self) -> bool {
+        match self {
+            Capability::SystemAudio { .. }
+            | Capability::Mute
+            | Capability::Microphone { .. }
+            | Capability::MicMute
+            | Capability::MediaPlayPause
+            | Capability::OBSSourceVisibility { .. }
+            | Capability::OBSAudio { .. }
+            | Capability::OBSStudioMode { .. }
+            | Capability::OBSVirtualCam { .. } => true,
+            Capability::RunCommand {
+                toggle,
+                state_command,
+                on_command,
+                off_command,
+                ..
+            } => {
+                *toggle || state_command.is_some() || on_command.is_some() || off_command.is_some()
+            }
+            Capability::ElgatoKeyLight { action, .. } => *action == KeyLightAction::Toggle,
+            Capability::OBSStream { action, .. } => *action == OBSStreamAction::Toggle,
+            Capability::OBSRecord { action, .. } => *action == OBSRecordAction::Toggle,
+            Capability::OBSReplayBuffer { action, .. } => *action == OBSReplayAction::Toggle,
+            _ => false,
+        }
+    }
+
+    /// State keys (see `Plugin::state`) in which the capability counts as
+    /// active; `button_image_alt` and `icon_color_alt` style these.
+    pub fn active_states(&self) -> &'static [&'static str] {
+        match self {
+            Capability::SystemAudio { .. }
+            | Capability::Mute
+            | Capability::Microphone { .. }
+            | Capability::MicMute => &["muted"],
+            Capability::MediaPlayPause => &["playing"],
+            Capability::RunCommand { .. } | Capability::ElgatoKeyLight { .. } => &["on"],
+            Capability::OBSRecord { .. } => &["recording", "paused"],
+            Capability::OBSStream { .. } => &["live"],
+            _ => &[crate::plugin::ACTIVE_STATE],
+        }
+    }
+
+    /// The capability that undoes a press of this one, run when a momentary
+    /// binding is released. Toggles undo themselves, on/off and start/stop
+    /// actions swap, and macros undo their steps in reverse order.
+    pub fn reverse(&self) -> Option<Capability> {
+        if self.is_toggle() {
+            return Some(self.clone());
+        }
+        let mut reversed = self.clone();
+        match &mut reversed {
+            Capability::ElgatoKeyLight { action, .. } => {
+                *action = match action {
+                    KeyLightAction::On => KeyLightAction::Off,
+                    KeyLightAction::Off => KeyLightAction::On,
+                    _ => return None,
+                }
+            }
+            Capability::OBSStream { action, .. } => {
+                *action = match action {
+                    OBSStreamAction::Start => OBSStreamAction::Stop,
+                    OBSStreamAction::Stop => OBSStreamAction::Start,
+                    _ => return None,
+                }
+            }
+            Capability::OBSRecord { action, .. } => {
+                *action = match action {
+                    OBSRecordAction::Start => OBSRecordAction::Stop,
+                    OBSRecordAction::Stop => OBSRecordAction::Start,
+                    _ => return None,
+                }
+            }
+            Capability::OBSReplayBuffer { action, .. } => {
+                *action = match action {
+                    OBSReplayAction::Start => OBSReplayAction::Stop,
+                    OBSReplayAction::Stop => OBSReplayAction::Start,
+                    _ => return None,
+                }
+            }
+            Capability::Macro { steps } => {
+                *steps = steps
+                    .iter()
+                    .rev()
+                    .filter_map(|step| {
+                        step.capability.reverse().map(|capability| MacroStep {
+                            capability,
+                            delay_ms: 0,
+                        })
+                    })
+                    .collect();
+                if steps.is_empty() {
+                    return None;
+                }
+            }
+            _ => return None,
+        }
+        Some(reversed)
+    }
 }
 
 /// Clamps a volume value to the valid range [0.0, 1.0].
@@ -507,6 +853,65 @@ mod tests {
         assert_eq!(effect, None);
     }
 
+    #[test]
+    fn toggles_are_recognised() {
+        assert!(Capability::MicMute.is_toggle());
+        let run_command = |toggle, state_command: Option<&str>| Capability::RunCommand {
+            command: "x".to_string(),
+            toggle,
+            state_command: state_command.map(String::from),
+            state_match: None,
+            on_command: None,
+            off_command: None,
+            cwd: None,
+            env: BTreeMap::new(),
+            timeout_ms: None,
+            kill_on_release: false,
+        };
+        assert!(run_command(true, None).is_toggle());
+        assert!(!run_command(false, None).is_toggle());
+        assert!(run_command(false, Some("systemctl is-active vpn")).is_toggle());
+        assert!(!Capability::MediaNext.is_toggle());
+        assert!(!Capability::VolumeUp { step: 0.05 }.is_toggle());
+    }
+
+    #[test]
+    fn reverse_undoes_a_press() {
+        let light = |action| Capability::ElgatoKeyLight {
+            ip: "10.0.0.2".to_string(),
+            port: 9123,
+            action,
+        };
+        assert_eq!(Capability::MicMute.reverse(), Some(Capability::MicMute));
+        assert_eq!(
+            light(KeyLightAction::On).reverse(),
+            Some(light(KeyLightAction::Off))
+        );
+        assert_eq!(light(KeyLightAction::SetBrightness).reverse(), None);
+        assert_eq!(Capability::MediaNext.reverse(), None);
+
+        let step = |capability, delay_ms| MacroStep {
+            capability,
+            delay_ms,
+        };
+        let on_air = Capability::Macro {
+            steps: vec![
+                step(light(KeyLightAction::On), 0),
+                step(Capability::MediaNext, 0),
+                step(Capability::MicMute, 100),
+            ],
+        };
+        assert_eq!(
+            on_air.reverse(),
+            Some(Capability::Macro {
+                steps: vec![
+                    step(Capability::MicMute, 0),
+                    step(light(KeyLightAction::Off), 0)
+                ],
+            })
+        );
+    }
+
     #[test]
     fn microphone_volume_on_encoder_positive() {
         let cap = Capability::Microphone { step: 0.02 };
@@ -555,7 +960,10 @@ mod tests {
     #[test]
     fn media_play_pause_produces_effect_on_press() {
         let cap = Capability::MediaPlayPause;
-        assert_eq!(cap.apply_button(true), Some(CapabilityEffect::MediaPlayPause));
+        assert_eq!(
+            cap.apply_button(true),
+            Some(CapabilityEffect::MediaPlayPause)
+        );
     }
 
     #[test]
@@ -630,6 +1038,14 @@ mod tests {
         let cap = Capability::RunCommand {
             command: "echo hello".to_string(),
             toggle: false,
+            state_command: None,
+            state_match: None,
+            on_command: None,
+            off_command: None,
+            cwd: None,
+            env: BTreeMap::new(),
+            timeout_ms: None,
+            kill_on_release: false,
         };
         assert_eq!(
             cap.apply_button(true),
@@ -642,6 +1058,14 @@ mod tests {
         let cap = Capability::RunCommand {
             command: "echo hello".to_string(),
             toggle: false,
+            state_command: None,
+            state_match: None,
+            on_command: None,
+            off_command: None,
+            cwd: None,
+            env: BTreeMap::new(),
+            timeout_ms: None,
+            kill_on_release: false,
         };
         assert_eq!(cap.apply_button(false), None);
     }
@@ -651,6 +1075,14 @@ mod tests {
         let cap = Capability::RunCommand {
             command: "dictation-toggle".to_string(),
             toggle: true,
+            state_command: None,
+            state_match: None,
+            on_command: None,
+            off_command: None,
+            cwd: None,
+            env: BTreeMap::new(),
+            timeout_ms: None,
+            kill_on_release: false,
         };
         assert_eq!(
             cap.apply_button(true),
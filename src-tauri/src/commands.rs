@@ -1,23 +1,63 @@
-use crate::binding::{Binding, InputRef};
+use crate::binding::{
+    normalize_chord, Acceleration, Binding, Gauge, Gesture, InputRef, LabelPosition, StateStyle,
+};
 use crate::capability::Capability;
 use crate::config;
-use crate::device::DeviceInfo;
+use crate::device::{edit_for_device, for_device, DeviceInfo, OnDevice};
+use crate::navigation::{CurrentPage, Navigation};
+use crate::page::{self, Page};
 use crate::plugin::{PluginInfo, PluginRegistry};
 use crate::state_manager::{self, SystemState};
 use crate::streamdeck;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, Mutex};
 use tauri::State;
 
-/// Shared application state accessible from commands.
+/// Shared application state accessible from commands and the deck threads.
+///
+/// Lock order: bindings, then pages, then navigation.
+#[derive(Clone)]
 pub struct AppState {
-    pub device_info: Arc<Mutex<Option<DeviceInfo>>>,
+    /// Connected decks, sorted by serial
+    pub devices: Arc<Mutex<Vec<DeviceInfo>>>,
     pub bindings: Arc<Mutex<Vec<Binding>>>,
+    /// Named pages from the config
+    pub pages: Arc<Mutex<Vec<Page>>>,
     pub system_state: Arc<Mutex<SystemState>>,
-    pub current_page: Arc<Mutex<usize>>,
+    /// Where each deck is, by serial
+    pub navigation: Arc<Mutex<HashMap<String, Navigation>>>,
     pub plugin_registry: Arc<PluginRegistry>,
 }
 
+impl AppState {
+    /// Serial of the deck a command is for: the one asked for, or else the first
+    /// connected deck (empty with none connected, which only sees shared bindings).
+    fn deck_serial(&self, device: Option<String>) -> String {
+        device
+            .or_else(|| self.devices.lock().ok()?.first().map(|d| d.serial.clone()))
+            .unwrap_or_default()
+    }
+
+    /// Where a deck currently is.
+    pub fn navigation_for(&self, serial: &str) -> Navigation {
+        self.navigation
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(serial)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Deck tag for bindings and pages created on a deck. With several decks
+    /// connected they belong to that deck; with one they stay shared, so a
+    /// single-deck setup keeps working when the deck is swapped.
+    fn new_item_tag(&self, serial: &str) -> Option<String> {
+        let several = self.devices.lock().map(|d| d.len() > 1).unwrap_or(false);
+        (several && !serial.is_empty()).then(|| serial.to_string())
+    }
+}
+
 /// Information about an available capability for the frontend.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CapabilityInfo {
@@ -40,16 +80,35 @@ pub struct CapabilityParameter {
     pub description: String,
 }
 
-/// Get connected device information.
+/// Get information about every connected deck.
+#[tauri::command]
+pub fn get_devices(state: State<AppState>) -> Vec<DeviceInfo> {
+    state.devices.lock().map(|d| d.clone()).unwrap_or_default()
+}
+
+/// Get connected device information (the first deck without a serial).
 #[tauri::command]
-pub fn get_device_info(state: State<AppState>) -> Option<DeviceInfo> {
-    state.device_info.lock().ok()?.clone()
+pub fn get_device_info(state: State<AppState>, device: Option<String>) -> Option<DeviceInfo> {
+    let serial = state.deck_serial(device);
+    state
+        .devices
+        .lock()
+        .ok()?
+        .iter()
+        .find(|d| d.serial == serial)
+        .cloned()
 }
 
-/// Get current bindings.
+/// Get the bindings shown on a deck.
 #[tauri::command]
-pub fn get_bindings(state: State<AppState>) -> Vec<Binding> {
-    state.bindings.lock().ok().map(|b| b.clone()).unwrap_or_default()
+pub fn get_bindings(state: State<AppState>, device: Option<String>) -> Vec<Binding> {
+    let serial = state.deck_serial(device);
+    state
+        .bindings
+        .lock()
+        .ok()
+        .map(|b| for_device(&b, &serial))
+        .unwrap_or_default()
 }
 
 /// Get available capabilities from all enabled plugins.
@@ -67,6 +126,17 @@ pub struct SetBindingParams {
     #[serde(default)]
     pub page: usize,
     #[serde(default)]
+    pub folder: Option<String>,
+    /// Deck to bind on (None = first connected deck)
+    #[serde(default)]
+    pub device: Option<String>,
+    #[serde(default)]
+    pub gesture: Option<Gesture>,
+    #[serde(default)]
+    pub acceleration: Option<Acceleration>,
+    #[serde(default)]
+    pub momentary: Option<bool>,
+    #[serde(default)]
     pub icon: Option<String>,
     #[serde(default)]
     pub label: Option<String>,
@@ -80,6 +150,20 @@ pub struct SetBindingParams {
     pub icon_color: Option<String>,
     #[serde(default)]
     pub icon_color_alt: Option<String>,
+    #[serde(default)]
+    pub background_color: Option<String>,
+    #[serde(default)]
+    pub text_color: Option<String>,
+    #[serde(default)]
+    pub font_size: Option<u32>,
+    #[serde(default)]
+    pub label_position: Option<LabelPosition>,
+    #[serde(default)]
+    pub outline_color: Option<String>,
+    #[serde(default)]
+    pub gauge: Option<Gauge>,
+    #[serde(default)]
+    pub states: BTreeMap<String, StateStyle>,
 }
 
 /// Add or update a binding.
@@ -91,16 +175,35 @@ pub fn set_binding(state: State<AppState>, params: SetBindingParams) -> Result<(
         params.page, params.button_image, params.show_label
     );
 
+    let serial = state.deck_serial(params.device);
+    let tag = state.new_item_tag(&serial);
     let mut bindings = state.bindings.lock().map_err(|e| e.to_string())?;
 
-    // Remove existing binding for this input AND page if present
-    bindings.retain(|b| !(inputs_match(&b.input, &params.input) && b.page == params.page));
+    // Remove existing binding for this input, page AND gesture on this deck if present
+    // (a replaced binding keeps its deck tag, so shared bindings stay shared)
+    let same_slot = |b: &Binding| {
+        b.on_device(&serial)
+            && inputs_match(&b.input, &params.input)
+            && b.page == params.page
+            && b.folder == params.folder
+            && gestures_match(b.gesture, params.gesture)
+    };
+    let device = bindings
+        .iter()
+        .find(|b| same_slot(b))
+        .map_or(tag, |b| b.device.clone());
+    bindings.retain(|b| !same_slot(b));
 
     // Add new binding
-    bindings.push(Binding {
+    let mut binding = Binding {
         input: params.input,
         capability: params.capability,
         page: params.page,
+        folder: params.folder,
+        device,
+        gesture: params.gesture,
+        acceleration: params.acceleration,
+        momentary: params.momentary,
         icon: params.icon,
         label: params.label,
         button_image: params.button_image,
@@ -108,7 +211,16 @@ pub fn set_binding(state: State<AppState>, params: SetBindingParams) -> Result<(
         show_label: params.show_label,
         icon_color: params.icon_color,
         icon_color_alt: params.icon_color_alt,
-    });
+        background_color: params.background_color,
+        text_color: params.text_color,
+        font_size: params.font_size,
+        label_position: params.label_position,
+        outline_color: params.outline_color,
+        gauge: params.gauge,
+        states: params.states,
+    };
+    binding.add_alt_states();
+    bindings.push(binding);
 
     // Request button image sync to hardware
     streamdeck::request_image_sync();
@@ -116,14 +228,33 @@ pub fn set_binding(state: State<AppState>, params: SetBindingParams) -> Result<(
     Ok(())
 }
 
-/// Remove a binding for an input on a specific page.
+/// Remove a binding for an input on a specific page of a deck.
+/// Without a page, the page currently shown on the deck (including its folder) is used.
+/// Without a gesture, every binding on the input is removed.
 #[tauri::command]
-pub fn remove_binding(state: State<AppState>, input: InputRef, page: Option<usize>) -> Result<(), String> {
-    let current_page = *state.current_page.lock().map_err(|e| e.to_string())?;
-    let target_page = page.unwrap_or(current_page);
+pub fn remove_binding(
+    state: State<AppState>,
+    input: InputRef,
+    page: Option<usize>,
+    folder: Option<String>,
+    gesture: Option<Gesture>,
+    device: Option<String>,
+) -> Result<(), String> {
+    let serial = state.deck_serial(device);
+    let nav = state.navigation_for(&serial);
+    let (target_page, target_folder) = match page {
+        Some(page) => (page, folder),
+        None => (nav.page(), nav.folder().map(String::from)),
+    };
 
     let mut bindings = state.bindings.lock().map_err(|e| e.to_string())?;
-    bindings.retain(|b| !(inputs_match(&b.input, &input) && b.page == target_page));
+    bindings.retain(|b| {
+        !(b.on_device(&serial)
+            && inputs_match(&b.input, &input)
+            && b.page == target_page
+            && b.folder == target_folder
+            && (gesture.is_none() || gestures_match(b.gesture, gesture)))
+    });
 
     // Request button image sync to clear the removed button
     streamdeck::request_image_sync();
@@ -131,30 +262,167 @@ pub fn remove_binding(state: State<AppState>, input: InputRef, page: Option<usiz
     Ok(())
 }
 
-/// Get the current page number.
+/// Get a deck's current page, its folder and the breadcrumb of open folders.
 #[tauri::command]
-pub fn get_current_page(state: State<AppState>) -> usize {
-    *state.current_page.lock().unwrap_or_else(|e| e.into_inner())
+pub fn get_current_page(state: State<AppState>, device: Option<String>) -> CurrentPage {
+    let serial = state.deck_serial(device);
+    state.navigation_for(&serial).current_page()
 }
 
-/// Set the current page number.
+/// Set a deck's current page number (within the open folder).
 #[tauri::command]
-pub fn set_current_page(state: State<AppState>, page: usize) {
-    if let Ok(mut current) = state.current_page.lock() {
-        *current = page;
+pub fn set_current_page(state: State<AppState>, page: usize, device: Option<String>) {
+    let serial = state.deck_serial(device);
+    if let Ok(mut navs) = state.navigation.lock() {
+        navs.entry(serial).or_default().set_page(page);
     }
     // Sync hardware to show the new page's bindings
     streamdeck::request_image_sync();
 }
 
-/// Get the total number of pages (based on max page in bindings + 1).
+/// Get the number of pages in a deck's open folder.
 #[tauri::command]
-pub fn get_page_count(state: State<AppState>) -> usize {
-    let bindings = state.bindings.lock().ok();
-    match bindings {
-        Some(b) => b.iter().map(|binding| binding.page).max().unwrap_or(0) + 1,
-        None => 1,
-    }
+pub fn get_page_count(state: State<AppState>, device: Option<String>) -> usize {
+    get_pages(state, device)
+        .map(|pages| pages.len())
+        .unwrap_or(1)
+}
+
+/// Get the pages in a deck's open folder, in display order.
+#[tauri::command]
+pub fn get_pages(state: State<AppState>, device: Option<String>) -> Result<Vec<Page>, String> {
+    let serial = state.deck_serial(device);
+    let bindings = state.bindings.lock().map_err(|e| e.to_string())?;
+    let pages = state.pages.lock().map_err(|e| e.to_string())?;
+    let nav = state.navigation_for(&serial);
+    Ok(nav.pages_here(
+        &for_device(&pages, &serial),
+        &for_device(&bindings, &serial),
+    ))
+}
+
+/// Run a page table edit on the pages and bindings of a deck's open folder.
+/// Pages and bindings the edit adds are tagged for that deck (see `new_item_tag`).
+fn edit_pages<R>(
+    state: &AppState,
+    device: Option<String>,
+    edit: impl FnOnce(&mut Vec<Page>, &mut Vec<Binding>, &mut Navigation) -> anyhow::Result<R>,
+) -> Result<R, String> {
+    let serial = state.deck_serial(device);
+    let tag = state.new_item_tag(&serial);
+    let mut bindings = state.bindings.lock().map_err(|e| e.to_string())?;
+    let mut pages = state.pages.lock().map_err(|e| e.to_string())?;
+    let mut navs = state.navigation.lock().map_err(|e| e.to_string())?;
+    let nav = navs.entry(serial.clone()).or_default();
+
+    edit_for_device(&mut bindings, &serial, tag.as_deref(), |bindings| {
+        edit_for_device(&mut pages, &serial, tag.as_deref(), |pages| {
+            edit(pages, bindings, nav)
+        })
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Add a new page at the end of a deck's open folder.
+#[tauri::command]
+pub fn create_page(
+    state: State<AppState>,
+    name: String,
+    device: Option<String>,
+) -> Result<Page, String> {
+    edit_pages(&state, device, |pages, bindings, nav| {
+        Ok(page::create_page(pages, bindings, nav.folder(), &name))
+    })
+}
+
+/// Rename a page in a deck's open folder.
+#[tauri::command]
+pub fn rename_page(
+    state: State<AppState>,
+    page: usize,
+    name: String,
+    device: Option<String>,
+) -> Result<(), String> {
+    edit_pages(&state, device, |pages, bindings, nav| {
+        page::rename_page(pages, bindings, nav.folder(), page, &name)
+    })
+}
+
+/// Set or clear the background image of a page in a deck's open folder.
+#[tauri::command]
+pub fn set_page_background(
+    state: State<AppState>,
+    page: usize,
+    background: Option<String>,
+    device: Option<String>,
+) -> Result<(), String> {
+    edit_pages(&state, device, |pages, bindings, nav| {
+        page::set_page_background(pages, bindings, nav.folder(), page, background)
+    })?;
+
+    streamdeck::request_image_sync();
+    Ok(())
+}
+
+/// Set or clear the touch strip background of a page in a deck's open folder.
+#[tauri::command]
+pub fn set_page_lcd_background(
+    state: State<AppState>,
+    page: usize,
+    background: Option<String>,
+    device: Option<String>,
+) -> Result<(), String> {
+    edit_pages(&state, device, |pages, bindings, nav| {
+        page::set_page_lcd_background(pages, bindings, nav.folder(), page, background)
+    })?;
+
+    streamdeck::request_image_sync();
+    Ok(())
+}
+
+/// Reorder the pages of a deck's open folder. `pages` lists every page id once.
+#[tauri::command]
+pub fn reorder_pages(
+    state: State<AppState>,
+    pages: Vec<usize>,
+    device: Option<String>,
+) -> Result<(), String> {
+    edit_pages(&state, device, |table, bindings, nav| {
+        page::reorder_pages(table, bindings, nav.folder(), &pages)
+    })
+}
+
+/// Copy a page of a deck's open folder, with its bindings, to a new page after it.
+#[tauri::command]
+pub fn duplicate_page(
+    state: State<AppState>,
+    page: usize,
+    device: Option<String>,
+) -> Result<Page, String> {
+    edit_pages(&state, device, |pages, bindings, nav| {
+        page::duplicate_page(pages, bindings, nav.folder(), page)
+    })
+}
+
+/// Delete a page of a deck's open folder and its bindings.
+/// If the deck was showing it, it moves to the first remaining page.
+#[tauri::command]
+pub fn delete_page(
+    state: State<AppState>,
+    page: usize,
+    device: Option<String>,
+) -> Result<(), String> {
+    edit_pages(&state, device, |pages, bindings, nav| {
+        page::delete_page(pages, bindings, nav.folder(), page)?;
+        if nav.page() == page {
+            let first = nav.pages_here(pages, bindings)[0].id;
+            nav.set_page(first);
+        }
+        Ok(())
+    })?;
+
+    streamdeck::request_image_sync();
+    Ok(())
 }
 
 /// Sync button images to hardware.
@@ -167,7 +435,8 @@ pub fn sync_button_images() {
 #[tauri::command]
 pub fn save_bindings(state: State<AppState>) -> Result<(), String> {
     let bindings = state.bindings.lock().map_err(|e| e.to_string())?;
-    config::save_bindings(&bindings).map_err(|e| e.to_string())
+    let pages = state.pages.lock().map_err(|e| e.to_string())?;
+    config::save_bindings(&bindings, &pages).map_err(|e| e.to_string())
 }
 
 /// Get current system state (mute, playback).
@@ -196,10 +465,24 @@ fn inputs_match(a: &InputRef, b: &InputRef) -> bool {
         (InputRef::Encoder { index: i1 }, InputRef::Encoder { index: i2 }) => i1 == i2,
         (InputRef::EncoderPress { index: i1 }, InputRef::EncoderPress { index: i2 }) => i1 == i2,
         (InputRef::Swipe, InputRef::Swipe) => true,
+        (InputRef::TouchTap { section: s1 }, InputRef::TouchTap { section: s2 }) => s1 == s2,
+        (InputRef::TouchLongPress { section: s1 }, InputRef::TouchLongPress { section: s2 }) => {
+            s1 == s2
+        }
+        (InputRef::Chord { indices: c1 }, InputRef::Chord { indices: c2 }) => {
+            normalize_chord(c1) == normalize_chord(c2)
+        }
+        (InputRef::Pedal { position: p1 }, InputRef::Pedal { position: p2 }) => p1 == p2,
         _ => false,
     }
 }
 
+/// Check if two gesture qualifiers select the same binding slot on a key.
+/// Thresholds are settings, not identity: two long-press bindings share a slot.
+fn gestures_match(a: Option<Gesture>, b: Option<Gesture>) -> bool {
+    a.map(|g| g.kind()) == b.map(|g| g.kind())
+}
+
 /// Get information about all plugins.
 #[tauri::command]
 pub fn get_plugins(state: State<AppState>) -> Vec<PluginInfo> {
@@ -224,7 +507,10 @@ pub fn set_plugin_enabled(
     }
 
     // Update the enabled state in the registry
-    if !state.plugin_registry.set_plugin_enabled(&plugin_id, enabled) {
+    if !state
+        .plugin_registry
+        .set_plugin_enabled(&plugin_id, enabled)
+    {
         return Err(format!("Failed to update plugin '{}' state", plugin_id));
     }
 
@@ -262,4 +548,35 @@ mod tests {
         assert!(!inputs_match(&a, &b));
     }
 
+    #[test]
+    fn inputs_match_chords_ignore_order() {
+        let a = InputRef::Chord {
+            indices: vec![3, 1],
+        };
+        let b = InputRef::Chord {
+            indices: vec![1, 3],
+        };
+        let c = InputRef::Chord {
+            indices: vec![1, 2],
+        };
+        assert!(inputs_match(&a, &b));
+        assert!(!inputs_match(&a, &c));
+    }
+
+    #[test]
+    fn gestures_match_ignores_thresholds() {
+        let a = Some(Gesture::LongPress { threshold_ms: 500 });
+        let b = Some(Gesture::LongPress { threshold_ms: 900 });
+        assert!(gestures_match(a, b));
+    }
+
+    #[test]
+    fn gestures_match_distinguishes_kinds() {
+        assert!(gestures_match(None, None));
+        assert!(!gestures_match(None, Some(Gesture::Tap)));
+        assert!(!gestures_match(
+            Some(Gesture::Tap),
+            Some(Gesture::DoubleTap)
+        ));
+    }
 }
@@ -1,4 +1,6 @@
 use crate::binding::Binding;
+use crate::focus::AppRule;
+use crate::page::Page;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -16,6 +18,9 @@ struct Config {
     /// Schema version for migration support
     #[serde(default = "default_version")]
     version: u32,
+    /// Named pages (pages only referenced by bindings need no entry)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pages: Vec<Page>,
     #[serde(rename = "bindings")]
     bindings: Vec<Binding>,
 }
@@ -26,18 +31,32 @@ fn default_version() -> u32 {
 
 /// Returns the path to the config file: ~/.config/{app_name}/bindings.toml
 pub fn config_path() -> Option<PathBuf> {
-    dirs::config_dir().map(|p| p.join(crate::app_constants::APP_NAME_LOWER).join("bindings.toml"))
+    dirs::config_dir().map(|p| {
+        p.join(crate::app_constants::APP_NAME_LOWER)
+            .join("bindings.toml")
+    })
 }
 
 /// Returns the path to the plugins config file: ~/.config/{app_name}/plugins.toml
 pub fn plugins_config_path() -> Option<PathBuf> {
-    dirs::config_dir().map(|p| p.join(crate::app_constants::APP_NAME_LOWER).join("plugins.toml"))
+    dirs::config_dir().map(|p| {
+        p.join(crate::app_constants::APP_NAME_LOWER)
+            .join("plugins.toml")
+    })
 }
 
-/// Save bindings to the config file.
+/// Returns the path to the app rules file: ~/.config/{app_name}/apps.toml
+pub fn apps_config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|p| {
+        p.join(crate::app_constants::APP_NAME_LOWER)
+            .join("apps.toml")
+    })
+}
+
+/// Save bindings and the page table to the config file.
 /// Uses atomic writes (write to temp, then rename) to prevent corruption.
 /// Keeps a .bak backup of the previous config.
-pub fn save_bindings(bindings: &[Binding]) -> Result<()> {
+pub fn save_bindings(bindings: &[Binding], pages: &[Page]) -> Result<()> {
     let Some(path) = config_path() else {
         anyhow::bail!("Could not determine config directory");
     };
@@ -50,11 +69,12 @@ pub fn save_bindings(bindings: &[Binding]) -> Result<()> {
 
     let config = Config {
         version: CONFIG_VERSION,
+        pages: pages.to_vec(),
         bindings: bindings.to_vec(),
     };
 
-    let contents = toml::to_string_pretty(&config)
-        .context("Failed to serialize bindings to TOML")?;
+    let contents =
+        toml::to_string_pretty(&config).context("Failed to serialize bindings to TOML")?;
 
     // Atomic write: write to temp file, then rename
     let tmp_path = path.with_extension("toml.tmp");
@@ -81,33 +101,41 @@ pub fn save_bindings(bindings: &[Binding]) -> Result<()> {
     #[cfg(unix)]
     {
         let permissions = fs::Permissions::from_mode(0o600);
-        fs::set_permissions(&path, permissions)
-            .with_context(|| format!("Failed to set permissions on config file: {}", path.display()))?;
+        fs::set_permissions(&path, permissions).with_context(|| {
+            format!(
+                "Failed to set permissions on config file: {}",
+                path.display()
+            )
+        })?;
     }
 
     Ok(())
 }
 
-/// Load bindings from the config file, or return defaults if it doesn't exist.
+/// Load bindings and the page table from the config file, or return defaults if
+/// it doesn't exist.
 /// If the main config is corrupted, attempts to load from backup.
-pub fn load_bindings() -> Result<Vec<Binding>> {
+pub fn load_bindings() -> Result<(Vec<Binding>, Vec<Page>)> {
     let Some(path) = config_path() else {
-        return Ok(default_bindings());
+        return Ok((default_bindings(), Vec::new()));
     };
 
     if !path.exists() {
         // Try backup if main config doesn't exist
         let bak_path = path.with_extension("toml.bak");
         if bak_path.exists() {
-            eprintln!("Main config missing, loading from backup: {}", bak_path.display());
+            eprintln!(
+                "Main config missing, loading from backup: {}",
+                bak_path.display()
+            );
             return load_from_path(&bak_path);
         }
-        return Ok(default_bindings());
+        return Ok((default_bindings(), Vec::new()));
     }
 
     // Try to load main config
     match load_from_path(&path) {
-        Ok(bindings) => Ok(bindings),
+        Ok(loaded) => Ok(loaded),
         Err(e) => {
             // Main config corrupted, try backup
             let bak_path = path.with_extension("toml.bak");
@@ -120,8 +148,8 @@ pub fn load_bindings() -> Result<Vec<Binding>> {
     }
 }
 
-/// Load bindings from a specific path
-fn load_from_path(path: &PathBuf) -> Result<Vec<Binding>> {
+/// Load bindings and pages from a specific path
+fn load_from_path(path: &PathBuf) -> Result<(Vec<Binding>, Vec<Page>)> {
     let contents = fs::read_to_string(path)
         .with_context(|| format!("Failed to read config file: {}", path.display()))?;
 
@@ -136,7 +164,9 @@ fn load_from_path(path: &PathBuf) -> Result<Vec<Binding>> {
         );
     }
 
-    Ok(config.bindings)
+    let mut bindings = config.bindings;
+    bindings.iter_mut().for_each(Binding::add_alt_states);
+    Ok((bindings, config.pages))
 }
 
 /// Default bindings when no config file exists.
@@ -165,15 +195,13 @@ pub fn load_plugin_states() -> HashMap<String, bool> {
     }
 
     match fs::read_to_string(&path) {
-        Ok(contents) => {
-            match toml::from_str::<PluginsConfig>(&contents) {
-                Ok(config) => config.enabled,
-                Err(e) => {
-                    eprintln!("Failed to parse plugins config: {}", e);
-                    HashMap::new()
-                }
+        Ok(contents) => match toml::from_str::<PluginsConfig>(&contents) {
+            Ok(config) => config.enabled,
+            Err(e) => {
+                eprintln!("Failed to parse plugins config: {}", e);
+                HashMap::new()
             }
-        }
+        },
         Err(e) => {
             eprintln!("Failed to read plugins config: {}", e);
             HashMap::new()
@@ -206,8 +234,7 @@ pub fn save_plugin_state(plugin_id: &str, enabled: bool) -> Result<()> {
     config.enabled.insert(plugin_id.to_string(), enabled);
 
     // Write back
-    let contents = toml::to_string_pretty(&config)
-        .context("Failed to serialize plugins config")?;
+    let contents = toml::to_string_pretty(&config).context("Failed to serialize plugins config")?;
 
     fs::write(&path, &contents)
         .with_context(|| format!("Failed to write plugins config: {}", path.display()))?;
@@ -216,13 +243,51 @@ pub fn save_plugin_state(plugin_id: &str, enabled: bool) -> Result<()> {
     #[cfg(unix)]
     {
         let permissions = fs::Permissions::from_mode(0o600);
-        fs::set_permissions(&path, permissions)
-            .with_context(|| format!("Failed to set permissions on plugins config: {}", path.display()))?;
+        fs::set_permissions(&path, permissions).with_context(|| {
+            format!(
+                "Failed to set permissions on plugins config: {}",
+                path.display()
+            )
+        })?;
     }
 
     Ok(())
 }
 
+// --- App Rules ---
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct AppsConfig {
+    /// Pages to show while an app has focus
+    #[serde(default)]
+    rules: Vec<AppRule>,
+}
+
+/// Load the app focus rules. A missing or broken file means no rules.
+pub fn load_app_rules() -> Vec<AppRule> {
+    let Some(path) = apps_config_path() else {
+        return Vec::new();
+    };
+
+    if !path.exists() {
+        return Vec::new();
+    }
+
+    match fs::read_to_string(&path) {
+        Ok(contents) => match toml::from_str::<AppsConfig>(&contents) {
+            Ok(config) => config.rules,
+            Err(e) => {
+                eprintln!("Failed to parse apps config: {}", e);
+                Vec::new()
+            }
+        },
+        Err(e) => {
+            eprintln!("Failed to read apps config: {}", e);
+            Vec::new()
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -240,6 +305,7 @@ mod tests {
         let bindings = default_bindings();
         let config = Config {
             version: CONFIG_VERSION,
+            pages: vec![],
             bindings: bindings.clone(),
         };
 
@@ -302,7 +368,57 @@ step = 0.02
 
         let config: Config = toml::from_str(toml).expect("parse");
         assert_eq!(config.bindings.len(), 1);
-        assert_eq!(config.bindings[0].capability, Capability::Microphone { step: 0.02 });
+        assert_eq!(
+            config.bindings[0].capability,
+            Capability::Microphone { step: 0.02 }
+        );
+    }
+
+    #[test]
+    fn parse_macro_binding() {
+        let toml = r#"
+[[bindings]]
+input = { type = "Button", index = 3 }
+
+[bindings.capability]
+type = "Macro"
+steps = [
+    { capability = { type = "MicMute" } },
+    { capability = { type = "OBSScene", scene = "Live" }, delay_ms = 500 },
+]
+"#;
+
+        let config: Config = toml::from_str(toml).expect("parse");
+        let Capability::Macro { steps } = &config.bindings[0].capability else {
+            panic!("expected Macro");
+        };
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].capability, Capability::MicMute);
+        assert_eq!(steps[0].delay_ms, 0);
+        assert!(
+            matches!(&steps[1].capability, Capability::OBSScene { scene, .. } if scene == "Live")
+        );
+        assert_eq!(steps[1].delay_ms, 500);
+    }
+
+    #[test]
+    fn parse_command_display_defaults() {
+        let toml = r#"
+[[bindings]]
+input = { type = "Button", index = 0 }
+capability = { type = "CommandDisplay", command = "git branch --show-current" }
+"#;
+
+        let config: Config = toml::from_str(toml).expect("parse");
+        assert_eq!(
+            config.bindings[0].capability,
+            Capability::CommandDisplay {
+                command: "git branch --show-current".to_string(),
+                interval_ms: 5000,
+                timeout_ms: 2000,
+                max_length: 10,
+            }
+        );
     }
 
     #[test]
@@ -343,6 +459,43 @@ step = 0.02
         assert_eq!(config.bindings.len(), 3);
     }
 
+    #[test]
+    fn parse_page_table() {
+        let toml = r#"
+[[pages]]
+id = 2
+name = "Lights"
+background = "/tmp/lights.png"
+
+[[pages]]
+id = 0
+name = "Home"
+order = 1
+
+[[bindings]]
+page = 2
+[bindings.input]
+type = "Button"
+index = 0
+[bindings.capability]
+type = "MediaPlayPause"
+"#;
+        let config: Config = toml::from_str(toml).expect("parse");
+        assert_eq!(config.pages.len(), 2);
+        assert_eq!(config.pages[0].name, "Lights");
+        assert_eq!(
+            config.pages[0].background.as_deref(),
+            Some("/tmp/lights.png")
+        );
+        assert_eq!(config.pages[1].order, 1);
+        assert_eq!(config.bindings[0].page, 2);
+
+        // Page table survives a save
+        let toml_str = toml::to_string_pretty(&config).expect("serialize");
+        let parsed: Config = toml::from_str(&toml_str).expect("deserialize");
+        assert_eq!(parsed.pages, config.pages);
+    }
+
     // --- Plugin Config Tests ---
 
     #[test]
@@ -383,6 +536,28 @@ obs = true
         assert!(config.enabled.is_empty());
     }
 
+    #[test]
+    fn apps_config_parses_rules() {
+        let toml = r#"
+[[rules]]
+class = "com.obsproject.Studio"
+page = 2
+
+[[rules]]
+title = "YouTube"
+folder = "Media"
+"#;
+        let config: AppsConfig = toml::from_str(toml).expect("parse");
+        assert_eq!(config.rules.len(), 2);
+        assert_eq!(
+            config.rules[0].class.as_deref(),
+            Some("com.obsproject.Studio")
+        );
+        assert_eq!(config.rules[0].page, 2);
+        assert_eq!(config.rules[1].folder.as_deref(), Some("Media"));
+        assert_eq!(config.rules[1].page, 0);
+    }
+
     #[test]
     fn load_plugin_states_returns_empty_when_no_file() {
         // This test relies on the function handling missing files gracefully
@@ -3,7 +3,8 @@
 use crate::binding::Binding;
 use crate::capability::Capability;
 use crate::input_processor::LogicalEvent;
-use crate::plugin::{CapabilityMetadata, ParameterDef, ParameterType};
+use crate::label_template;
+use crate::plugin::{BindingValue, CapabilityMetadata, ParameterDef, ParameterType};
 use crate::state_manager::{self, SystemState};
 use std::process::Command;
 use std::sync::{Arc, Mutex};
@@ -142,6 +143,7 @@ pub fn handle_event(
 
         (Capability::SystemAudio { step }, LogicalEvent::Encoder(e)) => {
             apply_volume_delta(e.delta as f32 * step);
+            state_manager::request_state_check();
             true
         }
 
@@ -189,6 +191,7 @@ pub fn handle_event(
 
         (Capability::Microphone { step }, LogicalEvent::Encoder(e)) => {
             apply_mic_volume_delta(e.delta as f32 * step);
+            state_manager::request_state_check();
             true
         }
 
@@ -240,6 +243,35 @@ pub fn is_active(binding: &Binding, state: &SystemState) -> bool {
     }
 }
 
+/// State key: "muted" or "unmuted".
+pub fn state(binding: &Binding, state: &SystemState) -> Option<&'static str> {
+    let muted = match &binding.capability {
+        Capability::SystemAudio { .. } | Capability::Mute => state.is_muted,
+        Capability::Microphone { .. } | Capability::MicMute => state.is_mic_muted,
+        _ => return None,
+    };
+    Some(if muted { "muted" } else { "unmuted" })
+}
+
+/// Label template values: `{volume}` and `{mic.volume}` in percent.
+pub fn label_value(key: &str, state: &SystemState) -> Option<String> {
+    match key {
+        "volume" => state.volume.map(label_template::percent),
+        "mic.volume" => state.mic_volume.map(label_template::percent),
+        _ => None,
+    }
+}
+
+/// Volume level of the device a binding controls.
+pub fn value(binding: &Binding, state: &SystemState) -> Option<BindingValue> {
+    let level = match &binding.capability {
+        Capability::SystemAudio { .. } | Capability::Mute => state.volume,
+        Capability::Microphone { .. } | Capability::MicMute => state.mic_volume,
+        _ => None,
+    };
+    level.map(BindingValue::level)
+}
+
 // ─────────────────────────────────────────────────────────────────
 // Audio control functions (using wpctl/PipeWire)
 // ─────────────────────────────────────────────────────────────────
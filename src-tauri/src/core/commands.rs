@@ -1,24 +1,30 @@
-//! Command execution capabilities: RunCommand, LaunchApp, OpenURL.
+//! Command execution capabilities: RunCommand, CommandDisplay, LaunchApp, OpenURL.
 //!
 //! Security notes:
 //! - RunCommand uses shlex for safe argument parsing (no shell injection)
+//! - RunCommand children are tracked until they exit and reaped, and their
+//!   exit status is queued for the frontend
 //! - LaunchApp validates against shell metacharacters
 //! - OpenURL only allows whitelisted schemes
 
-use crate::binding::{Binding, InputRef};
+use crate::binding::{normalize_chord, Binding, InputRef};
 use crate::capability::Capability;
+use crate::events::{CommandExitEvent, CommandOutcome};
 use crate::input_processor::LogicalEvent;
 use crate::plugin::{CapabilityMetadata, ParameterDef, ParameterType};
-use crate::state_manager::SystemState;
-use crate::streamdeck::request_image_sync;
+use crate::state_manager::{refresh_command_display, request_state_check, SystemState};
+use crate::streamdeck::request_key_sync;
 use std::collections::HashMap;
-use std::process::Command;
+use std::process::{Child, Command, ExitStatus, Stdio};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, Instant};
 
 /// Minimum interval between command executions (prevents rapid-fire from stuck buttons)
 const MIN_COMMAND_INTERVAL: Duration = Duration::from_millis(200);
 
+/// How often tracked RunCommand children are checked for exit or timeout
+const REAP_INTERVAL: Duration = Duration::from_millis(50);
+
 /// Track last execution time for rate limiting
 static COMMAND_RATE_LIMITER: OnceLock<Mutex<HashMap<String, Instant>>> = OnceLock::new();
 
@@ -67,6 +73,89 @@ pub fn capabilities() -> Vec<CapabilityMetadata> {
                     default_value: "false",
                     description: "Toggle mode: alternate between default and active image on each press",
                 },
+                ParameterDef {
+                    name: "state_command",
+                    param_type: ParameterType::String,
+                    default_value: "",
+                    description: "Command polled to decide whether the key is active (exit code 0 = active)",
+                },
+                ParameterDef {
+                    name: "state_match",
+                    param_type: ParameterType::String,
+                    default_value: "",
+                    description: "Treat the key as active when the state command prints this instead",
+                },
+                ParameterDef {
+                    name: "on_command",
+                    param_type: ParameterType::String,
+                    default_value: "",
+                    description: "Command run instead when the key is inactive",
+                },
+                ParameterDef {
+                    name: "off_command",
+                    param_type: ParameterType::String,
+                    default_value: "",
+                    description: "Command run instead when the key is active",
+                },
+                ParameterDef {
+                    name: "cwd",
+                    param_type: ParameterType::String,
+                    default_value: "",
+                    description: "Working directory (~ is your home directory)",
+                },
+                ParameterDef {
+                    name: "env",
+                    param_type: ParameterType::String,
+                    default_value: "",
+                    description: "Extra environment variables",
+                },
+                ParameterDef {
+                    name: "timeout_ms",
+                    param_type: ParameterType::Integer,
+                    default_value: "",
+                    description: "Kill the command if it runs longer than this many milliseconds",
+                },
+                ParameterDef {
+                    name: "kill_on_release",
+                    param_type: ParameterType::Bool,
+                    default_value: "false",
+                    description: "Kill the command when the key is released",
+                },
+            ],
+        },
+        CapabilityMetadata {
+            id: "CommandDisplay",
+            name: "Command Display",
+            description: "Show a command's output on the key, refreshed periodically and on press",
+            plugin_id: "core",
+            supports_button: true,
+            supports_encoder: false,
+            supports_encoder_press: false,
+            parameters: vec![
+                ParameterDef {
+                    name: "command",
+                    param_type: ParameterType::String,
+                    default_value: "",
+                    description: "Command whose first line of output is shown",
+                },
+                ParameterDef {
+                    name: "interval_ms",
+                    param_type: ParameterType::Integer,
+                    default_value: "5000",
+                    description: "Refresh interval in milliseconds (0 = only on press)",
+                },
+                ParameterDef {
+                    name: "timeout_ms",
+                    param_type: ParameterType::Integer,
+                    default_value: "2000",
+                    description: "Kill the command after this many milliseconds",
+                },
+                ParameterDef {
+                    name: "max_length",
+                    param_type: ParameterType::Integer,
+                    default_value: "10",
+                    description: "Longer output is cut to this many characters",
+                },
             ],
         },
         CapabilityMetadata {
@@ -111,19 +200,34 @@ pub fn handle_event(
     system_state: &Arc<Mutex<SystemState>>,
 ) -> bool {
     match (&binding.capability, event) {
-        (Capability::RunCommand { command, toggle }, LogicalEvent::EncoderPress(e)) if e.pressed => {
-            run_shell_command(command);
-            if *toggle {
-                flip_toggle_state(binding, system_state);
-            }
+        (
+            Capability::RunCommand { .. },
+            LogicalEvent::Button(e) | LogicalEvent::EncoderPress(e),
+        ) if e.pressed => {
+            run_command_binding(binding, system_state);
             true
         }
 
-        (Capability::RunCommand { command, toggle }, LogicalEvent::Button(e)) if e.pressed => {
-            run_shell_command(command);
-            if *toggle {
-                flip_toggle_state(binding, system_state);
-            }
+        (
+            Capability::RunCommand {
+                kill_on_release: true,
+                ..
+            },
+            LogicalEvent::Button(_) | LogicalEvent::EncoderPress(_),
+        ) => {
+            kill_released_children(binding);
+            true
+        }
+
+        (
+            Capability::CommandDisplay {
+                command,
+                timeout_ms,
+                ..
+            },
+            LogicalEvent::Button(e),
+        ) if e.pressed => {
+            refresh_command_display(command, Duration::from_millis(*timeout_ms), system_state);
             true
         }
 
@@ -152,42 +256,154 @@ pub fn handle_event(
 }
 
 /// Check if a command binding is in an active state.
+///
+/// A binding with a `state_command` is active when its last probe result says
+/// so; other toggles use the state flipped on each press.
 pub fn is_active(binding: &Binding, state: &SystemState) -> bool {
-    if let Capability::RunCommand { toggle: true, .. } = &binding.capability {
-        // Check toggle state for this binding
-        let key = binding_key(binding);
-        state.toggle_states.get(&key).copied().unwrap_or(false)
+    match &binding.capability {
+        Capability::RunCommand {
+            state_command: Some(probe),
+            state_match,
+            ..
+        } => state
+            .command_outputs
+            .get(probe)
+            .is_some_and(|output| output.is_active(state_match.as_deref())),
+        capability @ Capability::RunCommand { .. } if capability.is_toggle() => {
+            // Check toggle state for this binding
+            let key = binding_key(binding);
+            state.toggle_states.get(&key).copied().unwrap_or(false)
+        }
+        _ => false,
+    }
+}
+
+/// State key for toggles and commands with a state probe: "on" or "off".
+pub fn state(binding: &Binding, state: &SystemState) -> Option<&'static str> {
+    match &binding.capability {
+        Capability::RunCommand {
+            state_command: Some(_),
+            ..
+        } => {}
+        capability @ Capability::RunCommand { .. } if capability.is_toggle() => {}
+        _ => return None,
+    }
+    Some(if is_active(binding, state) {
+        "on"
     } else {
-        false
+        "off"
+    })
+}
+
+/// Text shown on a CommandDisplay key: the command's last output, cut to
+/// `max_length` characters.
+pub fn display_text(binding: &Binding, state: &SystemState) -> Option<String> {
+    let Capability::CommandDisplay {
+        command,
+        max_length,
+        ..
+    } = &binding.capability
+    else {
+        return None;
+    };
+    let output = state.display_outputs.get(command)?;
+    Some(truncate_output(output, *max_length))
+}
+
+/// Cut text to `max_length` characters, ending in an ellipsis when shortened
+fn truncate_output(text: &str, max_length: usize) -> String {
+    if text.chars().count() <= max_length {
+        return text.to_string();
     }
+    let mut truncated: String = text.chars().take(max_length.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
 }
 
-/// Get a unique key for a binding (used for toggle state tracking)
+/// Run a RunCommand press: `off_command` when active, `on_command` when not,
+/// falling back to `command`.
+fn run_command_binding(binding: &Binding, system_state: &Arc<Mutex<SystemState>>) {
+    let Capability::RunCommand {
+        command,
+        state_command,
+        on_command,
+        off_command,
+        ..
+    } = &binding.capability
+    else {
+        return;
+    };
+    let active = system_state
+        .lock()
+        .map(|state| is_active(binding, &state))
+        .unwrap_or(false);
+    let to_run = if active { off_command } else { on_command };
+    run_shell_command(to_run.as_deref().unwrap_or(command), binding);
+
+    if state_command.is_some() {
+        // The probe decides the new state
+        request_state_check();
+    } else if binding.capability.is_toggle() {
+        flip_toggle_state(binding, system_state);
+    }
+}
+
+/// Get a unique key for a binding on the deck it was pressed on (used for
+/// toggle state and child process tracking)
 fn binding_key(binding: &Binding) -> String {
     let input_key = match &binding.input {
         InputRef::Button { index } => format!("btn:{}", index),
         InputRef::Encoder { index } => format!("enc:{}", index),
         InputRef::EncoderPress { index } => format!("encp:{}", index),
         InputRef::Swipe => "swipe".to_string(),
+        InputRef::TouchTap { section } => format!("tap:{}", section),
+        InputRef::TouchLongPress { section } => format!("tlp:{}", section),
+        InputRef::Chord { indices } => {
+            let keys: Vec<String> = normalize_chord(indices)
+                .iter()
+                .map(|i| i.to_string())
+                .collect();
+            format!("chord:{}", keys.join("+"))
+        }
+        InputRef::Pedal { position } => format!("pedal:{}", position.index()),
+    };
+    let key = match &binding.folder {
+        Some(folder) => format!("{}:{}/{}", input_key, folder, binding.page),
+        None => format!("{}:{}", input_key, binding.page),
     };
-    format!("{}:{}", input_key, binding.page)
+    match &binding.device {
+        Some(serial) => format!("{}@{}", key, serial),
+        None => key,
+    }
 }
 
-/// Flip the toggle state for a binding and request image sync
+/// Flip the toggle state for a binding and redraw its key
 fn flip_toggle_state(binding: &Binding, system_state: &Arc<Mutex<SystemState>>) {
     let key = binding_key(binding);
     if let Ok(mut state) = system_state.lock() {
         let current = state.toggle_states.get(&key).copied().unwrap_or(false);
         state.toggle_states.insert(key, !current);
     }
-    request_image_sync();
+    request_key_sync(binding);
 }
 
 // ─────────────────────────────────────────────────────────────────
 // Command execution functions
 // ─────────────────────────────────────────────────────────────────
 
-fn run_shell_command(cmd: &str) {
+/// Start a RunCommand binding's command with its working directory and
+/// environment, and track it until it exits.
+fn run_shell_command(cmd: &str, binding: &Binding) {
+    let Capability::RunCommand {
+        cwd,
+        env,
+        timeout_ms,
+        kill_on_release,
+        ..
+    } = &binding.capability
+    else {
+        return;
+    };
     let cmd = cmd.trim();
     if cmd.is_empty() {
         eprintln!("Warning: Attempted to run empty command");
@@ -210,7 +426,10 @@ fn run_shell_command(cmd: &str) {
             return;
         }
         None => {
-            eprintln!("Warning: Failed to parse command (invalid quoting): {}", cmd);
+            eprintln!(
+                "Warning: Failed to parse command (invalid quoting): {}",
+                cmd
+            );
             return;
         }
     };
@@ -219,14 +438,187 @@ fn run_shell_command(cmd: &str) {
     eprintln!("Executing command: {:?}", args);
 
     // Execute directly without shell (no injection possible)
-    match Command::new(&args[0]).args(&args[1..]).spawn() {
-        Ok(_) => {}
-        Err(e) => eprintln!("Failed to execute command '{}': {}", cmd, e),
+    let mut process = Command::new(&args[0]);
+    process.args(&args[1..]).envs(env).stdin(Stdio::null());
+    if let Some(dir) = cwd.as_deref().filter(|dir| !dir.trim().is_empty()) {
+        process.current_dir(expand_home(dir.trim()));
+    }
+
+    let exit = CommandExitEvent {
+        command: cmd.to_string(),
+        device: binding.device.clone(),
+        input: binding.input.clone(),
+        page: binding.page,
+        folder: binding.folder.clone(),
+        outcome: CommandOutcome::FailedToStart,
+        success: false,
+        code: None,
+        error: None,
+    };
+    match process.spawn() {
+        Ok(child) => track_child(TrackedChild {
+            child,
+            key: binding_key(binding),
+            deadline: timeout_ms.map(|ms| Instant::now() + Duration::from_millis(ms)),
+            kill_on_release: *kill_on_release,
+            killed: None,
+            exit,
+        }),
+        Err(e) => {
+            eprintln!("Failed to execute command '{}': {}", cmd, e);
+            report_exit(CommandExitEvent {
+                error: Some(e.to_string()),
+                ..exit
+            });
+        }
+    }
+}
+
+/// Expand a leading `~` to the home directory
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix('~'), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) if rest.is_empty() || rest.starts_with('/') => {
+            format!("{}{}", home, rest)
+        }
+        _ => path.to_string(),
     }
 }
 
+// ─────────────────────────────────────────────────────────────────
+// Child process tracking
+// ─────────────────────────────────────────────────────────────────
+
+/// A RunCommand child watched until it exits
+struct TrackedChild {
+    child: Child,
+    /// Binding it was started from, so a release can find it
+    key: String,
+    deadline: Option<Instant>,
+    kill_on_release: bool,
+    /// Why it was killed, if it was
+    killed: Option<CommandOutcome>,
+    /// Reported once it exits, with the outcome filled in
+    exit: CommandExitEvent,
+}
+
+impl TrackedChild {
+    fn finish(self, status: ExitStatus) -> CommandExitEvent {
+        CommandExitEvent {
+            outcome: self.killed.unwrap_or(CommandOutcome::Exited),
+            success: status.success(),
+            code: status.code(),
+            ..self.exit
+        }
+    }
+}
+
+struct Children {
+    running: Vec<TrackedChild>,
+    /// Whether a reaper thread is watching `running`
+    reaper_running: bool,
+}
+
+static CHILDREN: Mutex<Children> = Mutex::new(Children {
+    running: Vec::new(),
+    reaper_running: false,
+});
+
+/// Exits waiting to be emitted to the frontend
+static COMMAND_EXITS: Mutex<Vec<CommandExitEvent>> = Mutex::new(Vec::new());
+
+fn children() -> std::sync::MutexGuard<'static, Children> {
+    CHILDREN.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Start watching a child, starting the reaper thread if it isn't running.
+fn track_child(tracked: TrackedChild) {
+    let mut children = children();
+    children.running.push(tracked);
+    if !children.reaper_running {
+        children.reaper_running = true;
+        std::thread::spawn(reap_children);
+    }
+}
+
+/// Wait on tracked children, killing any past their deadline, until none
+/// are left.
+fn reap_children() {
+    loop {
+        std::thread::sleep(REAP_INTERVAL);
+        let mut children = children();
+        let mut exits = Vec::new();
+        let mut i = 0;
+        while i < children.running.len() {
+            let tracked = &mut children.running[i];
+            if tracked.killed.is_none()
+                && tracked
+                    .deadline
+                    .is_some_and(|deadline| Instant::now() >= deadline)
+            {
+                let _ = tracked.child.kill();
+                tracked.killed = Some(CommandOutcome::TimedOut);
+            }
+            match tracked.child.try_wait() {
+                Ok(None) => i += 1,
+                Ok(Some(status)) => exits.push(children.running.swap_remove(i).finish(status)),
+                Err(e) => {
+                    eprintln!(
+                        "Failed to wait for command '{}': {}",
+                        tracked.exit.command, e
+                    );
+                    children.running.swap_remove(i);
+                }
+            }
+        }
+        if children.running.is_empty() {
+            children.reaper_running = false;
+        }
+        let done = !children.reaper_running;
+        drop(children);
+
+        exits.into_iter().for_each(report_exit);
+        if done {
+            return;
+        }
+    }
+}
+
+/// Kill the running children of a `kill_on_release` binding.
+fn kill_released_children(binding: &Binding) {
+    let key = binding_key(binding);
+    for tracked in children()
+        .running
+        .iter_mut()
+        .filter(|t| t.kill_on_release && t.key == key)
+    {
+        let _ = tracked.child.kill();
+        tracked.killed = Some(CommandOutcome::Killed);
+    }
+}
+
+/// Queue an exit for the frontend. Kills on release are expected, not failures.
+fn report_exit(exit: CommandExitEvent) {
+    if !exit.success && exit.outcome != CommandOutcome::Killed {
+        eprintln!(
+            "Command '{}' failed: {:?} (code {:?})",
+            exit.command, exit.outcome, exit.code
+        );
+    }
+    COMMAND_EXITS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(exit);
+}
+
+/// Take the command exits not yet emitted to the frontend.
+pub fn take_command_exits() -> Vec<CommandExitEvent> {
+    std::mem::take(&mut *COMMAND_EXITS.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
 /// Characters that could be used for shell injection or command chaining
-const DANGEROUS_CHARS: &[char] = &['$', '`', ';', '|', '&', '>', '<', '(', ')', '{', '}', '[', ']', '!', '\n', '\r'];
+const DANGEROUS_CHARS: &[char] = &[
+    '$', '`', ';', '|', '&', '>', '<', '(', ')', '{', '}', '[', ']', '!', '\n', '\r',
+];
 
 fn launch_app(app: &str) {
     let app = app.trim();
@@ -237,7 +629,10 @@ fn launch_app(app: &str) {
 
     // Reject any shell metacharacters that could be used for injection
     if app.chars().any(|c| DANGEROUS_CHARS.contains(&c)) {
-        eprintln!("Warning: Application name contains dangerous characters, rejected: {}", app);
+        eprintln!(
+            "Warning: Application name contains dangerous characters, rejected: {}",
+            app
+        );
         return;
     }
 
@@ -250,7 +645,10 @@ fn launch_app(app: &str) {
     // For absolute paths, only allow known safe directories
     if app.starts_with('/') {
         let allowed_prefixes = ["/usr/bin/", "/usr/local/bin/", "/bin/", "/opt/"];
-        if !allowed_prefixes.iter().any(|prefix| app.starts_with(prefix)) {
+        if !allowed_prefixes
+            .iter()
+            .any(|prefix| app.starts_with(prefix))
+        {
             eprintln!("Warning: Absolute path not in allowed directories: {}", app);
             return;
         }
@@ -303,3 +701,109 @@ fn open_url(url: &str) {
         Err(e) => eprintln!("Failed to open URL '{}': {}", url, e),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn binding(capability: Capability) -> Binding {
+        Binding::new(InputRef::Button { index: 0 }, capability)
+    }
+
+    #[test]
+    fn display_text_is_cut_to_max_length() {
+        let binding = binding(Capability::CommandDisplay {
+            command: "sensors-temp".to_string(),
+            interval_ms: 5000,
+            timeout_ms: 2000,
+            max_length: 5,
+        });
+        let mut state = SystemState::default();
+        assert_eq!(display_text(&binding, &state), None);
+
+        state
+            .display_outputs
+            .insert("sensors-temp".to_string(), "54°C".to_string());
+        assert_eq!(display_text(&binding, &state).as_deref(), Some("54°C"));
+
+        state.display_outputs.insert(
+            "sensors-temp".to_string(),
+            "feature/long-branch".to_string(),
+        );
+        assert_eq!(display_text(&binding, &state).as_deref(), Some("feat…"));
+    }
+
+    #[test]
+    fn command_exits_are_reported() {
+        let run = |command: &str, timeout_ms| {
+            let binding = binding(Capability::RunCommand {
+                command: command.to_string(),
+                toggle: false,
+                state_command: None,
+                state_match: None,
+                on_command: None,
+                off_command: None,
+                cwd: Some("/".to_string()),
+                env: BTreeMap::from([("DECK_TEST".to_string(), "1".to_string())]),
+                timeout_ms,
+                kill_on_release: false,
+            });
+            run_shell_command(command, &binding);
+        };
+        let exit_3 = "sh -c 'exit 3'";
+        let with_env = r#"sh -c 'test "$(pwd)" = / && test "$DECK_TEST" = 1'"#;
+        run(exit_3, None);
+        run(with_env, None);
+        run("sleep 5", Some(50));
+        run("definitely-not-a-command-xyz", None);
+
+        let deadline = Instant::now() + Duration::from_secs(3);
+        let mut exits = Vec::new();
+        while exits.len() < 4 && Instant::now() < deadline {
+            exits.extend(take_command_exits());
+            std::thread::sleep(REAP_INTERVAL);
+        }
+        let exit = |command: &str| {
+            exits
+                .iter()
+                .find(|e| e.command == command)
+                .expect("reported")
+        };
+
+        assert_eq!(exit(exit_3).outcome, CommandOutcome::Exited);
+        assert_eq!(exit(exit_3).code, Some(3));
+        assert!(exit(with_env).success);
+        assert_eq!(exit("sleep 5").outcome, CommandOutcome::TimedOut);
+        assert_eq!(
+            exit("definitely-not-a-command-xyz").outcome,
+            CommandOutcome::FailedToStart
+        );
+        assert!(exit("definitely-not-a-command-xyz").error.is_some());
+    }
+
+    #[test]
+    fn toggle_state_is_per_deck() {
+        let on = |device: &str| Binding {
+            device: Some(device.to_string()),
+            ..binding(Capability::RunCommand {
+                command: "true".to_string(),
+                toggle: true,
+                state_command: None,
+                state_match: None,
+                on_command: None,
+                off_command: None,
+                cwd: None,
+                env: BTreeMap::new(),
+                timeout_ms: None,
+                kill_on_release: false,
+            })
+        };
+        let state = Arc::new(Mutex::new(SystemState::default()));
+
+        flip_toggle_state(&on("DECK-A"), &state);
+        let state = state.lock().unwrap();
+        assert!(is_active(&on("DECK-A"), &state));
+        assert!(!is_active(&on("DECK-B"), &state));
+    }
+}
@@ -0,0 +1,121 @@
+//! Keyboard capabilities: SendHotkey, TypeText.
+//!
+//! Keys are sent through the uinput virtual keyboard in
+//! `crate::virtual_keyboard`, so they work the same under Wayland and X11.
+
+use crate::binding::Binding;
+use crate::capability::Capability;
+use crate::input_processor::LogicalEvent;
+use crate::plugin::{ActionOutcome, CapabilityMetadata, ParameterDef, ParameterType};
+use crate::state_manager::SystemState;
+use crate::virtual_keyboard;
+use std::sync::{Arc, Mutex};
+
+/// Get capability metadata for all keyboard capabilities.
+pub fn capabilities() -> Vec<CapabilityMetadata> {
+    vec![
+        CapabilityMetadata {
+            id: "SendHotkey",
+            name: "Send Hotkey",
+            description: "Press a keyboard shortcut",
+            plugin_id: "core",
+            supports_button: true,
+            supports_encoder: false,
+            supports_encoder_press: true,
+            parameters: vec![ParameterDef {
+                name: "keys",
+                param_type: ParameterType::String,
+                default_value: "ctrl+c",
+                description: "Keys joined with + (e.g., ctrl+shift+t, super+f2)",
+            }],
+        },
+        CapabilityMetadata {
+            id: "TypeText",
+            name: "Type Text",
+            description: "Type a piece of text",
+            plugin_id: "core",
+            supports_button: true,
+            supports_encoder: false,
+            supports_encoder_press: true,
+            parameters: vec![ParameterDef {
+                name: "text",
+                param_type: ParameterType::String,
+                default_value: "",
+                description: "Text to type (US keyboard characters)",
+            }],
+        },
+    ]
+}
+
+/// Handle keyboard-related events.
+///
+/// Returns `ActionOutcome::Error` when the virtual keyboard could not send
+/// the keys, so the failure shows on the key.
+pub fn handle_event(
+    event: &LogicalEvent,
+    binding: &Binding,
+    _system_state: &Arc<Mutex<SystemState>>,
+) -> ActionOutcome {
+    let result = match (&binding.capability, event) {
+        (
+            Capability::SendHotkey { keys },
+            LogicalEvent::Button(e) | LogicalEvent::EncoderPress(e),
+        ) if e.pressed => virtual_keyboard::send_hotkey(keys),
+        (
+            Capability::TypeText { text },
+            LogicalEvent::Button(e) | LogicalEvent::EncoderPress(e),
+        ) if e.pressed => virtual_keyboard::type_text(text),
+        _ => return ActionOutcome::Ignored,
+    };
+
+    match result {
+        Ok(()) => ActionOutcome::Ok,
+        Err(e) => ActionOutcome::Error(format!("Keyboard input failed: {:#}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binding::InputRef;
+    use crate::events::ButtonEvent;
+    use crate::virtual_keyboard::{set_backend, KeyEvent, KeyboardBackend};
+
+    struct RecordingKeyboard(Arc<Mutex<Vec<KeyEvent>>>);
+
+    impl KeyboardBackend for RecordingKeyboard {
+        fn send(&mut self, events: &[KeyEvent]) -> anyhow::Result<()> {
+            self.0.lock().unwrap().extend_from_slice(events);
+            Ok(())
+        }
+    }
+
+    fn binding(capability: Capability) -> Binding {
+        Binding::new(InputRef::Button { index: 0 }, capability)
+    }
+
+    #[test]
+    fn presses_send_keys_to_the_backend() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        set_backend(Box::new(RecordingKeyboard(sent.clone())));
+        let state = Arc::new(Mutex::new(SystemState::default()));
+        let press = |pressed| LogicalEvent::Button(ButtonEvent { index: 0, pressed });
+
+        let hotkey = binding(Capability::SendHotkey {
+            keys: "alt+tab".to_string(),
+        });
+        assert_eq!(
+            handle_event(&press(true), &hotkey, &state),
+            ActionOutcome::Ok
+        );
+        assert!(!handle_event(&press(false), &hotkey, &state).handled());
+        assert_eq!(sent.lock().unwrap().len(), 4);
+
+        let text = binding(Capability::TypeText {
+            text: "ok".to_string(),
+        });
+        assert_eq!(handle_event(&press(true), &text, &state), ActionOutcome::Ok);
+        let codes: Vec<u16> = sent.lock().unwrap()[4..].iter().map(|e| e.code).collect();
+        assert_eq!(codes, vec![24, 24, 37, 37]);
+    }
+}
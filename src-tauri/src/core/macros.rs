@@ -0,0 +1,20 @@
+//! Macro capability: run several capabilities in order on one press.
+//!
+//! Steps can belong to any plugin, so `PluginRegistry::handle_event` runs
+//! macros itself; this module only describes the capability.
+
+use crate::plugin::CapabilityMetadata;
+
+/// Get capability metadata for the macro capability.
+pub fn capabilities() -> Vec<CapabilityMetadata> {
+    vec![CapabilityMetadata {
+        id: "Macro",
+        name: "Macro",
+        description: "Run several actions in order, with optional delays between them",
+        plugin_id: "core",
+        supports_button: true,
+        supports_encoder: false,
+        supports_encoder_press: true,
+        parameters: vec![],
+    }]
+}
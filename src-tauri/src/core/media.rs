@@ -114,13 +114,28 @@ pub fn is_active(binding: &Binding, state: &SystemState) -> bool {
     matches!(&binding.capability, Capability::MediaPlayPause if state.is_playing)
 }
 
+/// State key for play/pause: "playing", "paused" or "stopped".
+pub fn state(binding: &Binding, state: &SystemState) -> Option<&'static str> {
+    if !matches!(&binding.capability, Capability::MediaPlayPause) {
+        return None;
+    }
+    Some(match (state.is_playing, state.is_paused) {
+        (true, _) => "playing",
+        (false, true) => "paused",
+        (false, false) => "stopped",
+    })
+}
+
 // ─────────────────────────────────────────────────────────────────
 // Media control functions (using playerctl)
 // ─────────────────────────────────────────────────────────────────
 
 fn media_play_pause() {
     if let Err(e) = Command::new("playerctl").arg("play-pause").status() {
-        eprintln!("Failed to play/pause media (is playerctl installed?): {}", e);
+        eprintln!(
+            "Failed to play/pause media (is playerctl installed?): {}",
+            e
+        );
     }
 }
 
@@ -4,16 +4,25 @@
 //! It provides:
 //! - Audio control (SystemAudio, Mute, Volume, Microphone)
 //! - Media control (PlayPause, Next, Previous, Stop)
-//! - Command execution (RunCommand, LaunchApp, OpenURL)
+//! - Command execution (RunCommand, CommandDisplay, LaunchApp, OpenURL)
+//! - Keyboard input (SendHotkey, TypeText)
+//! - Navigation (OpenFolder, NextPage, PreviousPage, GoToPage, PageByEncoder)
+//! - Macros (Macro)
 
 pub mod audio;
 pub mod commands;
+pub mod keyboard;
+pub mod macros;
 pub mod media;
+pub mod navigation;
 
 use crate::binding::Binding;
 use crate::impl_owns_capability;
 use crate::input_processor::LogicalEvent;
-use crate::plugin::{CapabilityMetadata, Plugin, PluginConfig};
+use crate::label_template;
+use crate::plugin::{
+    active_state_key, ActionOutcome, BindingValue, CapabilityMetadata, Plugin, PluginConfig,
+};
 use crate::state_manager::SystemState;
 use std::any::Any;
 use std::sync::{Arc, Mutex};
@@ -51,6 +60,9 @@ impl Plugin for CorePlugin {
         caps.extend(audio::capabilities());
         caps.extend(media::capabilities());
         caps.extend(commands::capabilities());
+        caps.extend(keyboard::capabilities());
+        caps.extend(navigation::capabilities());
+        caps.extend(macros::capabilities());
         caps
     }
 
@@ -59,18 +71,25 @@ impl Plugin for CorePlugin {
         event: &LogicalEvent,
         binding: &Binding,
         system_state: &Arc<Mutex<SystemState>>,
-    ) -> bool {
-        // Try each module in order
+    ) -> ActionOutcome {
+        // Try each module in order; only keyboard input can fail synchronously
         if audio::handle_event(event, binding, system_state) {
-            return true;
+            return ActionOutcome::Ok;
         }
         if media::handle_event(event, binding, system_state) {
-            return true;
+            return ActionOutcome::Ok;
         }
         if commands::handle_event(event, binding, system_state) {
-            return true;
+            return ActionOutcome::Ok;
         }
-        false
+        let outcome = keyboard::handle_event(event, binding, system_state);
+        if outcome.handled() {
+            return outcome;
+        }
+        if navigation::handle_event(event, binding, system_state) {
+            return ActionOutcome::Ok;
+        }
+        ActionOutcome::Ignored
     }
 
     impl_owns_capability!(
@@ -87,22 +106,52 @@ impl Plugin for CorePlugin {
         "MediaPrevious",
         "MediaStop",
         "RunCommand",
+        "CommandDisplay",
         "LaunchApp",
-        "OpenURL"
+        "OpenURL",
+        "SendHotkey",
+        "TypeText",
+        "OpenFolder",
+        "NextPage",
+        "PreviousPage",
+        "GoToPage",
+        "PageByEncoder",
+        "Macro"
     );
 
-    fn is_active(&self, binding: &Binding, system_state: &SystemState) -> bool {
-        // Check each module
-        if audio::is_active(binding, system_state) {
-            return true;
-        }
-        if media::is_active(binding, system_state) {
-            return true;
-        }
-        if commands::is_active(binding, system_state) {
-            return true;
+    fn state(&self, binding: &Binding, system_state: &SystemState) -> Option<String> {
+        let state = audio::state(binding, system_state)
+            .or_else(|| media::state(binding, system_state))
+            .or_else(|| commands::state(binding, system_state))
+            .unwrap_or_else(|| {
+                active_state_key(
+                    audio::is_active(binding, system_state)
+                        || media::is_active(binding, system_state)
+                        || commands::is_active(binding, system_state),
+                )
+            });
+        Some(state.to_string())
+    }
+
+    fn display_text(&self, binding: &Binding, system_state: &SystemState) -> Option<String> {
+        commands::display_text(binding, system_state)
+    }
+
+    fn label_value(
+        &self,
+        _binding: &Binding,
+        key: &str,
+        argument: Option<&str>,
+        system_state: &SystemState,
+    ) -> Option<String> {
+        match key {
+            "time" => label_template::format_local_time(argument.unwrap_or("%H:%M")),
+            _ => audio::label_value(key, system_state),
         }
-        false
+    }
+
+    fn value(&self, binding: &Binding, system_state: &SystemState) -> Option<BindingValue> {
+        audio::value(binding, system_state)
     }
 
     fn initialize(&mut self, _config: &PluginConfig) -> anyhow::Result<()> {
@@ -157,6 +206,11 @@ mod tests {
         assert!(ids.contains(&"RunCommand"));
         assert!(ids.contains(&"LaunchApp"));
         assert!(ids.contains(&"OpenURL"));
+        assert!(ids.contains(&"SendHotkey"));
+        assert!(ids.contains(&"TypeText"));
+        assert!(ids.contains(&"OpenFolder"));
+        assert!(ids.contains(&"PageByEncoder"));
+        assert!(ids.contains(&"Macro"));
     }
 
     #[test]
@@ -169,6 +223,43 @@ mod tests {
         assert!(!plugin.owns_capability("ElgatoKeyLight"));
     }
 
+    #[test]
+    fn core_plugin_resolves_audio_and_time_placeholders() {
+        let plugin = CorePlugin::new();
+        let binding: Binding = toml::from_str(
+            r#"
+            input = { type = "Button", index = 0 }
+            capability = { type = "MediaPlayPause" }
+            "#,
+        )
+        .unwrap();
+        let state = SystemState {
+            volume: Some(0.5),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            plugin
+                .label_value(&binding, "volume", None, &state)
+                .as_deref(),
+            Some("50")
+        );
+        assert_eq!(
+            plugin.label_value(&binding, "mic.volume", None, &state),
+            None
+        );
+        assert_eq!(
+            plugin
+                .label_value(&binding, "time", Some("%Y"), &state)
+                .map(|y| y.len()),
+            Some(4)
+        );
+        assert_eq!(
+            plugin.label_value(&binding, "obs.scene", None, &state),
+            None
+        );
+    }
+
     #[test]
     fn core_plugin_metadata_id() {
         let plugin = CorePlugin::new();
@@ -196,7 +287,10 @@ mod tests {
     #[test]
     fn core_plugin_metadata_description() {
         let plugin = CorePlugin::new();
-        assert_eq!(plugin.description(), "Audio, media controls, and shell commands");
+        assert_eq!(
+            plugin.description(),
+            "Audio, media controls, and shell commands"
+        );
     }
 
     #[test]
@@ -0,0 +1,138 @@
+//! Navigation capabilities: OpenFolder, NextPage, PreviousPage, GoToPage, PageByEncoder.
+//!
+//! Handlers only queue a request for the deck the binding was pressed on; that
+//! deck's event loop owns its navigation state and applies it on its next
+//! iteration.
+
+use crate::binding::Binding;
+use crate::capability::Capability;
+use crate::input_processor::LogicalEvent;
+use crate::navigation::{request_navigation, NavigationRequest};
+use crate::plugin::{CapabilityMetadata, ParameterDef, ParameterType};
+use crate::state_manager::SystemState;
+use std::sync::{Arc, Mutex};
+
+/// Get capability metadata for all navigation capabilities.
+pub fn capabilities() -> Vec<CapabilityMetadata> {
+    vec![
+        CapabilityMetadata {
+            id: "OpenFolder",
+            name: "Open Folder",
+            description:
+                "Open a folder of sub-pages. Button 1 becomes a back key inside the folder",
+            plugin_id: "core",
+            supports_button: true,
+            supports_encoder: false,
+            supports_encoder_press: true,
+            parameters: vec![ParameterDef {
+                name: "name",
+                param_type: ParameterType::String,
+                default_value: "Folder",
+                description: "Folder name (bindings with the same folder are shown inside)",
+            }],
+        },
+        CapabilityMetadata {
+            id: "NextPage",
+            name: "Next Page",
+            description: "Go to the next page",
+            plugin_id: "core",
+            supports_button: true,
+            supports_encoder: false,
+            supports_encoder_press: true,
+            parameters: vec![],
+        },
+        CapabilityMetadata {
+            id: "PreviousPage",
+            name: "Previous Page",
+            description: "Go to the previous page",
+            plugin_id: "core",
+            supports_button: true,
+            supports_encoder: false,
+            supports_encoder_press: true,
+            parameters: vec![],
+        },
+        CapabilityMetadata {
+            id: "GoToPage",
+            name: "Go to Page",
+            description: "Jump to a specific page",
+            plugin_id: "core",
+            supports_button: true,
+            supports_encoder: false,
+            supports_encoder_press: true,
+            parameters: vec![ParameterDef {
+                name: "page",
+                param_type: ParameterType::Integer,
+                default_value: "0",
+                description: "Page number (0 = first page)",
+            }],
+        },
+        CapabilityMetadata {
+            id: "PageByEncoder",
+            name: "Page Scroll",
+            description: "Rotate to scroll through pages",
+            plugin_id: "core",
+            supports_button: false,
+            supports_encoder: true,
+            supports_encoder_press: false,
+            parameters: vec![],
+        },
+    ]
+}
+
+/// Handle navigation-related events.
+///
+/// Returns `true` if the event was handled.
+pub fn handle_event(
+    event: &LogicalEvent,
+    binding: &Binding,
+    _system_state: &Arc<Mutex<SystemState>>,
+) -> bool {
+    // The event loop tags each binding with the deck it was pressed on
+    let device = binding.device.as_deref();
+    match (&binding.capability, event) {
+        (
+            Capability::OpenFolder { name },
+            LogicalEvent::Button(e) | LogicalEvent::EncoderPress(e),
+        ) if e.pressed => {
+            request_navigation(device, NavigationRequest::OpenFolder(name.clone()));
+            true
+        }
+
+        (Capability::NextPage, LogicalEvent::Button(e) | LogicalEvent::EncoderPress(e))
+            if e.pressed =>
+        {
+            request_navigation(device, NavigationRequest::NextPage);
+            true
+        }
+
+        (Capability::PreviousPage, LogicalEvent::Button(e) | LogicalEvent::EncoderPress(e))
+            if e.pressed =>
+        {
+            request_navigation(device, NavigationRequest::PreviousPage);
+            true
+        }
+
+        (
+            Capability::GoToPage { page },
+            LogicalEvent::Button(e) | LogicalEvent::EncoderPress(e),
+        ) if e.pressed => {
+            request_navigation(device, NavigationRequest::GoToPage(*page));
+            true
+        }
+
+        // One page per rotation event, whatever the delta
+        (Capability::PageByEncoder, LogicalEvent::Encoder(e)) if e.delta != 0 => {
+            request_navigation(
+                device,
+                if e.delta > 0 {
+                    NavigationRequest::NextPage
+                } else {
+                    NavigationRequest::PreviousPage
+                },
+            );
+            true
+        }
+
+        _ => false,
+    }
+}
@@ -1,31 +1,109 @@
+use crate::binding::Binding;
+use crate::page::Page;
 use elgato_streamdeck::info::Kind;
 use serde::{Deserialize, Serialize};
 
 /// Device information exposed to the frontend.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeviceInfo {
+    /// Serial number, used to tell several connected decks apart
+    #[serde(default)]
+    pub serial: String,
     pub model: String,
     pub button_count: u8,
     pub encoder_count: u8,
     pub rows: u8,
     pub columns: u8,
     pub has_touch_strip: bool,
+    /// False for the Pedal, which has no keys to draw on
+    pub has_display: bool,
 }
 
 impl DeviceInfo {
     /// Create DeviceInfo from a Stream Deck Kind.
     pub fn from_kind(kind: Kind) -> Self {
         Self {
+            serial: String::new(),
             model: kind_to_model_name(kind),
             button_count: kind.key_count(),
             encoder_count: kind.encoder_count(),
             rows: kind.row_count(),
             columns: kind.column_count(),
             has_touch_strip: kind.lcd_strip_size().is_some(),
+            has_display: kind.is_visual(),
         }
     }
 }
 
+/// Bindings and pages tied to one deck by serial. Untagged ones (the only kind
+/// in configs from before multi-deck support) show on every deck.
+pub trait OnDevice: Clone {
+    fn device(&self) -> Option<&str>;
+    fn set_device(&mut self, device: Option<String>);
+
+    fn on_device(&self, serial: &str) -> bool {
+        self.device().is_none_or(|device| device == serial)
+    }
+}
+
+impl OnDevice for Binding {
+    fn device(&self) -> Option<&str> {
+        self.device.as_deref()
+    }
+
+    fn set_device(&mut self, device: Option<String>) {
+        self.device = device;
+    }
+}
+
+impl OnDevice for Page {
+    fn device(&self) -> Option<&str> {
+        self.device.as_deref()
+    }
+
+    fn set_device(&mut self, device: Option<String>) {
+        self.device = device;
+    }
+}
+
+/// Copy out the items shown on one deck.
+pub fn for_device<T: OnDevice>(items: &[T], serial: &str) -> Vec<T> {
+    items
+        .iter()
+        .filter(|item| item.on_device(serial))
+        .cloned()
+        .collect()
+}
+
+/// Copy out the items shown on one deck, tagged with its serial so state kept
+/// per item (e.g., toggles) is kept per deck too.
+pub fn on_deck<T: OnDevice>(items: &[T], serial: &str) -> Vec<T> {
+    let mut items = for_device(items, serial);
+    for item in &mut items {
+        item.set_device(Some(serial.to_string()));
+    }
+    items
+}
+
+/// Edit one deck's items in place of the full list. Items the edit appends
+/// past the original length are tagged with `tag`.
+pub fn edit_for_device<T: OnDevice, R>(
+    items: &mut Vec<T>,
+    serial: &str,
+    tag: Option<&str>,
+    edit: impl FnOnce(&mut Vec<T>) -> R,
+) -> R {
+    let mut scoped = for_device(items, serial);
+    let existing = scoped.len();
+    let result = edit(&mut scoped);
+    for item in scoped.iter_mut().skip(existing) {
+        item.set_device(tag.map(String::from));
+    }
+    items.retain(|item| !item.on_device(serial));
+    items.extend(scoped);
+    result
+}
+
 /// Map Kind to human-readable model name.
 fn kind_to_model_name(kind: Kind) -> String {
     match kind {
@@ -69,6 +147,45 @@ mod tests {
         assert!(!info.has_touch_strip);
     }
 
+    #[test]
+    fn stream_deck_pedal_info() {
+        let info = DeviceInfo::from_kind(Kind::Pedal);
+        assert_eq!(info.model, "Stream Deck Pedal");
+        assert_eq!(info.button_count, 3);
+        assert!(!info.has_display);
+    }
+
+    #[test]
+    fn edits_only_touch_one_deck() {
+        let page = |id: usize, device: Option<&str>| Page {
+            id,
+            name: String::new(),
+            folder: None,
+            device: device.map(String::from),
+            background: None,
+            lcd_background: None,
+            order: id,
+        };
+        let mut pages = vec![page(0, None), page(1, Some("XL")), page(2, Some("PLUS"))];
+
+        assert_eq!(for_device(&pages, "XL").len(), 2);
+
+        edit_for_device(&mut pages, "PLUS", Some("PLUS"), |scoped| {
+            scoped.retain(|p| p.id != 0)
+        });
+        edit_for_device(&mut pages, "PLUS", Some("PLUS"), |scoped| {
+            scoped.push(page(3, None))
+        });
+        let mut ids: Vec<(usize, Option<&str>)> =
+            pages.iter().map(|p| (p.id, p.device())).collect();
+        ids.sort();
+        // The shared page is gone from every deck, the new one only shows on the Plus
+        assert_eq!(
+            ids,
+            vec![(1, Some("XL")), (2, Some("PLUS")), (3, Some("PLUS"))]
+        );
+    }
+
     #[test]
     fn stream_deck_mini_info() {
         let info = DeviceInfo::from_kind(Kind::Mini);
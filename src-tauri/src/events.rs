@@ -1,3 +1,5 @@
+use crate::binding::InputRef;
+use crate::capability::Capability;
 use serde::Serialize;
 
 #[derive(Debug, Serialize, Clone, PartialEq)]
@@ -6,10 +8,33 @@ pub struct ButtonEvent {
     pub pressed: bool,
 }
 
+/// Gesture recognised on a button by the input processor.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum GestureKind {
+    Tap,
+    DoubleTap,
+    LongPress,
+    HoldRepeat,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct GestureEvent {
+    pub index: usize,
+    pub gesture: GestureKind,
+}
+
+/// Several buttons held together (indices sorted ascending).
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ChordEvent {
+    pub indices: Vec<usize>,
+}
+
 #[derive(Debug, Serialize, Clone, PartialEq)]
 pub struct EncoderEvent {
     pub index: usize,
     pub delta: i8,
+    /// Turn speed: ticks on this encoder in the last 100 ms (including this one)
+    pub ticks_per_100ms: u32,
 }
 
 #[derive(Debug, Serialize, Clone, PartialEq)]
@@ -18,9 +43,28 @@ pub struct TouchSwipeEvent {
     pub end: (u16, u16),
 }
 
+/// Tap or long-press on the LCD touch strip
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct TouchPressEvent {
+    /// Encoder section under the touch point
+    pub section: usize,
+    pub position: (u16, u16),
+}
+
+/// Input event as emitted to the frontend, tagged with the deck it came from
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct DeviceEvent<T> {
+    /// Serial of the deck
+    pub device: String,
+    #[serde(flatten)]
+    pub event: T,
+}
+
 /// Device connection status event
 #[derive(Debug, Serialize, Clone, PartialEq)]
 pub struct ConnectionStatusEvent {
+    /// Serial of the deck that connected or disconnected
+    pub device: String,
     pub connected: bool,
     pub model: Option<String>,
 }
@@ -28,6 +72,55 @@ pub struct ConnectionStatusEvent {
 /// Page change event
 #[derive(Debug, Serialize, Clone, PartialEq)]
 pub struct PageChangeEvent {
+    /// Serial of the deck that changed page
+    pub device: String,
     pub page: usize,
     pub page_count: usize,
+    /// Open folder (None = root pages)
+    pub folder: Option<String>,
+    /// Open folder names, outermost first
+    pub breadcrumb: Vec<String>,
+}
+
+/// How a RunCommand child finished
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandOutcome {
+    /// Exited by itself (check `code`)
+    Exited,
+    /// Killed after running longer than its `timeout_ms`
+    TimedOut,
+    /// Killed because its key was released
+    Killed,
+    /// Could not be started (see `error`)
+    FailedToStart,
+}
+
+/// A RunCommand child finished or could not be started
+#[derive(Debug, Serialize, Clone)]
+pub struct CommandExitEvent {
+    pub command: String,
+    /// Serial of the deck the binding belongs to (None = any deck)
+    pub device: Option<String>,
+    pub input: InputRef,
+    pub page: usize,
+    pub folder: Option<String>,
+    pub outcome: CommandOutcome,
+    pub success: bool,
+    /// Exit code (None when killed by a signal or not started)
+    pub code: Option<i32>,
+    pub error: Option<String>,
+}
+
+/// Result of a bound action, emitted as `action:result`
+#[derive(Debug, Serialize, Clone)]
+pub struct ActionResultEvent {
+    /// Serial of the deck it was pressed on
+    pub device: String,
+    pub input: InputRef,
+    pub page: usize,
+    pub folder: Option<String>,
+    pub capability: Capability,
+    pub ok: bool,
+    pub error: Option<String>,
 }
@@ -0,0 +1,477 @@
+//! Application-aware page switching.
+//!
+//! Watches the focused window on Hyprland (event socket), Sway/i3 (i3-ipc) or
+//! X11 (`xprop -spy _NET_ACTIVE_WINDOW`) and queues a navigation request when
+//! the focus moves to or away from an app listed in `apps.toml`. A watcher
+//! whose socket closes or whose xprop exits is restarted with a backoff.
+
+use crate::navigation::{request_navigation, NavigationRequest, PageLocation};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// i3-ipc message magic
+const I3_MAGIC: &[u8; 6] = b"i3-ipc";
+
+/// i3-ipc SUBSCRIBE message type
+const I3_SUBSCRIBE: u32 = 2;
+
+/// i3-ipc window event type (event bit set)
+const I3_WINDOW_EVENT: u32 = 0x8000_0003;
+
+/// Wait before restarting a watcher that stopped, doubled while it keeps
+/// stopping quickly
+const MIN_RESTART_DELAY: Duration = Duration::from_secs(1);
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(60);
+
+/// Window that just received focus.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FocusedWindow {
+    /// Window class (X11 / Hyprland) or app_id (Wayland-native on Sway)
+    pub class: String,
+    pub title: String,
+}
+
+/// Page to show while a matching app has focus.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppRule {
+    /// Window class or app_id (case-insensitive exact match)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class: Option<String>,
+    /// Text contained in the window title (case-insensitive)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub page: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+}
+
+impl AppRule {
+    /// Whether the rule applies to a window. A rule without class or title
+    /// matches nothing.
+    pub fn matches(&self, window: &FocusedWindow) -> bool {
+        if self.class.is_none() && self.title.is_none() {
+            return false;
+        }
+        let class_ok = self
+            .class
+            .as_ref()
+            .is_none_or(|class| class.eq_ignore_ascii_case(&window.class));
+        let title_ok = self
+            .title
+            .as_ref()
+            .is_none_or(|title| window.title.to_lowercase().contains(&title.to_lowercase()));
+        class_ok && title_ok
+    }
+
+    fn location(&self) -> PageLocation {
+        PageLocation {
+            folder: self.folder.clone(),
+            page: self.page,
+        }
+    }
+}
+
+/// Page for the focused window: the first matching rule wins.
+pub fn target_for(rules: &[AppRule], window: &FocusedWindow) -> Option<PageLocation> {
+    rules
+        .iter()
+        .find(|rule| rule.matches(window))
+        .map(AppRule::location)
+}
+
+/// Start watching window focus in a background thread.
+/// Does nothing without rules or a supported desktop.
+pub fn start_focus_watcher(rules: Vec<AppRule>) {
+    if rules.is_empty() {
+        return;
+    }
+
+    thread::spawn(move || {
+        let mut last_target = None;
+        let mut on_focus = |window: FocusedWindow| {
+            let target = target_for(&rules, &window);
+            // Only act on transitions so manual paging within an app sticks
+            if target != last_target {
+                #[cfg(debug_assertions)]
+                eprintln!("Focus: {:?} -> {:?}", window, target);
+                last_target = target.clone();
+                request_navigation(None, NavigationRequest::FocusApp(target));
+            }
+        };
+
+        let mut delay = None;
+        loop {
+            let started = Instant::now();
+            match watch_desktop(&mut on_focus) {
+                // Nothing to watch at all; a compositor that is restarting
+                // may just not have its socket back yet
+                None if delay.is_none() => {
+                    eprintln!("Focus watcher: no supported compositor or X11 display found");
+                    return;
+                }
+                None => {}
+                Some(Ok(())) => eprintln!("Focus watcher stopped, restarting"),
+                Some(Err(e)) => eprintln!("Focus watcher error: {}, restarting", e),
+            }
+            let wait = restart_delay(delay, started.elapsed());
+            delay = Some(wait);
+            thread::sleep(wait);
+        }
+    });
+}
+
+/// Follow the focused window on whichever desktop is running until its
+/// socket closes or xprop exits. None without a supported compositor or X11
+/// display.
+fn watch_desktop(on_focus: impl FnMut(FocusedWindow)) -> Option<anyhow::Result<()>> {
+    let result = if let Some(path) = hyprland_socket_path() {
+        UnixStream::connect(&path)
+            .map_err(Into::into)
+            .and_then(|s| watch_hyprland(BufReader::new(s), on_focus))
+    } else if let Some(path) = sway_socket_path() {
+        UnixStream::connect(&path)
+            .map_err(Into::into)
+            .and_then(|s| watch_i3(s, on_focus))
+    } else if std::env::var_os("DISPLAY").is_some() {
+        watch_x11(on_focus)
+    } else {
+        return None;
+    };
+    Some(result)
+}
+
+/// How long to wait before restarting a watcher that ran for `ran_for`:
+/// double the previous wait while watchers keep stopping quickly, and start
+/// over from `MIN_RESTART_DELAY` once one has run for a while.
+fn restart_delay(previous: Option<Duration>, ran_for: Duration) -> Duration {
+    match previous {
+        Some(delay) if ran_for < MAX_RESTART_DELAY => (delay * 2).min(MAX_RESTART_DELAY),
+        _ => MIN_RESTART_DELAY,
+    }
+}
+
+// --- Hyprland ---
+
+/// `$XDG_RUNTIME_DIR/hypr/<signature>/.socket2.sock` (older releases use /tmp/hypr)
+fn hyprland_socket_path() -> Option<PathBuf> {
+    let signature = std::env::var("HYPRLAND_INSTANCE_SIGNATURE").ok()?;
+    let runtime = std::env::var("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| "/tmp".into());
+    [runtime.join("hypr"), PathBuf::from("/tmp/hypr")]
+        .into_iter()
+        .map(|dir| dir.join(&signature).join(".socket2.sock"))
+        .find(|path| path.exists())
+}
+
+/// Parse an `activewindow>>class,title` line from the Hyprland event socket.
+fn parse_hyprland_event(line: &str) -> Option<FocusedWindow> {
+    let data = line.strip_prefix("activewindow>>")?;
+    let (class, title) = data.split_once(',').unwrap_or((data, ""));
+    Some(FocusedWindow {
+        class: class.to_string(),
+        title: title.to_string(),
+    })
+}
+
+/// Read Hyprland events until the socket closes.
+fn watch_hyprland(
+    reader: impl BufRead,
+    mut on_focus: impl FnMut(FocusedWindow),
+) -> anyhow::Result<()> {
+    for line in reader.lines() {
+        if let Some(window) = parse_hyprland_event(&line?) {
+            on_focus(window);
+        }
+    }
+    Ok(())
+}
+
+// --- Sway / i3 ---
+
+fn sway_socket_path() -> Option<PathBuf> {
+    std::env::var_os("SWAYSOCK")
+        .or_else(|| std::env::var_os("I3SOCK"))
+        .map(PathBuf::from)
+}
+
+fn write_i3_message(stream: &mut impl Write, kind: u32, payload: &[u8]) -> std::io::Result<()> {
+    let mut message = Vec::with_capacity(14 + payload.len());
+    message.extend_from_slice(I3_MAGIC);
+    message.extend_from_slice(&(payload.len() as u32).to_ne_bytes());
+    message.extend_from_slice(&kind.to_ne_bytes());
+    message.extend_from_slice(payload);
+    stream.write_all(&message)
+}
+
+fn read_i3_message(stream: &mut impl Read) -> std::io::Result<(u32, Vec<u8>)> {
+    let mut header = [0u8; 14];
+    stream.read_exact(&mut header)?;
+    if &header[..6] != I3_MAGIC {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "bad i3-ipc magic",
+        ));
+    }
+    let len = u32::from_ne_bytes([header[6], header[7], header[8], header[9]]) as usize;
+    let kind = u32::from_ne_bytes([header[10], header[11], header[12], header[13]]);
+    let mut payload = vec![0u8; len];
+    stream.read_exact(&mut payload)?;
+    Ok((kind, payload))
+}
+
+/// Parse a window event payload, returning the window if it gained focus.
+fn parse_i3_window_event(payload: &[u8]) -> Option<FocusedWindow> {
+    let event: serde_json::Value = serde_json::from_slice(payload).ok()?;
+    if event["change"] != "focus" {
+        return None;
+    }
+    let container = &event["container"];
+    let class = container["app_id"]
+        .as_str()
+        .or_else(|| container["window_properties"]["class"].as_str())
+        .unwrap_or_default();
+    Some(FocusedWindow {
+        class: class.to_string(),
+        title: container["name"].as_str().unwrap_or_default().to_string(),
+    })
+}
+
+/// Subscribe to window events and read them until the socket closes.
+fn watch_i3<S: Read + Write>(
+    mut stream: S,
+    mut on_focus: impl FnMut(FocusedWindow),
+) -> anyhow::Result<()> {
+    write_i3_message(&mut stream, I3_SUBSCRIBE, br#"["window"]"#)?;
+    loop {
+        let (kind, payload) = match read_i3_message(&mut stream) {
+            Ok(message) => message,
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        if kind == I3_WINDOW_EVENT {
+            if let Some(window) = parse_i3_window_event(&payload) {
+                on_focus(window);
+            }
+        }
+    }
+}
+
+// --- X11 ---
+
+/// Parse `_NET_ACTIVE_WINDOW(WINDOW): window id # 0x3a00007`.
+fn parse_xprop_active_window(line: &str) -> Option<String> {
+    let id = line.rsplit("# ").next()?.trim();
+    // 0x0 means no window has focus
+    (id.starts_with("0x") && id != "0x0").then(|| id.to_string())
+}
+
+/// Parse `xprop -id <id> WM_CLASS _NET_WM_NAME` output.
+fn parse_xprop_window(output: &str) -> FocusedWindow {
+    let mut window = FocusedWindow::default();
+    for line in output.lines() {
+        let Some((name, value)) = line.split_once(" = ") else {
+            continue;
+        };
+        let values: Vec<&str> = value.split("\", \"").map(|v| v.trim_matches('"')).collect();
+        if name.starts_with("WM_CLASS") {
+            // WM_CLASS is "instance", "class"
+            window.class = values.last().copied().unwrap_or_default().to_string();
+        } else if name.starts_with("_NET_WM_NAME") {
+            window.title = value.trim_matches('"').to_string();
+        }
+    }
+    window
+}
+
+/// Follow `_NET_ACTIVE_WINDOW` changes until xprop exits.
+fn watch_x11(mut on_focus: impl FnMut(FocusedWindow)) -> anyhow::Result<()> {
+    let mut child = Command::new("xprop")
+        .args(["-root", "-spy", "_NET_ACTIVE_WINDOW"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    let Some(stdout) = child.stdout.take() else {
+        anyhow::bail!("xprop has no stdout");
+    };
+
+    let result = BufReader::new(stdout).lines().try_for_each(|line| {
+        if let Some(id) = parse_xprop_active_window(&line?) {
+            let output = Command::new("xprop")
+                .args(["-id", &id, "WM_CLASS", "_NET_WM_NAME"])
+                .output()?;
+            on_focus(parse_xprop_window(&String::from_utf8_lossy(&output.stdout)));
+        }
+        Ok(())
+    });
+
+    // Don't leave the spy running behind the watcher that replaces this one
+    let _ = child.kill();
+    let _ = child.wait();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::net::UnixListener;
+
+    fn rule(class: Option<&str>, title: Option<&str>, page: usize) -> AppRule {
+        AppRule {
+            class: class.map(String::from),
+            title: title.map(String::from),
+            page,
+            folder: None,
+        }
+    }
+
+    fn window(class: &str, title: &str) -> FocusedWindow {
+        FocusedWindow {
+            class: class.to_string(),
+            title: title.to_string(),
+        }
+    }
+
+    /// Fresh socket path for a fake IPC server
+    fn socket_path(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("deck-focus-{}-{}.sock", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn rules_match_class_and_title() {
+        let rules = vec![
+            rule(Some("firefox"), Some("youtube"), 1),
+            rule(Some("obs"), None, 2),
+        ];
+
+        assert_eq!(
+            target_for(&rules, &window("OBS", "Scenes")).map(|t| t.page),
+            Some(2)
+        );
+        assert_eq!(
+            target_for(&rules, &window("firefox", "Music - YouTube")).map(|t| t.page),
+            Some(1)
+        );
+        assert_eq!(target_for(&rules, &window("firefox", "Docs")), None);
+        assert!(!rule(None, None, 0).matches(&window("obs", "")));
+    }
+
+    #[test]
+    fn parses_hyprland_events() {
+        assert_eq!(
+            parse_hyprland_event("activewindow>>kitty,vim: notes, todo"),
+            Some(window("kitty", "vim: notes, todo"))
+        );
+        assert_eq!(parse_hyprland_event("workspace>>2"), None);
+    }
+
+    #[test]
+    fn reads_fake_hyprland_socket() {
+        let path = socket_path("hypr");
+        let listener = UnixListener::bind(&path).unwrap();
+        let server = thread::spawn(move || {
+            let (mut conn, _) = listener.accept().unwrap();
+            conn.write_all(b"workspace>>1\nactivewindow>>obs,OBS 30\nactivewindow>>kitty,shell\n")
+                .unwrap();
+        });
+
+        let mut seen = Vec::new();
+        let stream = UnixStream::connect(&path).unwrap();
+        watch_hyprland(BufReader::new(stream), |w| seen.push(w)).unwrap();
+        server.join().unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(
+            seen,
+            vec![window("obs", "OBS 30"), window("kitty", "shell")]
+        );
+    }
+
+    #[test]
+    fn reads_fake_sway_socket() {
+        let path = socket_path("sway");
+        let listener = UnixListener::bind(&path).unwrap();
+        let server = thread::spawn(move || {
+            let (mut conn, _) = listener.accept().unwrap();
+            let (kind, payload) = read_i3_message(&mut conn).unwrap();
+            assert_eq!(kind, I3_SUBSCRIBE);
+            assert_eq!(payload, br#"["window"]"#);
+
+            write_i3_message(&mut conn, I3_SUBSCRIBE, br#"{"success":true}"#).unwrap();
+            write_i3_message(
+                &mut conn,
+                I3_WINDOW_EVENT,
+                br#"{"change":"title","container":{"app_id":"foot","name":"x"}}"#,
+            )
+            .unwrap();
+            write_i3_message(
+                &mut conn,
+                I3_WINDOW_EVENT,
+                br#"{"change":"focus","container":{"app_id":"foot","name":"htop"}}"#,
+            )
+            .unwrap();
+            write_i3_message(
+                &mut conn,
+                I3_WINDOW_EVENT,
+                br#"{"change":"focus","container":{"app_id":null,"name":"Spotify","window_properties":{"class":"Spotify"}}}"#,
+            )
+            .unwrap();
+        });
+
+        let mut seen = Vec::new();
+        watch_i3(UnixStream::connect(&path).unwrap(), |w| seen.push(w)).unwrap();
+        server.join().unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(
+            seen,
+            vec![window("foot", "htop"), window("Spotify", "Spotify")]
+        );
+    }
+
+    #[test]
+    fn restart_delay_backs_off() {
+        let quick = Duration::from_millis(10);
+        assert_eq!(restart_delay(None, quick), MIN_RESTART_DELAY);
+        assert_eq!(
+            restart_delay(Some(MIN_RESTART_DELAY), quick),
+            MIN_RESTART_DELAY * 2
+        );
+        assert_eq!(
+            restart_delay(Some(MAX_RESTART_DELAY), quick),
+            MAX_RESTART_DELAY
+        );
+        // A watcher that ran for a while starts the backoff over
+        assert_eq!(
+            restart_delay(Some(MAX_RESTART_DELAY), Duration::from_secs(600)),
+            MIN_RESTART_DELAY
+        );
+    }
+
+    #[test]
+    fn parses_xprop_output() {
+        assert_eq!(
+            parse_xprop_active_window("_NET_ACTIVE_WINDOW(WINDOW): window id # 0x3a00007"),
+            Some("0x3a00007".to_string())
+        );
+        assert_eq!(
+            parse_xprop_active_window("_NET_ACTIVE_WINDOW(WINDOW): window id # 0x0"),
+            None
+        );
+
+        let output = "WM_CLASS(STRING) = \"Navigator\", \"firefox\"\n_NET_WM_NAME(UTF8_STRING) = \"Docs — Mozilla Firefox\"\n";
+        assert_eq!(
+            parse_xprop_window(output),
+            window("firefox", "Docs — Mozilla Firefox")
+        );
+    }
+}
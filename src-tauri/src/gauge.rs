@@ -0,0 +1,208 @@
+//! Gauges showing a binding's value (see `Plugin::value`) as a fraction of
+//! its range.
+//!
+//! Shapes are drawn pixel by pixel with anti-aliased edges, so they blend
+//! into whatever image is already on the canvas; fills are translucent so
+//! the image stays visible under them.
+
+use image::{Rgba, RgbaImage};
+
+/// Unfilled part of a gauge
+pub const TRACK: Rgba<u8> = Rgba([64, 64, 64, 255]);
+/// Filled part of a gauge unless the binding sets an icon color
+pub const ACCENT: Rgba<u8> = Rgba([59, 130, 246, 255]);
+
+/// Where an arc starts, in degrees clockwise from 3 o'clock (bottom left)
+const ARC_START: f32 = 135.0;
+/// How far an arc sweeps, leaving a gap at the bottom
+const ARC_SWEEP: f32 = 270.0;
+/// Where a ring starts (12 o'clock)
+const RING_START: f32 = 270.0;
+/// Opacity of a fill level, so the image under it stays visible
+const FILL_ALPHA: f32 = 0.45;
+
+/// Mix `color` into the pixel at (x, y) by `coverage` (0.0-1.0).
+fn blend(img: &mut RgbaImage, x: u32, y: u32, color: Rgba<u8>, coverage: f32) {
+    let alpha = coverage.clamp(0.0, 1.0) * color[3] as f32 / 255.0;
+    if alpha <= 0.0 {
+        return;
+    }
+    let pixel = img.get_pixel_mut(x, y);
+    for c in 0..3 {
+        pixel[c] = (pixel[c] as f32 * (1.0 - alpha) + color[c] as f32 * alpha).round() as u8;
+    }
+    pixel[3] = pixel[3].max((alpha * 255.0).round() as u8);
+}
+
+/// Draw a 270° arc around `center`, open at the bottom and filled clockwise
+/// up to `fraction`.
+pub fn draw_arc(
+    img: &mut RgbaImage,
+    center: (f32, f32),
+    radius: f32,
+    thickness: f32,
+    fraction: f32,
+    color: Rgba<u8>,
+) {
+    draw_sweep(
+        img,
+        center,
+        (radius, thickness),
+        (ARC_START, ARC_SWEEP),
+        fraction,
+        color,
+    );
+}
+
+/// Draw a full ring around `center`, filled clockwise from 12 o'clock up to
+/// `fraction`.
+pub fn draw_ring(
+    img: &mut RgbaImage,
+    center: (f32, f32),
+    radius: f32,
+    thickness: f32,
+    fraction: f32,
+    color: Rgba<u8>,
+) {
+    draw_sweep(
+        img,
+        center,
+        (radius, thickness),
+        (RING_START, 360.0),
+        fraction,
+        color,
+    );
+}
+
+/// Draw the `(start, sweep)` degrees of a circle's outline `(radius,
+/// thickness)`, filled clockwise up to `fraction` and the rest as track.
+fn draw_sweep(
+    img: &mut RgbaImage,
+    center: (f32, f32),
+    (radius, thickness): (f32, f32),
+    (start, sweep): (f32, f32),
+    fraction: f32,
+    color: Rgba<u8>,
+) {
+    let (cx, cy) = center;
+    let half = thickness / 2.0;
+    let filled = sweep * fraction.clamp(0.0, 1.0);
+    let reach = radius + half + 1.0;
+    let x_range = (cx - reach).max(0.0) as u32..((cx + reach).ceil() as u32).min(img.width());
+    let y_range = (cy - reach).max(0.0) as u32..((cy + reach).ceil() as u32).min(img.height());
+
+    for y in y_range {
+        for x in x_range.clone() {
+            let (dx, dy) = (x as f32 + 0.5 - cx, y as f32 + 0.5 - cy);
+            let coverage = half + 0.5 - ((dx * dx + dy * dy).sqrt() - radius).abs();
+            if coverage <= 0.0 {
+                continue;
+            }
+            // Image y grows downwards, so atan2 runs clockwise
+            let along = (dy.atan2(dx).to_degrees() - start).rem_euclid(360.0);
+            if along > sweep {
+                continue;
+            }
+            let shade = if along <= filled { color } else { TRACK };
+            blend(img, x, y, shade, coverage);
+        }
+    }
+}
+
+/// Draw a horizontal bar at (x, y), filled from the left up to `fraction`.
+pub fn draw_bar(
+    img: &mut RgbaImage,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    fraction: f32,
+    color: Rgba<u8>,
+) {
+    let filled = x as f32 + width as f32 * fraction.clamp(0.0, 1.0);
+    for py in y..(y + height).min(img.height()) {
+        for px in x..(x + width).min(img.width()) {
+            let coverage = (filled - px as f32).clamp(0.0, 1.0);
+            blend(img, px, py, TRACK, 1.0);
+            blend(img, px, py, color, coverage);
+        }
+    }
+}
+
+/// Tint the image from the bottom up to `fraction` of its height.
+pub fn draw_fill_up(img: &mut RgbaImage, fraction: f32, color: Rgba<u8>) {
+    let top = img.height() as f32 * (1.0 - fraction.clamp(0.0, 1.0));
+    for (_, y, pixel) in img.enumerate_pixels_mut() {
+        let coverage = (y as f32 + 1.0 - top).clamp(0.0, 1.0);
+        tint(pixel, color, coverage);
+    }
+}
+
+/// Tint the image from the left up to `fraction` of its width.
+pub fn draw_fill_across(img: &mut RgbaImage, fraction: f32, color: Rgba<u8>) {
+    let right = img.width() as f32 * fraction.clamp(0.0, 1.0);
+    for (x, _, pixel) in img.enumerate_pixels_mut() {
+        let coverage = (right - x as f32).clamp(0.0, 1.0);
+        tint(pixel, color, coverage);
+    }
+}
+
+/// Mix a translucent fill color into a pixel.
+fn tint(pixel: &mut Rgba<u8>, color: Rgba<u8>, coverage: f32) {
+    let alpha = coverage * FILL_ALPHA;
+    for c in 0..3 {
+        pixel[c] = (pixel[c] as f32 * (1.0 - alpha) + color[c] as f32 * alpha).round() as u8;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: Rgba<u8> = Rgba([0, 0, 0, 255]);
+    const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+
+    #[test]
+    fn arc_fills_clockwise_from_bottom_left() {
+        let mut img = RgbaImage::from_pixel(100, 100, BLACK);
+        draw_arc(&mut img, (50.0, 50.0), 40.0, 8.0, 0.5, RED);
+
+        // Left of centre is early in the sweep, right of centre is late
+        assert_eq!(*img.get_pixel(10, 50), RED);
+        assert_eq!(*img.get_pixel(89, 50), TRACK);
+        // The gap at the bottom and the middle stay untouched
+        assert_eq!(*img.get_pixel(50, 89), BLACK);
+        assert_eq!(*img.get_pixel(50, 50), BLACK);
+    }
+
+    #[test]
+    fn bar_fills_from_the_left() {
+        let mut img = RgbaImage::from_pixel(100, 20, BLACK);
+        draw_bar(&mut img, 10, 5, 80, 10, 0.25, RED);
+
+        assert_eq!(*img.get_pixel(15, 10), RED);
+        assert_eq!(*img.get_pixel(50, 10), TRACK);
+        assert_eq!(*img.get_pixel(5, 10), BLACK);
+        assert_eq!(*img.get_pixel(50, 2), BLACK);
+    }
+
+    #[test]
+    fn ring_and_fill_cover_their_fraction() {
+        let mut img = RgbaImage::from_pixel(100, 100, BLACK);
+        draw_ring(&mut img, (50.0, 50.0), 40.0, 8.0, 0.5, RED);
+        // Clockwise from the top: the right half is filled, the left is track
+        assert_eq!(*img.get_pixel(89, 50), RED);
+        assert_eq!(*img.get_pixel(10, 50), TRACK);
+        assert_eq!(*img.get_pixel(50, 89), RED);
+
+        let mut img = RgbaImage::from_pixel(10, 100, BLACK);
+        draw_fill_up(&mut img, 0.3, RED);
+        assert!(img.get_pixel(5, 90)[0] > 0);
+        assert_eq!(*img.get_pixel(5, 60), BLACK);
+
+        let mut img = RgbaImage::from_pixel(100, 10, BLACK);
+        draw_fill_across(&mut img, 0.3, RED);
+        assert!(img.get_pixel(10, 5)[0] > 0);
+        assert_eq!(*img.get_pixel(40, 5), BLACK);
+    }
+}
@@ -2,13 +2,17 @@
 //!
 //! Caches loaded images to avoid re-fetching URLs on every sync.
 //! Local files are also cached but can be invalidated if modified.
+//! Animated GIFs and APNGs are decoded into a separate cache of frames.
 
 use anyhow::{Context, Result};
-use image::{DynamicImage, RgbaImage};
+use image::codecs::gif::GifDecoder;
+use image::codecs::png::PngDecoder;
+use image::{AnimationDecoder, DynamicImage, ImageFormat, RgbaImage};
 use lru::LruCache;
+use std::io::Cursor;
 use std::num::NonZeroUsize;
 use std::path::Path;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant, SystemTime};
 
 /// Cache entry with metadata
@@ -20,6 +24,25 @@ struct CacheEntry {
     file_mtime: Option<SystemTime>,
 }
 
+/// One frame of an animated image
+#[derive(Clone)]
+pub struct Frame {
+    pub image: DynamicImage,
+    /// How long the frame is shown
+    pub delay: Duration,
+}
+
+/// Frames of an animated image, shared between the cache and the deck
+pub type Frames = Arc<[Frame]>;
+
+/// Decoded animation, or None for a source that turned out to be still or
+/// failed to load
+struct AnimationEntry {
+    frames: Option<Frames>,
+    cached_at: Instant,
+    file_mtime: Option<SystemTime>,
+}
+
 /// Global image cache
 static IMAGE_CACHE: Mutex<Option<ImageCache>> = Mutex::new(None);
 
@@ -29,18 +52,50 @@ const URL_CACHE_TTL: Duration = Duration::from_secs(300);
 /// Maximum number of entries in the cache (prevents unbounded growth)
 const MAX_CACHE_ENTRIES: usize = 100;
 
+/// Maximum number of decoded animations (each holds every frame)
+const MAX_ANIMATION_ENTRIES: usize = 20;
+
+/// Frames shorter than this are shown for `DEFAULT_FRAME_DELAY`, as browsers do
+const MIN_FRAME_DELAY: Duration = Duration::from_millis(20);
+const DEFAULT_FRAME_DELAY: Duration = Duration::from_millis(100);
+
 /// Image cache implementation using O(1) LRU eviction
 struct ImageCache {
     entries: LruCache<String, CacheEntry>,
+    animations: LruCache<String, AnimationEntry>,
 }
 
 impl ImageCache {
     fn new() -> Self {
         Self {
             entries: LruCache::new(NonZeroUsize::new(MAX_CACHE_ENTRIES).unwrap()),
+            animations: LruCache::new(NonZeroUsize::new(MAX_ANIMATION_ENTRIES).unwrap()),
         }
     }
 
+    /// Get a cached animation lookup if still valid. `Some(None)` means the
+    /// source is known not to be animated.
+    fn get_animation(&mut self, key: &str, source: &str) -> Option<Option<Frames>> {
+        let entry = self.animations.peek(key)?;
+        if !is_fresh(source, entry.cached_at, entry.file_mtime) {
+            self.animations.pop(key);
+            return None;
+        }
+        self.animations.get(key).map(|e| e.frames.clone())
+    }
+
+    /// Store an animation lookup for `source`
+    fn put_animation(&mut self, key: &str, source: &str, frames: Option<Frames>) {
+        self.animations.put(
+            key.to_string(),
+            AnimationEntry {
+                frames,
+                cached_at: Instant::now(),
+                file_mtime: file_mtime(source),
+            },
+        );
+    }
+
     /// Get a cached image if valid, or None if not cached/expired
     fn get(&mut self, source: &str) -> Option<DynamicImage> {
         // Use peek to check validity first without promoting
@@ -97,6 +152,32 @@ impl ImageCache {
     #[allow(dead_code)]
     fn clear(&mut self) {
         self.entries.clear();
+        self.animations.clear();
+    }
+}
+
+fn is_url(source: &str) -> bool {
+    source.starts_with("http://") || source.starts_with("https://")
+}
+
+/// Modification time of a local file (None for URLs)
+fn file_mtime(source: &str) -> Option<SystemTime> {
+    if is_url(source) {
+        return None;
+    }
+    std::fs::metadata(source)
+        .ok()
+        .and_then(|m| m.modified().ok())
+}
+
+/// Whether something cached from `source` at `cached_at` can still be used:
+/// URLs expire after `URL_CACHE_TTL`, local files when they change, appear or
+/// disappear.
+fn is_fresh(source: &str, cached_at: Instant, cached_mtime: Option<SystemTime>) -> bool {
+    if is_url(source) {
+        cached_at.elapsed() <= URL_CACHE_TTL
+    } else {
+        file_mtime(source) == cached_mtime
     }
 }
 
@@ -121,6 +202,81 @@ fn load_image_uncached(source: &str) -> Result<DynamicImage> {
     }
 }
 
+/// Fetch a source's raw bytes
+fn load_bytes_uncached(source: &str) -> Result<Vec<u8>> {
+    if is_url(source) {
+        let response = reqwest::blocking::get(source).context("Failed to fetch image from URL")?;
+        Ok(response
+            .bytes()
+            .context("Failed to read image bytes")?
+            .to_vec())
+    } else {
+        std::fs::read(source).context(format!("Failed to load image: {}", source))
+    }
+}
+
+/// Resize an image to fit within target_size, preserving aspect ratio.
+/// Smaller images are left as they are.
+fn fit_within(img: DynamicImage, target_size: u32) -> DynamicImage {
+    if img.width() > target_size || img.height() > target_size {
+        img.resize(
+            target_size,
+            target_size,
+            image::imageops::FilterType::Lanczos3,
+        )
+    } else {
+        img
+    }
+}
+
+/// Whether a source could be an animated GIF or APNG, judging by its name
+fn may_be_animated(source: &str) -> bool {
+    let path = source
+        .split(['?', '#'])
+        .next()
+        .unwrap_or(source)
+        .to_lowercase();
+    path.ends_with(".gif") || path.ends_with(".png") || path.ends_with(".apng")
+}
+
+/// Decode every frame of an animated GIF or APNG, each fitted to target_size.
+/// Returns None for still images and other formats.
+fn load_animation_uncached(source: &str, target_size: u32) -> Result<Option<Frames>> {
+    let bytes = load_bytes_uncached(source)?;
+    let frames = match image::guess_format(&bytes) {
+        Ok(ImageFormat::Gif) => GifDecoder::new(Cursor::new(&bytes))?
+            .into_frames()
+            .collect_frames()?,
+        Ok(ImageFormat::Png) => {
+            let decoder = PngDecoder::new(Cursor::new(&bytes))?;
+            if !decoder.is_apng()? {
+                return Ok(None);
+            }
+            decoder.apng()?.into_frames().collect_frames()?
+        }
+        _ => return Ok(None),
+    };
+    if frames.len() < 2 {
+        return Ok(None);
+    }
+
+    let frames: Vec<Frame> = frames
+        .into_iter()
+        .map(|frame| {
+            let delay = Duration::from(frame.delay());
+            Frame {
+                delay: if delay < MIN_FRAME_DELAY {
+                    DEFAULT_FRAME_DELAY
+                } else {
+                    delay
+                },
+                image: fit_within(DynamicImage::ImageRgba8(frame.into_buffer()), target_size),
+            }
+        })
+        .collect();
+    Ok(Some(frames.into()))
+}
+
 /// Check if a source is an SVG file
 fn is_svg(source: &str) -> bool {
     source.to_lowercase().ends_with(".svg")
@@ -164,8 +320,7 @@ fn load_svg(source: &str, color: Option<&str>, target_size: u32) -> Result<Dynam
 
     // Parse and render SVG
     let opts = resvg::usvg::Options::default();
-    let tree = resvg::usvg::Tree::from_str(&svg_text, &opts)
-        .context("Failed to parse SVG")?;
+    let tree = resvg::usvg::Tree::from_str(&svg_text, &opts).context("Failed to parse SVG")?;
 
     // Create a pixmap for rendering
     let size = resvg::tiny_skia::IntSize::from_wh(target_size, target_size)
@@ -181,8 +336,8 @@ fn load_svg(source: &str, color: Option<&str>, target_size: u32) -> Result<Dynam
     let x_offset = (target_size as f32 - svg_size.width() * scale) / 2.0;
     let y_offset = (target_size as f32 - svg_size.height() * scale) / 2.0;
 
-    let transform = resvg::tiny_skia::Transform::from_scale(scale, scale)
-        .post_translate(x_offset, y_offset);
+    let transform =
+        resvg::tiny_skia::Transform::from_scale(scale, scale).post_translate(x_offset, y_offset);
 
     resvg::render(&tree, transform, &mut pixmap.as_mut());
 
@@ -218,7 +373,11 @@ fn load_svg(source: &str, color: Option<&str>, target_size: u32) -> Result<Dynam
 /// Load an image with optional SVG colorization (with caching).
 /// For SVG files, the color parameter is used to colorize the icon.
 /// All images are resized to fit within target_size while preserving aspect ratio.
-pub fn load_cached_with_color(source: &str, color: Option<&str>, target_size: u32) -> Result<DynamicImage> {
+pub fn load_cached_with_color(
+    source: &str,
+    color: Option<&str>,
+    target_size: u32,
+) -> Result<DynamicImage> {
     // Create cache key including color and size
     let cache_key = match color {
         Some(c) => format!("{}@{}@{}", source, c, target_size),
@@ -234,13 +393,8 @@ pub fn load_cached_with_color(source: &str, color: Option<&str>, target_size: u3
     let img = if is_svg(source) {
         load_svg(source, color, target_size)?
     } else {
-        let loaded = load_image_uncached(source)?;
         // Resize non-SVG images to fit within target_size (preserving aspect ratio)
-        if loaded.width() > target_size || loaded.height() > target_size {
-            loaded.resize(target_size, target_size, image::imageops::FilterType::Lanczos3)
-        } else {
-            loaded
-        }
+        fit_within(load_image_uncached(source)?, target_size)
     };
 
     // Cache it
@@ -249,6 +403,26 @@ pub fn load_cached_with_color(source: &str, color: Option<&str>, target_size: u3
     Ok(img)
 }
 
+/// Load every frame of an animated GIF or APNG, fitted to target_size
+/// (with caching). Returns None if the source isn't animated, so callers fall
+/// back to `load_cached_with_color`. A source that fails returns its error
+/// once and is then cached as not animated until it changes, leaving the
+/// still loader to report it.
+pub fn load_animation_cached(source: &str, target_size: u32) -> Result<Option<Frames>> {
+    if !may_be_animated(source) {
+        return Ok(None);
+    }
+    let cache_key = format!("{}@{}", source, target_size);
+    if let Some(frames) = with_cache(|c| c.get_animation(&cache_key, source)) {
+        return Ok(frames);
+    }
+
+    let result = load_animation_uncached(source, target_size);
+    let frames = result.as_ref().ok().cloned().flatten();
+    with_cache(|c| c.put_animation(&cache_key, source, frames));
+    result
+}
+
 /// Clear the image cache (e.g., when bindings change significantly)
 #[allow(dead_code)]
 pub fn clear_cache() {
@@ -333,6 +507,77 @@ mod tests {
         assert!(MAX_CACHE_ENTRIES <= 500);
     }
 
+    /// Write a two-frame GIF to a temporary file
+    fn write_test_gif(name: &str) -> std::path::PathBuf {
+        use image::codecs::gif::GifEncoder;
+        use image::{Delay, Rgba};
+
+        let path = std::env::temp_dir().join(format!("deck-{}-{}.gif", name, std::process::id()));
+        let mut encoder = GifEncoder::new(std::fs::File::create(&path).unwrap());
+        let frames =
+            [(Rgba([255, 0, 0, 255]), 200), (Rgba([0, 0, 255, 255]), 0)].map(|(color, ms)| {
+                image::Frame::from_parts(
+                    RgbaImage::from_pixel(100, 50, color),
+                    0,
+                    0,
+                    Delay::from_numer_denom_ms(ms, 1),
+                )
+            });
+        encoder.encode_frames(frames).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_animation_frames_and_delays() {
+        let path = write_test_gif("animation");
+        let frames = load_animation_uncached(path.to_str().unwrap(), 72)
+            .unwrap()
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].delay, Duration::from_millis(200));
+        // A zero delay gets the browser default
+        assert_eq!(frames[1].delay, DEFAULT_FRAME_DELAY);
+        // Fitted to the key, aspect ratio kept
+        assert_eq!(
+            (frames[0].image.width(), frames[0].image.height()),
+            (72, 36)
+        );
+        assert_eq!(frames[1].image.to_rgba8().get_pixel(36, 18)[2], 255);
+    }
+
+    #[test]
+    fn test_still_images_are_not_animations() {
+        let path = std::env::temp_dir().join(format!("deck-still-{}.png", std::process::id()));
+        RgbaImage::new(4, 4).save(&path).unwrap();
+        let source = path.to_str().unwrap();
+        assert!(load_animation_cached(source, 72).unwrap().is_none());
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(!may_be_animated("/icons/mic.svg"));
+        assert!(may_be_animated("https://example.com/live.GIF?v=2"));
+    }
+
+    #[test]
+    fn test_broken_animations_fail_once() {
+        let path = std::env::temp_dir().join(format!("deck-broken-{}.gif", std::process::id()));
+        std::fs::write(&path, b"GIF89a").unwrap();
+        let source = path.to_str().unwrap();
+        assert!(load_animation_cached(source, 72).is_err());
+        assert!(load_animation_cached(source, 72).unwrap().is_none());
+        std::fs::remove_file(&path).unwrap();
+
+        // A missing file is retried once it shows up
+        let path = write_test_gif("appears");
+        let source = path.to_str().unwrap().to_string();
+        std::fs::remove_file(&path).unwrap();
+        assert!(load_animation_cached(&source, 72).is_err());
+        let path = write_test_gif("appears");
+        assert!(load_animation_cached(&source, 72).unwrap().is_some());
+        std::fs::remove_file(&path).unwrap();
+    }
+
     #[test]
     fn test_lru_eviction() {
         let mut cache = ImageCache::new();
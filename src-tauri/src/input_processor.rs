@@ -1,4 +1,10 @@
-use crate::events::{ButtonEvent, EncoderEvent, TouchSwipeEvent};
+use crate::binding::{normalize_chord, Acceleration, Binding, Gesture, InputRef};
+use crate::events::{
+    ButtonEvent, ChordEvent, EncoderEvent, GestureEvent, GestureKind, TouchPressEvent,
+    TouchSwipeEvent,
+};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
 
 /// Direction of a swipe gesture on the touch strip.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -31,10 +37,115 @@ pub fn detect_swipe_direction(start: (u16, u16), end: (u16, u16)) -> Option<Swip
     }
 }
 
+/// Map a touch x-coordinate to the encoder section beneath it.
+/// Touches past the last section are clamped to it.
+pub fn touch_section(x: u16, section_width: u32, section_count: usize) -> usize {
+    if section_width == 0 || section_count == 0 {
+        return 0;
+    }
+    (x as u32 / section_width).min(section_count as u32 - 1) as usize
+}
+
+/// Window over which encoder turn speed is measured.
+pub const ENCODER_RATE_WINDOW: Duration = Duration::from_millis(100);
+
+/// Maximum gap between two taps for them to count as a double-tap.
+pub const DOUBLE_TAP_WINDOW: Duration = Duration::from_millis(300);
+
+/// Gesture recognition settings for a single button.
+///
+/// Only buttons with at least one gesture binding on the current page are tracked.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GestureConfig {
+    /// Delay taps until the double-tap window has passed
+    pub double_tap: bool,
+    /// Hold time before a long-press fires
+    pub long_press: Option<Duration>,
+    /// Auto-repeat (initial delay, interval) while held
+    pub repeat: Option<(Duration, Duration)>,
+}
+
+/// Build per-button gesture settings from the bindings on a page.
+pub fn gesture_configs(bindings: &[Binding]) -> HashMap<usize, GestureConfig> {
+    let mut configs: HashMap<usize, GestureConfig> = HashMap::new();
+
+    for binding in bindings {
+        let (InputRef::Button { index }, Some(gesture)) = (&binding.input, binding.gesture) else {
+            continue;
+        };
+        let config = configs.entry(*index).or_default();
+
+        match gesture {
+            Gesture::Tap => {}
+            Gesture::DoubleTap => config.double_tap = true,
+            Gesture::LongPress { threshold_ms } => {
+                let threshold = Duration::from_millis(threshold_ms);
+                // Use the shortest threshold if several long-press bindings exist
+                config.long_press = Some(config.long_press.map_or(threshold, |t| t.min(threshold)));
+            }
+            Gesture::HoldRepeat {
+                delay_ms,
+                interval_ms,
+            } => {
+                // Guard against a zero interval flooding the event loop
+                let interval = Duration::from_millis(interval_ms.max(10));
+                config.repeat = Some((Duration::from_millis(delay_ms), interval));
+            }
+        }
+    }
+
+    configs
+}
+
+/// How long a chord member's press is held back waiting for the rest of the chord.
+pub const CHORD_WINDOW: Duration = Duration::from_millis(200);
+
+/// Collect the chords bound on a page (normalized, at least two distinct buttons).
+pub fn chord_sets(bindings: &[Binding]) -> Vec<Vec<usize>> {
+    let mut chords: Vec<Vec<usize>> = Vec::new();
+
+    for binding in bindings {
+        if let InputRef::Chord { indices } = &binding.input {
+            let chord = normalize_chord(indices);
+            if chord.len() >= 2 && !chords.contains(&chord) {
+                chords.push(chord);
+            }
+        }
+    }
+
+    chords
+}
+
+/// Gesture tracking state for a single button.
+#[derive(Debug, Default)]
+struct KeyGestureState {
+    /// When the current press started
+    pressed_at: Option<Instant>,
+    /// Long-press already fired for the current press
+    long_press_fired: bool,
+    /// Next auto-repeat deadline while held
+    next_repeat_at: Option<Instant>,
+    /// Release time of a tap waiting for a possible second tap
+    pending_tap_at: Option<Instant>,
+    /// Current press completed a double-tap (release emits nothing)
+    double_tap_fired: bool,
+}
+
 #[derive(Default)]
 pub struct InputProcessor {
     last_buttons: Vec<bool>,
     last_encoders: Vec<bool>,
+    gesture_configs: HashMap<usize, GestureConfig>,
+    gesture_states: HashMap<usize, KeyGestureState>,
+    chords: Vec<Vec<usize>>,
+    /// Chord member presses not yet delivered (index -> press time)
+    held_presses: BTreeMap<usize, Instant>,
+    /// Buttons that completed a chord; their release is swallowed
+    chord_consumed: HashSet<usize>,
+    /// Recent (time, ticks) per encoder for turn speed, same direction only
+    encoder_history: HashMap<usize, VecDeque<(Instant, i8)>>,
+    /// The deck's keys are pedals (no gestures or chords)
+    pedal: bool,
 }
 
 /// Normalized input events from the Stream Deck.
@@ -44,17 +155,161 @@ pub struct InputProcessor {
 /// - `Encoder`: Rotary encoder twist (index + delta)
 /// - `EncoderPress`: Encoder push button (index + pressed state)
 /// - `Swipe`: Touch screen swipe gesture (start + end coordinates)
+/// - `Gesture`: Tap, double-tap, long-press or auto-repeat recognised on a button
+/// - `Chord`: Several buttons pressed together
+/// - `TouchTap` / `TouchLongPress`: Tap or long-press on an LCD strip section
+/// - `Pedal`: Pedal press/release on a Stream Deck Pedal (index 0-2, left to right)
 #[derive(Debug, Clone)]
 #[non_exhaustive]
 pub enum LogicalEvent {
     Button(ButtonEvent),
+    Gesture(GestureEvent),
+    Chord(ChordEvent),
     Encoder(EncoderEvent),
     EncoderPress(ButtonEvent),
     Swipe(TouchSwipeEvent),
+    TouchTap(TouchPressEvent),
+    TouchLongPress(TouchPressEvent),
+    Pedal(ButtonEvent),
+}
+
+/// A key, encoder or pedal that goes down and comes back up, identified the
+/// same way on its press and its release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HeldInput {
+    Button(usize),
+    EncoderPress(usize),
+    Pedal(usize),
+}
+
+impl GestureEvent {
+    /// The press event handed to plugins when a gesture binding fires.
+    pub fn as_button_press(&self) -> LogicalEvent {
+        LogicalEvent::Button(ButtonEvent {
+            index: self.index,
+            pressed: true,
+        })
+    }
+}
+
+impl ChordEvent {
+    /// The press event handed to plugins when a chord binding fires.
+    pub fn as_button_press(&self) -> LogicalEvent {
+        LogicalEvent::Button(ButtonEvent {
+            index: self.indices.first().copied().unwrap_or_default(),
+            pressed: true,
+        })
+    }
+}
+
+impl EncoderEvent {
+    /// The rotation event handed to plugins for a binding's acceleration curve.
+    pub fn accelerated(&self, acceleration: Option<&Acceleration>) -> LogicalEvent {
+        let delta = match acceleration {
+            Some(curve) => curve.apply(self.delta, self.ticks_per_100ms),
+            None => self.delta,
+        };
+        LogicalEvent::Encoder(EncoderEvent {
+            delta,
+            ..self.clone()
+        })
+    }
+}
+
+impl TouchPressEvent {
+    /// The press event handed to plugins: a touch acts on the encoder below it.
+    pub fn as_encoder_press(&self) -> LogicalEvent {
+        LogicalEvent::EncoderPress(ButtonEvent {
+            index: self.section,
+            pressed: true,
+        })
+    }
+}
+
+impl LogicalEvent {
+    /// For a button or encoder release, the matching press. Momentary bindings
+    /// hand this to plugins along with the capability that undoes theirs.
+    pub fn release_as_press(&self) -> Option<LogicalEvent> {
+        let press = |e: &ButtonEvent| ButtonEvent {
+            index: e.index,
+            pressed: true,
+        };
+        match self {
+            LogicalEvent::Button(e) if !e.pressed => Some(LogicalEvent::Button(press(e))),
+            LogicalEvent::EncoderPress(e) if !e.pressed => {
+                Some(LogicalEvent::EncoderPress(press(e)))
+            }
+            LogicalEvent::Pedal(e) if !e.pressed => Some(LogicalEvent::Pedal(press(e))),
+            _ => None,
+        }
+    }
+
+    /// The key, encoder or pedal this press or release is on.
+    pub fn held_input(&self) -> Option<HeldInput> {
+        match self {
+            LogicalEvent::Button(e) => Some(HeldInput::Button(e.index)),
+            LogicalEvent::EncoderPress(e) => Some(HeldInput::EncoderPress(e.index)),
+            LogicalEvent::Pedal(e) => Some(HeldInput::Pedal(e.index)),
+            _ => None,
+        }
+    }
+
+    /// Whether this is a key, encoder or pedal going down, which a release follows.
+    pub fn is_press(&self) -> bool {
+        matches!(
+            self,
+            LogicalEvent::Button(e) | LogicalEvent::EncoderPress(e) | LogicalEvent::Pedal(e) if e.pressed
+        )
+    }
+
+    /// The event handed to plugins for a pedal: a press or release of its key.
+    pub fn pedal_as_button(&self) -> LogicalEvent {
+        match self {
+            LogicalEvent::Pedal(e) => LogicalEvent::Button(e.clone()),
+            other => other.clone(),
+        }
+    }
 }
 
 impl InputProcessor {
+    /// Processor for a Stream Deck Pedal, whose keys report as `Pedal` events.
+    pub fn pedal() -> Self {
+        Self {
+            pedal: true,
+            ..Default::default()
+        }
+    }
+
+    /// Replace the gesture settings (called when the page's bindings change).
+    /// Tracking state is dropped for buttons that no longer have gesture bindings.
+    pub fn set_gesture_configs(&mut self, configs: HashMap<usize, GestureConfig>) {
+        if configs == self.gesture_configs {
+            return;
+        }
+        self.gesture_states
+            .retain(|index, _| configs.contains_key(index));
+        self.gesture_configs = configs;
+    }
+
+    /// Replace the chords bound on the current page.
+    pub fn set_chords(&mut self, chords: Vec<Vec<usize>>) {
+        self.chords = chords;
+    }
+
+    /// Drop presses held back for a chord, e.g. when the page changes under
+    /// them; their releases then come through without a press.
+    pub fn clear_held_presses(&mut self) {
+        self.held_presses.clear();
+    }
+
     pub fn process_buttons(&mut self, states: &[bool]) -> Vec<LogicalEvent> {
+        self.process_buttons_at(states, Instant::now())
+    }
+
+    pub fn process_buttons_at(&mut self, states: &[bool], now: Instant) -> Vec<LogicalEvent> {
+        if self.pedal {
+            return self.process_pedals(states);
+        }
         let mut events = Vec::new();
 
         // First frame: emit DOWN for any pressed buttons
@@ -65,18 +320,46 @@ impl InputProcessor {
                         index: i,
                         pressed: true,
                     }));
+                    self.gesture_press(i, now, &mut events);
                 }
             }
             self.last_buttons = states.to_vec();
             return events;
         }
 
-        for (i, (&prev, &curr)) in self.last_buttons.iter().zip(states).enumerate() {
-            if prev != curr {
+        let last_buttons = std::mem::take(&mut self.last_buttons);
+        for (i, (&prev, &curr)) in last_buttons.iter().zip(states).enumerate() {
+            if prev == curr {
+                continue;
+            }
+
+            if curr {
+                if self.is_chord_member(i) {
+                    // Hold the press back until we know whether a chord forms
+                    self.held_presses.insert(i, now);
+                    self.complete_chord(false, &mut events);
+                } else {
+                    self.deliver_press(i, now, &mut events);
+                }
+            } else {
+                // Releasing a held-back button decides a chord that was
+                // waiting for a larger one
+                if self.held_presses.contains_key(&i) {
+                    self.complete_chord(true, &mut events);
+                }
+                if self.chord_consumed.remove(&i) {
+                    // Part of a chord that already fired
+                    continue;
+                }
+                if let Some(pressed_at) = self.held_presses.remove(&i) {
+                    // Released before a chord formed: deliver it as a normal press
+                    self.deliver_press(i, pressed_at, &mut events);
+                }
                 events.push(LogicalEvent::Button(ButtonEvent {
                     index: i,
-                    pressed: curr,
+                    pressed: false,
                 }));
+                self.gesture_release(i, now, &mut events);
             }
         }
 
@@ -84,13 +367,216 @@ impl InputProcessor {
         events
     }
 
-    pub fn process_encoders(&self, deltas: &[i8]) -> Vec<LogicalEvent> {
-        deltas
+    /// Pedals only go down and up, as `Pedal` events.
+    fn process_pedals(&mut self, states: &[bool]) -> Vec<LogicalEvent> {
+        let events = states
             .iter()
             .enumerate()
-            .filter(|(_, &d)| d != 0)
-            .map(|(i, &d)| LogicalEvent::Encoder(EncoderEvent { index: i, delta: d }))
-            .collect()
+            .filter(|&(i, &pressed)| self.last_buttons.get(i).copied().unwrap_or(false) != pressed)
+            .map(|(index, &pressed)| LogicalEvent::Pedal(ButtonEvent { index, pressed }))
+            .collect();
+        self.last_buttons = states.to_vec();
+        events
+    }
+
+    fn is_chord_member(&self, index: usize) -> bool {
+        self.chords.iter().any(|chord| chord.contains(&index))
+    }
+
+    /// Fire the largest chord whose buttons are all held back. While a larger
+    /// chord containing it could still form, it waits for the rest of that
+    /// chord unless `decide` is set (a release or the chord window ran out).
+    fn complete_chord(&mut self, decide: bool, events: &mut Vec<LogicalEvent>) {
+        let Some(chord) = self
+            .chords
+            .iter()
+            .filter(|chord| chord.iter().all(|i| self.held_presses.contains_key(i)))
+            .max_by_key(|chord| chord.len())
+            .cloned()
+        else {
+            return;
+        };
+        let may_grow = self.chords.iter().any(|other| {
+            other.len() > chord.len() && chord.iter().all(|index| other.contains(index))
+        });
+        if may_grow && !decide {
+            return;
+        }
+
+        for index in &chord {
+            self.held_presses.remove(index);
+            self.chord_consumed.insert(*index);
+        }
+        events.push(LogicalEvent::Chord(ChordEvent { indices: chord }));
+    }
+
+    fn deliver_press(&mut self, index: usize, pressed_at: Instant, events: &mut Vec<LogicalEvent>) {
+        events.push(LogicalEvent::Button(ButtonEvent {
+            index,
+            pressed: true,
+        }));
+        self.gesture_press(index, pressed_at, events);
+    }
+
+    /// Emit time-based gestures (long-press, auto-repeat, expired double-tap and chord windows).
+    ///
+    /// Must be called regularly, including when no input arrived.
+    pub fn tick(&mut self, now: Instant) -> Vec<LogicalEvent> {
+        let mut events = Vec::new();
+
+        // Chord window expired: fire a chord that was waiting for a larger
+        // one, and deliver the other held-back presses individually
+        if self
+            .held_presses
+            .values()
+            .any(|&pressed_at| now.duration_since(pressed_at) >= CHORD_WINDOW)
+        {
+            self.complete_chord(true, &mut events);
+        }
+        let expired: Vec<(usize, Instant)> = self
+            .held_presses
+            .iter()
+            .filter(|(_, &pressed_at)| now.duration_since(pressed_at) >= CHORD_WINDOW)
+            .map(|(&index, &pressed_at)| (index, pressed_at))
+            .collect();
+        for (index, pressed_at) in expired {
+            self.held_presses.remove(&index);
+            self.deliver_press(index, pressed_at, &mut events);
+        }
+
+        let mut indices: Vec<usize> = self.gesture_states.keys().copied().collect();
+        indices.sort_unstable();
+
+        for index in indices {
+            let Some(config) = self.gesture_configs.get(&index) else {
+                continue;
+            };
+            let Some(state) = self.gesture_states.get_mut(&index) else {
+                continue;
+            };
+
+            if let Some(pressed_at) = state.pressed_at {
+                if let Some(threshold) = config.long_press {
+                    if !state.long_press_fired && now.duration_since(pressed_at) >= threshold {
+                        state.long_press_fired = true;
+                        events.push(gesture_event(index, GestureKind::LongPress));
+                    }
+                }
+
+                if let (Some(next), Some((_, interval))) = (state.next_repeat_at, config.repeat) {
+                    if now >= next {
+                        state.next_repeat_at = Some(now + interval);
+                        events.push(gesture_event(index, GestureKind::HoldRepeat));
+                    }
+                }
+            }
+
+            if let Some(released_at) = state.pending_tap_at {
+                if now.duration_since(released_at) > DOUBLE_TAP_WINDOW {
+                    state.pending_tap_at = None;
+                    events.push(gesture_event(index, GestureKind::Tap));
+                }
+            }
+        }
+
+        events
+    }
+
+    fn gesture_press(&mut self, index: usize, now: Instant, events: &mut Vec<LogicalEvent>) {
+        let Some(config) = self.gesture_configs.get(&index) else {
+            return;
+        };
+        let state = self.gesture_states.entry(index).or_default();
+
+        // Second press inside the window completes a double-tap
+        if let Some(released_at) = state.pending_tap_at.take() {
+            if now.duration_since(released_at) <= DOUBLE_TAP_WINDOW {
+                state.double_tap_fired = true;
+                events.push(gesture_event(index, GestureKind::DoubleTap));
+                return;
+            }
+            // Window expired before tick() noticed: flush the first tap
+            events.push(gesture_event(index, GestureKind::Tap));
+        }
+
+        state.pressed_at = Some(now);
+        state.long_press_fired = false;
+        state.double_tap_fired = false;
+        state.next_repeat_at = None;
+
+        if let Some((delay, _)) = config.repeat {
+            state.next_repeat_at = Some(now + delay);
+            events.push(gesture_event(index, GestureKind::HoldRepeat));
+        }
+    }
+
+    fn gesture_release(&mut self, index: usize, now: Instant, events: &mut Vec<LogicalEvent>) {
+        let Some(config) = self.gesture_configs.get(&index) else {
+            return;
+        };
+        let Some(state) = self.gesture_states.get_mut(&index) else {
+            return;
+        };
+
+        let was_tap =
+            state.pressed_at.is_some() && !state.long_press_fired && !state.double_tap_fired;
+        state.pressed_at = None;
+        state.next_repeat_at = None;
+        state.long_press_fired = false;
+        state.double_tap_fired = false;
+
+        if !was_tap {
+            return;
+        }
+
+        if config.double_tap {
+            // Hold the tap back until we know no second tap follows
+            state.pending_tap_at = Some(now);
+        } else {
+            events.push(gesture_event(index, GestureKind::Tap));
+        }
+    }
+
+    pub fn process_encoders(&mut self, deltas: &[i8]) -> Vec<LogicalEvent> {
+        self.process_encoders_at(deltas, Instant::now())
+    }
+
+    pub fn process_encoders_at(&mut self, deltas: &[i8], now: Instant) -> Vec<LogicalEvent> {
+        let mut events = Vec::new();
+
+        for (i, &d) in deltas.iter().enumerate() {
+            if d == 0 {
+                continue;
+            }
+
+            let history = self.encoder_history.entry(i).or_default();
+            // Turning back resets the speed so reversals start fine-grained again
+            if history
+                .back()
+                .is_some_and(|&(_, prev)| prev.signum() != d.signum())
+            {
+                history.clear();
+            }
+            while history
+                .front()
+                .is_some_and(|&(at, _)| now.duration_since(at) >= ENCODER_RATE_WINDOW)
+            {
+                history.pop_front();
+            }
+            history.push_back((now, d));
+
+            let ticks_per_100ms = history
+                .iter()
+                .map(|&(_, delta)| delta.unsigned_abs() as u32)
+                .sum();
+            events.push(LogicalEvent::Encoder(EncoderEvent {
+                index: i,
+                delta: d,
+                ticks_per_100ms,
+            }));
+        }
+
+        events
     }
 
     pub fn process_encoder_presses(&mut self, states: &[bool]) -> Vec<LogicalEvent> {
@@ -127,12 +613,65 @@ impl InputProcessor {
     pub fn process_swipe(&self, start: (u16, u16), end: (u16, u16)) -> LogicalEvent {
         LogicalEvent::Swipe(TouchSwipeEvent { start, end })
     }
+
+    pub fn process_touch_tap(&self, position: (u16, u16), section: usize) -> LogicalEvent {
+        LogicalEvent::TouchTap(TouchPressEvent { section, position })
+    }
+
+    pub fn process_touch_long_press(&self, position: (u16, u16), section: usize) -> LogicalEvent {
+        LogicalEvent::TouchLongPress(TouchPressEvent { section, position })
+    }
+}
+
+fn gesture_event(index: usize, gesture: GestureKind) -> LogicalEvent {
+    LogicalEvent::Gesture(GestureEvent { index, gesture })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn press_and_release_share_their_held_input() {
+        let event = |pressed| ButtonEvent { index: 3, pressed };
+        for (press, release) in [
+            (
+                LogicalEvent::Button(event(true)),
+                LogicalEvent::Button(event(false)),
+            ),
+            (
+                LogicalEvent::EncoderPress(event(true)),
+                LogicalEvent::EncoderPress(event(false)),
+            ),
+            (
+                LogicalEvent::Pedal(event(true)),
+                LogicalEvent::Pedal(event(false)),
+            ),
+        ] {
+            assert!(press.held_input().is_some());
+            assert_eq!(press.held_input(), release.held_input());
+        }
+        assert_ne!(
+            LogicalEvent::Button(event(true)).held_input(),
+            LogicalEvent::Pedal(event(true)).held_input()
+        );
+    }
+
+    #[test]
+    fn pedals_emit_pedal_events() {
+        let mut p = InputProcessor::pedal();
+        p.set_chords(vec![vec![0, 1]]);
+
+        let events = p.process_buttons(&[true, true, false]);
+        assert!(
+            matches!(&events[..], [LogicalEvent::Pedal(a), LogicalEvent::Pedal(b)]
+            if a.index == 0 && b.index == 1 && a.pressed && b.pressed)
+        );
+
+        let events = p.process_buttons(&[false, true, false]);
+        assert!(matches!(&events[..], [LogicalEvent::Pedal(e)] if e.index == 0 && !e.pressed));
+    }
+
     #[test]
     fn initial_button_press_emits_down() {
         let mut p = InputProcessor::default();
@@ -176,7 +715,7 @@ mod tests {
 
     #[test]
     fn encoder_zero_deltas_ignored() {
-        let p = InputProcessor::default();
+        let mut p = InputProcessor::default();
 
         let events = p.process_encoders(&[0, 0, 0]);
         assert!(events.is_empty());
@@ -184,12 +723,52 @@ mod tests {
 
     #[test]
     fn encoder_deltas_preserved() {
-        let p = InputProcessor::default();
+        let mut p = InputProcessor::default();
 
         let events = p.process_encoders(&[1, -1, 0]);
         assert_eq!(events.len(), 2);
     }
 
+    fn encoder_rate(events: &[LogicalEvent]) -> u32 {
+        match &events[0] {
+            LogicalEvent::Encoder(e) => e.ticks_per_100ms,
+            _ => panic!("expected encoder event"),
+        }
+    }
+
+    #[test]
+    fn encoder_rate_counts_ticks_in_window() {
+        let mut p = InputProcessor::default();
+        let t0 = Instant::now();
+
+        assert_eq!(encoder_rate(&p.process_encoders_at(&[1], t0)), 1);
+        assert_eq!(
+            encoder_rate(&p.process_encoders_at(&[2], t0 + Duration::from_millis(30))),
+            3
+        );
+        assert_eq!(
+            encoder_rate(&p.process_encoders_at(&[1], t0 + Duration::from_millis(60))),
+            4
+        );
+        // Older ticks fall out of the window
+        assert_eq!(
+            encoder_rate(&p.process_encoders_at(&[1], t0 + Duration::from_millis(200))),
+            1
+        );
+    }
+
+    #[test]
+    fn encoder_rate_resets_on_reversal() {
+        let mut p = InputProcessor::default();
+        let t0 = Instant::now();
+
+        p.process_encoders_at(&[3], t0);
+        assert_eq!(
+            encoder_rate(&p.process_encoders_at(&[-1], t0 + Duration::from_millis(20))),
+            1
+        );
+    }
+
     #[test]
     fn swipe_event_is_forwarded() {
         let p = InputProcessor::default();
@@ -204,6 +783,34 @@ mod tests {
             _ => panic!("expected swipe event"),
         }
     }
+    #[test]
+    fn touch_x_maps_to_encoder_section() {
+        // Stream Deck Plus: 800px strip, 4 sections of 200px
+        assert_eq!(touch_section(0, 200, 4), 0);
+        assert_eq!(touch_section(199, 200, 4), 0);
+        assert_eq!(touch_section(200, 200, 4), 1);
+        assert_eq!(touch_section(799, 200, 4), 3);
+        // Out of range clamps to the last section
+        assert_eq!(touch_section(950, 200, 4), 3);
+    }
+
+    #[test]
+    fn touch_tap_event_carries_section() {
+        let p = InputProcessor::default();
+
+        match p.process_touch_tap((450, 30), 2) {
+            LogicalEvent::TouchTap(e) => {
+                assert_eq!(e.section, 2);
+                assert_eq!(e.position, (450, 30));
+            }
+            _ => panic!("expected touch tap event"),
+        }
+        assert!(matches!(
+            p.process_touch_long_press((10, 10), 0),
+            LogicalEvent::TouchLongPress(_)
+        ));
+    }
+
     #[test]
     fn initial_encoder_press_emits_down() {
         let mut p = InputProcessor::default();
@@ -243,4 +850,311 @@ mod tests {
         let direction = detect_swipe_direction((100, 50), (200, 200));
         assert_eq!(direction, None);
     }
+
+    // ─────────────────────────────────────────────────────────────────
+    // Gesture recognition tests
+    // ─────────────────────────────────────────────────────────────────
+
+    fn gestures(events: &[LogicalEvent]) -> Vec<GestureKind> {
+        events
+            .iter()
+            .filter_map(|e| match e {
+                LogicalEvent::Gesture(g) => Some(g.gesture),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn processor_with(config: GestureConfig) -> InputProcessor {
+        let mut p = InputProcessor::default();
+        p.set_gesture_configs(HashMap::from([(0, config)]));
+        p
+    }
+
+    #[test]
+    fn no_gestures_for_unconfigured_buttons() {
+        let mut p = InputProcessor::default();
+        let t0 = Instant::now();
+
+        p.process_buttons_at(&[false], t0);
+        let events = p.process_buttons_at(&[true], t0);
+        assert!(gestures(&events).is_empty());
+        let events = p.process_buttons_at(&[false], t0 + Duration::from_millis(50));
+        assert!(gestures(&events).is_empty());
+    }
+
+    #[test]
+    fn short_press_emits_tap_on_release() {
+        let mut p = processor_with(GestureConfig::default());
+        let t0 = Instant::now();
+
+        p.process_buttons_at(&[false], t0);
+        p.process_buttons_at(&[true], t0);
+        let events = p.process_buttons_at(&[false], t0 + Duration::from_millis(80));
+
+        assert_eq!(gestures(&events), vec![GestureKind::Tap]);
+    }
+
+    #[test]
+    fn long_press_fires_while_held_and_suppresses_tap() {
+        let mut p = processor_with(GestureConfig {
+            long_press: Some(Duration::from_millis(500)),
+            ..Default::default()
+        });
+        let t0 = Instant::now();
+
+        p.process_buttons_at(&[false], t0);
+        p.process_buttons_at(&[true], t0);
+        assert!(gestures(&p.tick(t0 + Duration::from_millis(400))).is_empty());
+        assert_eq!(
+            gestures(&p.tick(t0 + Duration::from_millis(500))),
+            vec![GestureKind::LongPress]
+        );
+        // Only fires once per press
+        assert!(gestures(&p.tick(t0 + Duration::from_millis(900))).is_empty());
+
+        let events = p.process_buttons_at(&[false], t0 + Duration::from_millis(1000));
+        assert!(gestures(&events).is_empty());
+    }
+
+    #[test]
+    fn tap_is_delayed_until_double_tap_window_expires() {
+        let mut p = processor_with(GestureConfig {
+            double_tap: true,
+            ..Default::default()
+        });
+        let t0 = Instant::now();
+
+        p.process_buttons_at(&[false], t0);
+        p.process_buttons_at(&[true], t0);
+        let events = p.process_buttons_at(&[false], t0 + Duration::from_millis(50));
+        assert!(gestures(&events).is_empty());
+
+        assert!(gestures(&p.tick(t0 + Duration::from_millis(200))).is_empty());
+        assert_eq!(
+            gestures(&p.tick(
+                t0 + Duration::from_millis(50) + DOUBLE_TAP_WINDOW + Duration::from_millis(1)
+            )),
+            vec![GestureKind::Tap]
+        );
+    }
+
+    #[test]
+    fn second_press_inside_window_emits_double_tap() {
+        let mut p = processor_with(GestureConfig {
+            double_tap: true,
+            ..Default::default()
+        });
+        let t0 = Instant::now();
+
+        p.process_buttons_at(&[false], t0);
+        p.process_buttons_at(&[true], t0);
+        p.process_buttons_at(&[false], t0 + Duration::from_millis(50));
+        let events = p.process_buttons_at(&[true], t0 + Duration::from_millis(150));
+        assert_eq!(gestures(&events), vec![GestureKind::DoubleTap]);
+
+        // Releasing the second press and waiting emits no extra tap
+        let events = p.process_buttons_at(&[false], t0 + Duration::from_millis(200));
+        assert!(gestures(&events).is_empty());
+        assert!(gestures(&p.tick(t0 + Duration::from_secs(1))).is_empty());
+    }
+
+    #[test]
+    fn hold_repeat_fires_on_press_and_while_held() {
+        let mut p = processor_with(GestureConfig {
+            repeat: Some((Duration::from_millis(400), Duration::from_millis(100))),
+            ..Default::default()
+        });
+        let t0 = Instant::now();
+
+        p.process_buttons_at(&[false], t0);
+        let events = p.process_buttons_at(&[true], t0);
+        assert_eq!(gestures(&events), vec![GestureKind::HoldRepeat]);
+
+        assert!(gestures(&p.tick(t0 + Duration::from_millis(300))).is_empty());
+        assert_eq!(
+            gestures(&p.tick(t0 + Duration::from_millis(400))),
+            vec![GestureKind::HoldRepeat]
+        );
+        assert!(gestures(&p.tick(t0 + Duration::from_millis(450))).is_empty());
+        assert_eq!(
+            gestures(&p.tick(t0 + Duration::from_millis(500))),
+            vec![GestureKind::HoldRepeat]
+        );
+
+        p.process_buttons_at(&[false], t0 + Duration::from_millis(550));
+        assert!(gestures(&p.tick(t0 + Duration::from_millis(700))).is_empty());
+    }
+
+    #[test]
+    fn gesture_configs_built_from_bindings() {
+        use crate::capability::Capability;
+
+        let binding = |index: usize, gesture: Option<Gesture>| Binding {
+            gesture,
+            ..Binding::new(InputRef::Button { index }, Capability::MediaPlayPause)
+        };
+
+        let configs = gesture_configs(&[
+            binding(0, None),
+            binding(1, Some(Gesture::Tap)),
+            binding(1, Some(Gesture::DoubleTap)),
+            binding(1, Some(Gesture::LongPress { threshold_ms: 800 })),
+        ]);
+
+        assert!(!configs.contains_key(&0));
+        let config = &configs[&1];
+        assert!(config.double_tap);
+        assert_eq!(config.long_press, Some(Duration::from_millis(800)));
+        assert_eq!(config.repeat, None);
+    }
+
+    // ─────────────────────────────────────────────────────────────────
+    // Chord recognition tests
+    // ─────────────────────────────────────────────────────────────────
+
+    fn buttons(events: &[LogicalEvent]) -> Vec<(usize, bool)> {
+        events
+            .iter()
+            .filter_map(|e| match e {
+                LogicalEvent::Button(b) => Some((b.index, b.pressed)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn chords(events: &[LogicalEvent]) -> Vec<Vec<usize>> {
+        events
+            .iter()
+            .filter_map(|e| match e {
+                LogicalEvent::Chord(c) => Some(c.indices.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn processor_with_chord(chord: Vec<usize>) -> InputProcessor {
+        let mut p = InputProcessor::default();
+        p.set_chords(vec![chord]);
+        p.process_buttons_at(&[false, false, false], Instant::now());
+        p
+    }
+
+    #[test]
+    fn chord_fires_and_holds_back_individual_presses() {
+        let mut p = processor_with_chord(vec![0, 1]);
+        let t0 = Instant::now();
+
+        let events = p.process_buttons_at(&[true, false, false], t0);
+        assert!(events.is_empty());
+
+        let events = p.process_buttons_at(&[true, true, false], t0 + Duration::from_millis(50));
+        assert_eq!(chords(&events), vec![vec![0, 1]]);
+        assert!(buttons(&events).is_empty());
+
+        // Releases of chord members are swallowed
+        let events = p.process_buttons_at(&[false, true, false], t0 + Duration::from_millis(300));
+        assert!(events.is_empty());
+        let events = p.process_buttons_at(&[false, false, false], t0 + Duration::from_millis(320));
+        assert!(events.is_empty());
+        assert!(p.tick(t0 + Duration::from_secs(1)).is_empty());
+    }
+
+    #[test]
+    fn chord_member_released_early_is_delivered_as_press() {
+        let mut p = processor_with_chord(vec![0, 1]);
+        let t0 = Instant::now();
+
+        p.process_buttons_at(&[true, false, false], t0);
+        let events = p.process_buttons_at(&[false, false, false], t0 + Duration::from_millis(80));
+
+        assert_eq!(buttons(&events), vec![(0, true), (0, false)]);
+        assert!(chords(&events).is_empty());
+    }
+
+    #[test]
+    fn chord_member_press_delivered_after_window() {
+        let mut p = processor_with_chord(vec![0, 1]);
+        let t0 = Instant::now();
+
+        p.process_buttons_at(&[true, false, false], t0);
+        assert!(p.tick(t0 + Duration::from_millis(100)).is_empty());
+        assert_eq!(buttons(&p.tick(t0 + CHORD_WINDOW)), vec![(0, true)]);
+
+        // Too late for the chord: the second button is held back on its own
+        let events = p.process_buttons_at(&[true, true, false], t0 + Duration::from_millis(300));
+        assert!(chords(&events).is_empty());
+    }
+
+    #[test]
+    fn larger_chord_wins_over_its_subset() {
+        let mut p = processor_with_chord(vec![0, 1]);
+        p.set_chords(vec![vec![0, 1], vec![0, 1, 2]]);
+        let t0 = Instant::now();
+
+        p.process_buttons_at(&[true, false, false], t0);
+        let events = p.process_buttons_at(&[true, true, false], t0 + Duration::from_millis(30));
+        assert!(events.is_empty());
+        let events = p.process_buttons_at(&[true, true, true], t0 + Duration::from_millis(60));
+        assert_eq!(chords(&events), vec![vec![0, 1, 2]]);
+    }
+
+    #[test]
+    fn subset_chord_fires_on_release_or_window() {
+        let mut p = processor_with_chord(vec![0, 1]);
+        p.set_chords(vec![vec![0, 1], vec![0, 1, 2]]);
+        let t0 = Instant::now();
+
+        p.process_buttons_at(&[true, true, false], t0);
+        let events = p.process_buttons_at(&[false, true, false], t0 + Duration::from_millis(50));
+        assert_eq!(chords(&events), vec![vec![0, 1]]);
+        assert!(buttons(&events).is_empty());
+        p.process_buttons_at(&[false, false, false], t0 + Duration::from_millis(60));
+
+        let t1 = t0 + Duration::from_secs(1);
+        p.process_buttons_at(&[true, true, false], t1);
+        let events = p.tick(t1 + CHORD_WINDOW);
+        assert_eq!(chords(&events), vec![vec![0, 1]]);
+        assert!(buttons(&events).is_empty());
+    }
+
+    #[test]
+    fn held_presses_are_dropped_on_page_change() {
+        let mut p = processor_with_chord(vec![0, 1]);
+        let t0 = Instant::now();
+
+        p.process_buttons_at(&[true, false, false], t0);
+        p.clear_held_presses();
+        assert!(p.tick(t0 + CHORD_WINDOW).is_empty());
+        let events = p.process_buttons_at(&[false, false, false], t0 + CHORD_WINDOW);
+        assert_eq!(buttons(&events), vec![(0, false)]);
+    }
+
+    #[test]
+    fn non_member_buttons_are_not_delayed() {
+        let mut p = processor_with_chord(vec![0, 1]);
+
+        let events = p.process_buttons_at(&[false, false, true], Instant::now());
+        assert_eq!(buttons(&events), vec![(2, true)]);
+    }
+
+    #[test]
+    fn chord_sets_normalized_from_bindings() {
+        use crate::capability::Capability;
+
+        let binding = |input: InputRef| Binding::new(input, Capability::MediaStop);
+
+        let chords = chord_sets(&[
+            binding(InputRef::Chord {
+                indices: vec![4, 0],
+            }),
+            binding(InputRef::Chord {
+                indices: vec![0, 4, 4],
+            }),
+            binding(InputRef::Chord { indices: vec![2] }),
+            binding(InputRef::Button { index: 1 }),
+        ]);
+
+        assert_eq!(chords, vec![vec![0, 4]]);
+    }
 }
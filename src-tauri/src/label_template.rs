@@ -0,0 +1,113 @@
+//! Label templates: `{placeholder}`s in a binding's label replaced with live
+//! values whenever images sync, e.g. `{volume}%` or `{time:%H:%M}`.
+//!
+//! A placeholder is `{key}` or `{key:argument}`. Plugins supply the values
+//! through `Plugin::label_value`; placeholders nobody resolves show as `?`.
+//! `{{` and `}}` are literal braces.
+
+/// Shown in place of a placeholder without a value
+const MISSING_VALUE: &str = "?";
+
+/// Whether a label has placeholders to resolve.
+pub fn has_placeholders(label: &str) -> bool {
+    label.contains('{')
+}
+
+/// Replace each placeholder with `lookup(key, argument)`.
+pub fn render(
+    template: &str,
+    mut lookup: impl FnMut(&str, Option<&str>) -> Option<String>,
+) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find(['{', '}']) {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+        } else if let Some(after) = tail.strip_prefix('}') {
+            out.push('}');
+            rest = after;
+        } else if let Some(end) = tail.find('}') {
+            let placeholder = &tail[1..end];
+            let (key, argument) = match placeholder.split_once(':') {
+                Some((key, argument)) => (key.trim(), Some(argument)),
+                None => (placeholder.trim(), None),
+            };
+            out.push_str(&lookup(key, argument).unwrap_or_else(|| MISSING_VALUE.to_string()));
+            rest = &tail[end + 1..];
+        } else {
+            // Unclosed brace, keep the rest as typed
+            out.push_str(tail);
+            rest = "";
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Format a 0.0-1.0 level as a whole percentage.
+pub fn percent(level: f32) -> String {
+    format!("{}", (level * 100.0).round() as i32)
+}
+
+/// Format the current local time with strftime, e.g. `%H:%M`.
+pub fn format_local_time(format: &str) -> Option<String> {
+    let format = std::ffi::CString::new(format).ok()?;
+    let mut buf = [0u8; 128];
+
+    // SAFETY: tm is plain old data filled in by localtime_r, and strftime
+    // writes at most buf.len() bytes into buf
+    let len = unsafe {
+        let now = libc::time(std::ptr::null_mut());
+        let mut tm: libc::tm = std::mem::zeroed();
+        if libc::localtime_r(&now, &mut tm).is_null() {
+            return None;
+        }
+        libc::strftime(buf.as_mut_ptr().cast(), buf.len(), format.as_ptr(), &tm)
+    };
+
+    Some(String::from_utf8_lossy(&buf[..len]).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(key: &str, argument: Option<&str>) -> Option<String> {
+        match (key, argument) {
+            ("volume", None) => Some("42".to_string()),
+            ("obs.volume", Some(input)) => Some(format!("{input}=80")),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn placeholders_are_replaced() {
+        assert_eq!(render("{volume}%", lookup), "42%");
+        assert_eq!(render("Mic { volume }", lookup), "Mic 42");
+        assert_eq!(render("{obs.volume:Mic/Aux}", lookup), "Mic/Aux=80");
+        assert_eq!(render("{obs.scene}", lookup), "?");
+        assert_eq!(render("Plain label", lookup), "Plain label");
+    }
+
+    #[test]
+    fn braces_can_be_escaped() {
+        assert_eq!(render("{{volume}} {volume}", lookup), "{volume} 42");
+        assert_eq!(render("a } b", lookup), "a } b");
+        assert_eq!(render("open {volume", lookup), "open {volume");
+    }
+
+    #[test]
+    fn time_is_formatted() {
+        let year = format_local_time("%Y").unwrap();
+        assert_eq!(year.len(), 4);
+        assert!(year.chars().all(|c| c.is_ascii_digit()));
+        assert_eq!(format_local_time("%H:%M").unwrap().len(), 5);
+        assert_eq!(percent(0.426), "43");
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use tauri::{Builder, Manager, RunEvent, WindowEvent};
 use tauri_plugin_single_instance::init as single_instance_init;
@@ -10,14 +11,21 @@ mod config;
 mod core;
 mod device;
 mod events;
+mod focus;
+mod gauge;
 mod hid;
 mod hotplug;
 mod image_cache;
 mod input_processor;
+mod label_template;
+mod navigation;
+mod page;
 mod plugin;
 mod plugins;
 mod state_manager;
 mod streamdeck;
+mod text_layout;
+mod virtual_keyboard;
 
 // Re-export for backwards compatibility
 #[cfg(feature = "plugin-elgato")]
@@ -58,7 +66,10 @@ fn create_plugin_registry() -> Arc<PluginRegistry> {
     // Register core plugin (always available, always enabled)
     registry.register(
         Box::new(core::CorePlugin::new()),
-        Some(&PluginConfig { enabled: true, settings: std::collections::HashMap::new() }),
+        Some(&PluginConfig {
+            enabled: true,
+            settings: std::collections::HashMap::new(),
+        }),
     );
 
     // Register optional plugins based on feature flags
@@ -71,7 +82,7 @@ fn create_plugin_registry() -> Arc<PluginRegistry> {
     #[cfg(feature = "plugin-obs")]
     registry.register(
         Box::new(plugins::obs::OBSPlugin::new()),
-        Some(&make_config("obs", false)),  // Default disabled until user enables
+        Some(&make_config("obs", false)), // Default disabled until user enables
     );
 
     Arc::new(registry)
@@ -83,22 +94,23 @@ pub fn run_with_options(start_hidden: bool) {
     let plugin_registry = create_plugin_registry();
 
     // Shared state for device info and bindings
-    let device_info = Arc::new(Mutex::new(None));
-    let bindings = Arc::new(Mutex::new(
-        config::load_bindings().unwrap_or_else(|_| config::default_bindings()),
-    ));
-    let system_state = Arc::new(Mutex::new(state_manager::SystemState::default()));
-    let current_page = Arc::new(Mutex::new(0usize));
+    let (bindings, pages) =
+        config::load_bindings().unwrap_or_else(|_| (config::default_bindings(), Vec::new()));
+    let state = AppState {
+        devices: Arc::new(Mutex::new(Vec::new())),
+        bindings: Arc::new(Mutex::new(bindings)),
+        pages: Arc::new(Mutex::new(pages)),
+        system_state: Arc::new(Mutex::new(state_manager::SystemState::default())),
+        navigation: Arc::new(Mutex::new(HashMap::new())),
+        plugin_registry,
+    };
 
     // Clone for the streamdeck thread
-    let device_info_clone = Arc::clone(&device_info);
-    let bindings_clone = Arc::clone(&bindings);
-    let system_state_clone = Arc::clone(&system_state);
-    let current_page_clone = Arc::clone(&current_page);
-    let registry_clone = Arc::clone(&plugin_registry);
+    let deck_state = state.clone();
 
     // Clone for the state poller thread
-    let system_state_poller = Arc::clone(&system_state);
+    let system_state_poller = Arc::clone(&state.system_state);
+    let bindings_poller = Arc::clone(&state.bindings);
 
     let app = Builder::default()
         .plugin(single_instance_init(|app, _args, _cwd| {
@@ -110,14 +122,9 @@ pub fn run_with_options(start_hidden: bool) {
         }))
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
-        .manage(AppState {
-            device_info: Arc::clone(&device_info),
-            bindings: Arc::clone(&bindings),
-            system_state: Arc::clone(&system_state),
-            current_page: Arc::clone(&current_page),
-            plugin_registry: Arc::clone(&plugin_registry),
-        })
+        .manage(state)
         .invoke_handler(tauri::generate_handler![
+            commands::get_devices,
             commands::get_device_info,
             commands::get_bindings,
             commands::get_capabilities,
@@ -129,6 +136,14 @@ pub fn run_with_options(start_hidden: bool) {
             commands::get_current_page,
             commands::set_current_page,
             commands::get_page_count,
+            commands::get_pages,
+            commands::create_page,
+            commands::rename_page,
+            commands::set_page_background,
+            commands::set_page_lcd_background,
+            commands::reorder_pages,
+            commands::duplicate_page,
+            commands::delete_page,
             commands::get_plugins,
             commands::set_plugin_enabled,
         ])
@@ -146,23 +161,19 @@ pub fn run_with_options(start_hidden: bool) {
             // Start hotplug monitor for device connection events
             hotplug::start_hotplug_monitor();
 
-            // Start Stream Deck thread
+            // Follow window focus for app-specific pages
+            focus::start_focus_watcher(config::load_app_rules());
+
+            // Start Stream Deck thread (one more per connected deck)
             std::thread::spawn(move || {
-                if let Err(e) = crate::streamdeck::run(
-                    handle,
-                    device_info_clone,
-                    bindings_clone,
-                    system_state_clone,
-                    current_page_clone,
-                    registry_clone,
-                ) {
+                if let Err(e) = crate::streamdeck::run(handle, deck_state) {
                     eprintln!("Stream Deck error: {:?}", e);
                 }
             });
 
             // Start state poller thread
             std::thread::spawn(move || {
-                state_manager::run_state_poller(state_handle, system_state_poller);
+                state_manager::run_state_poller(state_handle, system_state_poller, bindings_poller);
             });
 
             Ok(())
@@ -173,13 +184,20 @@ pub fn run_with_options(start_hidden: bool) {
     // Run the app with custom event handling to prevent exit on window close
     app.run(|app_handle, event| {
         match event {
-            RunEvent::WindowEvent { label, event: WindowEvent::CloseRequested { api, .. }, .. } => {
+            RunEvent::WindowEvent {
+                label,
+                event: WindowEvent::CloseRequested { api, .. },
+                ..
+            } => {
                 // Prevent the window from closing - just hide it instead
                 api.prevent_close();
                 if let Some(window) = app_handle.get_webview_window(&label) {
                     let _ = window.hide();
                 }
-                eprintln!("Window hidden - {} continues running in background", app_constants::APP_NAME);
+                eprintln!(
+                    "Window hidden - {} continues running in background",
+                    app_constants::APP_NAME
+                );
             }
             RunEvent::ExitRequested { api, .. } => {
                 // Prevent automatic exit - keep running in background
@@ -0,0 +1,467 @@
+//! Page and folder navigation state.
+//!
+//! The root page set holds every binding without a `folder`. A folder is a named
+//! child page set opened from a button; while inside one, `BACK_KEY` is reserved
+//! for returning to the page the folder was opened from.
+//!
+//! Pages are identified by id and stepped through in the order of the page table
+//! (see `page.rs`), plus one empty page at the end for adding new bindings.
+//!
+//! Every connected deck has its own `Navigation` and request queue, keyed by
+//! serial; the bindings and pages passed in are already that deck's own.
+
+use crate::binding::Binding;
+use crate::page::{self, Page};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// Key that returns to the parent page while a folder is open.
+pub const BACK_KEY: usize = 0;
+
+/// A page inside the root page set or a named folder.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PageLocation {
+    /// Folder name (None = root pages)
+    pub folder: Option<String>,
+    pub page: usize,
+}
+
+/// Where the deck currently is, plus the pages to return to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Navigation {
+    current: PageLocation,
+    /// Pages the open folders were entered from, outermost first
+    parents: Vec<PageLocation>,
+    /// Where to go back to once the focused app no longer has a page
+    app_return: Option<(PageLocation, Vec<PageLocation>)>,
+}
+
+/// Current page as reported to the frontend.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CurrentPage {
+    /// Page id (within the open folder)
+    pub page: usize,
+    pub folder: Option<String>,
+    /// Open folder names, outermost first (empty at the root)
+    pub breadcrumb: Vec<String>,
+}
+
+/// Navigation change requested by a capability handler.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NavigationRequest {
+    OpenFolder(String),
+    Back,
+    NextPage,
+    PreviousPage,
+    GoToPage(usize),
+    /// Focus moved to an app with a page (Some) or to one without (None)
+    FocusApp(Option<PageLocation>),
+}
+
+type NavigationQueues = BTreeMap<String, Vec<NavigationRequest>>;
+
+/// Requests queued by plugin threads, applied by each deck's event loop
+static NAVIGATION_REQUESTS: Mutex<NavigationQueues> = Mutex::new(BTreeMap::new());
+
+/// Queue a navigation change for the deck with `device` as serial, or for
+/// every connected deck when None.
+pub fn request_navigation(device: Option<&str>, request: NavigationRequest) {
+    let mut queues = NAVIGATION_REQUESTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    push_request(&mut queues, device, request);
+}
+
+fn push_request(queues: &mut NavigationQueues, device: Option<&str>, request: NavigationRequest) {
+    match device {
+        Some(serial) => {
+            if let Some(queue) = queues.get_mut(serial) {
+                queue.push(request);
+            }
+        }
+        None => {
+            for queue in queues.values_mut() {
+                queue.push(request.clone());
+            }
+        }
+    }
+}
+
+/// Take all pending navigation requests for a deck. The first call opens the
+/// deck's queue.
+pub fn take_navigation_requests(serial: &str) -> Vec<NavigationRequest> {
+    let mut queues = NAVIGATION_REQUESTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    take_requests(&mut queues, serial)
+}
+
+fn take_requests(queues: &mut NavigationQueues, serial: &str) -> Vec<NavigationRequest> {
+    std::mem::take(queues.entry(serial.to_string()).or_default())
+}
+
+/// Stop queueing requests for a disconnected deck.
+pub fn close_navigation_queue(serial: &str) {
+    NAVIGATION_REQUESTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(serial);
+}
+
+impl Navigation {
+    pub fn page(&self) -> usize {
+        self.current.page
+    }
+
+    pub fn folder(&self) -> Option<&str> {
+        self.current.folder.as_deref()
+    }
+
+    pub fn in_folder(&self) -> bool {
+        self.current.folder.is_some()
+    }
+
+    /// Switch to a page id within the current folder.
+    pub fn set_page(&mut self, page: usize) {
+        self.current.page = page;
+    }
+
+    /// Open a folder at its first page, remembering where we came from.
+    /// Opening the folder we're already in does nothing.
+    pub fn open_folder(&mut self, name: &str) -> bool {
+        if self.folder() == Some(name) {
+            return false;
+        }
+        let parent = std::mem::replace(
+            &mut self.current,
+            PageLocation {
+                folder: Some(name.to_string()),
+                page: 0,
+            },
+        );
+        self.parents.push(parent);
+        true
+    }
+
+    /// Return to the page the current folder was opened from.
+    pub fn back(&mut self) -> bool {
+        match self.parents.pop() {
+            Some(parent) => {
+                self.current = parent;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Show an app's page, remembering where the deck was. `None` returns to
+    /// that page once no mapped app has focus.
+    pub fn focus_app(&mut self, target: Option<PageLocation>) -> bool {
+        match target {
+            Some(target) => {
+                let saved = self
+                    .app_return
+                    .get_or_insert_with(|| (self.current.clone(), self.parents.clone()))
+                    .clone();
+                // Back from an app's folder leads to where the deck was
+                let parents = if target.folder.is_some() {
+                    let (current, mut parents) = saved;
+                    parents.push(current);
+                    parents
+                } else {
+                    Vec::new()
+                };
+                let changed = self.current != target;
+                self.current = target;
+                self.parents = parents;
+                changed
+            }
+            None => match self.app_return.take() {
+                Some((current, parents)) => {
+                    let changed = self.current != current;
+                    self.current = current;
+                    self.parents = parents;
+                    changed
+                }
+                None => false,
+            },
+        }
+    }
+
+    /// Pages in the current folder, in display order.
+    pub fn pages_here(&self, pages: &[Page], bindings: &[Binding]) -> Vec<Page> {
+        page::pages_in(pages, bindings, self.folder())
+    }
+
+    /// Page ids a swipe or page capability can reach, in order: every page in
+    /// the current folder plus one empty page after the last.
+    pub fn reachable_pages(&self, pages: &[Page], bindings: &[Binding]) -> Vec<usize> {
+        let mut ids = page::page_ids(pages, bindings, self.folder());
+        ids.push(page::next_page_id(pages, bindings, self.folder()));
+        ids
+    }
+
+    /// Apply a queued request, returning whether the location changed.
+    ///
+    /// Page steps follow `reachable_pages` without wrapping; jumps to a page
+    /// that doesn't exist are ignored.
+    pub fn apply(
+        &mut self,
+        request: &NavigationRequest,
+        pages: &[Page],
+        bindings: &[Binding],
+    ) -> bool {
+        let reachable = self.reachable_pages(pages, bindings);
+        let position = reachable.iter().position(|id| *id == self.current.page);
+        let target = match request {
+            NavigationRequest::OpenFolder(name) => return self.open_folder(name),
+            NavigationRequest::Back => return self.back(),
+            NavigationRequest::FocusApp(target) => return self.focus_app(target.clone()),
+            NavigationRequest::NextPage => match position {
+                Some(i) => reachable.get(i + 1).copied(),
+                None => reachable.first().copied(),
+            },
+            NavigationRequest::PreviousPage => match position {
+                Some(i) => i.checked_sub(1).map(|i| reachable[i]),
+                None => reachable.first().copied(),
+            },
+            NavigationRequest::GoToPage(id) => reachable.contains(id).then_some(*id),
+        };
+        match target {
+            Some(id) if id != self.current.page => {
+                self.current.page = id;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Background image of the current page, if it has one.
+    pub fn background<'a>(&self, pages: &'a [Page]) -> Option<&'a str> {
+        pages
+            .iter()
+            .find(|p| p.id == self.current.page && p.folder == self.current.folder)
+            .and_then(|p| p.background.as_deref())
+    }
+
+    /// Touch strip background image of the current page, if it has one.
+    pub fn lcd_background<'a>(&self, pages: &'a [Page]) -> Option<&'a str> {
+        pages
+            .iter()
+            .find(|p| p.id == self.current.page && p.folder == self.current.folder)
+            .and_then(|p| p.lcd_background.as_deref())
+    }
+
+    /// Whether a binding belongs to the current page.
+    pub fn shows(&self, binding: &Binding) -> bool {
+        binding.page == self.current.page && binding.folder == self.current.folder
+    }
+
+    pub fn breadcrumb(&self) -> Vec<String> {
+        self.parents
+            .iter()
+            .chain(std::iter::once(&self.current))
+            .filter_map(|location| location.folder.clone())
+            .collect()
+    }
+
+    pub fn current_page(&self) -> CurrentPage {
+        CurrentPage {
+            page: self.current.page,
+            folder: self.current.folder.clone(),
+            breadcrumb: self.breadcrumb(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binding::InputRef;
+    use crate::capability::Capability;
+
+    #[test]
+    fn starts_on_root_first_page() {
+        let nav = Navigation::default();
+        assert_eq!(nav.page(), 0);
+        assert!(!nav.in_folder());
+        assert!(nav.breadcrumb().is_empty());
+    }
+
+    #[test]
+    fn open_folder_and_back_restores_parent_page() {
+        let mut nav = Navigation::default();
+        nav.set_page(2);
+
+        assert!(nav.open_folder("Streaming"));
+        assert_eq!(nav.folder(), Some("Streaming"));
+        assert_eq!(nav.page(), 0);
+
+        assert!(nav.back());
+        assert_eq!(nav.folder(), None);
+        assert_eq!(nav.page(), 2);
+        assert!(!nav.back());
+    }
+
+    #[test]
+    fn nested_folders_build_breadcrumb() {
+        let mut nav = Navigation::default();
+        nav.open_folder("Streaming");
+        nav.set_page(1);
+        nav.open_folder("Scenes");
+
+        assert_eq!(
+            nav.breadcrumb(),
+            vec!["Streaming".to_string(), "Scenes".to_string()]
+        );
+
+        nav.back();
+        assert_eq!(nav.folder(), Some("Streaming"));
+        assert_eq!(nav.page(), 1);
+        assert_eq!(nav.breadcrumb(), vec!["Streaming".to_string()]);
+    }
+
+    #[test]
+    fn reopening_current_folder_is_ignored() {
+        let mut nav = Navigation::default();
+        nav.open_folder("Streaming");
+        assert!(!nav.open_folder("Streaming"));
+        nav.back();
+        assert!(!nav.in_folder());
+    }
+
+    #[test]
+    fn apply_handles_requests() {
+        let mut nav = Navigation::default();
+        assert!(nav.apply(
+            &NavigationRequest::OpenFolder("Lights".to_string()),
+            &[],
+            &[]
+        ));
+        assert!(nav.apply(&NavigationRequest::Back, &[], &[]));
+        assert!(!nav.apply(&NavigationRequest::Back, &[], &[]));
+    }
+
+    fn binding_on(page: usize, folder: Option<&str>) -> Binding {
+        Binding {
+            page,
+            folder: folder.map(String::from),
+            ..Binding::new(InputRef::Button { index: 1 }, Capability::MediaNext)
+        }
+    }
+
+    #[test]
+    fn page_steps_are_clamped_without_wrapping() {
+        // Pages 0 and 1 are bound, so page 2 is the last reachable (empty) page
+        let bindings = vec![binding_on(0, None), binding_on(1, None)];
+        let mut nav = Navigation::default();
+
+        assert!(!nav.apply(&NavigationRequest::PreviousPage, &[], &bindings));
+        assert!(nav.apply(&NavigationRequest::NextPage, &[], &bindings));
+        assert!(nav.apply(&NavigationRequest::NextPage, &[], &bindings));
+        assert_eq!(nav.page(), 2);
+        assert!(!nav.apply(&NavigationRequest::NextPage, &[], &bindings));
+        assert!(nav.apply(&NavigationRequest::PreviousPage, &[], &bindings));
+        assert_eq!(nav.page(), 1);
+    }
+
+    #[test]
+    fn go_to_page_ignores_missing_pages() {
+        let bindings = vec![binding_on(5, None), binding_on(0, Some("Lights"))];
+        let mut nav = Navigation::default();
+
+        assert!(nav.apply(&NavigationRequest::GoToPage(5), &[], &bindings));
+        assert_eq!(nav.page(), 5);
+        assert!(!nav.apply(&NavigationRequest::GoToPage(5), &[], &bindings));
+        assert!(!nav.apply(&NavigationRequest::GoToPage(4), &[], &bindings));
+
+        nav.open_folder("Lights");
+        assert!(!nav.apply(&NavigationRequest::GoToPage(5), &[], &bindings));
+        assert!(nav.apply(&NavigationRequest::GoToPage(1), &[], &bindings));
+    }
+
+    #[test]
+    fn page_steps_follow_page_table_order() {
+        let pages = vec![
+            Page {
+                id: 3,
+                name: "Lights".to_string(),
+                folder: None,
+                device: None,
+                background: Some("bg.png".to_string()),
+                lcd_background: Some("strip.png".to_string()),
+                order: 0,
+            },
+            Page {
+                id: 0,
+                name: "Home".to_string(),
+                folder: None,
+                device: None,
+                background: None,
+                lcd_background: None,
+                order: 1,
+            },
+        ];
+        let bindings = vec![binding_on(0, None), binding_on(3, None)];
+        let mut nav = Navigation::default();
+
+        assert_eq!(nav.reachable_pages(&pages, &bindings), vec![3, 0, 4]);
+        assert!(nav.apply(&NavigationRequest::PreviousPage, &pages, &bindings));
+        assert_eq!(nav.page(), 3);
+        assert_eq!(nav.background(&pages), Some("bg.png"));
+        assert_eq!(nav.lcd_background(&pages), Some("strip.png"));
+        assert!(!nav.apply(&NavigationRequest::PreviousPage, &pages, &bindings));
+        assert!(nav.apply(&NavigationRequest::NextPage, &pages, &bindings));
+        assert_eq!(nav.page(), 0);
+        assert_eq!(nav.background(&pages), None);
+        assert_eq!(nav.lcd_background(&pages), None);
+    }
+
+    #[test]
+    fn focus_app_restores_previous_page() {
+        let mut nav = Navigation::default();
+        nav.set_page(2);
+
+        let obs = PageLocation {
+            folder: None,
+            page: 5,
+        };
+        assert!(nav.apply(&NavigationRequest::FocusApp(Some(obs.clone())), &[], &[]));
+        assert_eq!(nav.page(), 5);
+
+        // Moving between mapped apps keeps the original return point
+        let lights = PageLocation {
+            folder: Some("Lights".to_string()),
+            page: 0,
+        };
+        assert!(nav.apply(&NavigationRequest::FocusApp(Some(lights)), &[], &[]));
+        assert_eq!(nav.breadcrumb(), vec!["Lights".to_string()]);
+
+        assert!(nav.apply(&NavigationRequest::FocusApp(None), &[], &[]));
+        assert_eq!(nav.folder(), None);
+        assert_eq!(nav.page(), 2);
+        assert!(!nav.apply(&NavigationRequest::FocusApp(None), &[], &[]));
+    }
+
+    #[test]
+    fn requests_reach_their_own_deck() {
+        let mut queues = NavigationQueues::new();
+        take_requests(&mut queues, "deck-a");
+        take_requests(&mut queues, "deck-b");
+
+        push_request(&mut queues, Some("deck-a"), NavigationRequest::NextPage);
+        push_request(&mut queues, None, NavigationRequest::Back);
+        push_request(&mut queues, Some("unplugged"), NavigationRequest::NextPage);
+
+        assert_eq!(
+            take_requests(&mut queues, "deck-a"),
+            vec![NavigationRequest::NextPage, NavigationRequest::Back]
+        );
+        assert_eq!(
+            take_requests(&mut queues, "deck-b"),
+            vec![NavigationRequest::Back]
+        );
+        assert!(take_requests(&mut queues, "unplugged").is_empty());
+    }
+}
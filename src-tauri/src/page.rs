@@ -0,0 +1,326 @@
+//! Named pages.
+//!
+//! Bindings reference a page by id (`Binding::page`). The `[[pages]]` table in
+//! `bindings.toml` gives pages a name, backgrounds and position; pages that only
+//! exist because a binding uses them are listed after the named ones, so older
+//! configs without a page table keep working unchanged.
+
+use crate::binding::Binding;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Page {
+    /// Stable id referenced by `Binding::page` (unique within a folder)
+    pub id: usize,
+    #[serde(default)]
+    pub name: String,
+    /// Folder the page belongs to (None = root pages)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+    /// Serial of the deck this page is on (None = every deck)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    /// Image shown on keys without an image of their own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+    /// Image stretched across a Stream Deck Plus touch strip, behind the
+    /// encoders' sections
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lcd_background: Option<String>,
+    /// Position among the folder's pages (lower first)
+    #[serde(default)]
+    pub order: usize,
+}
+
+impl Page {
+    fn new(id: usize, folder: Option<&str>, order: usize) -> Self {
+        Self {
+            id,
+            name: String::new(),
+            folder: folder.map(String::from),
+            device: None,
+            background: None,
+            lcd_background: None,
+            order,
+        }
+    }
+}
+
+/// All pages in a folder in display order, including unnamed pages that are
+/// only referenced by bindings. There is always at least one page.
+pub fn pages_in(pages: &[Page], bindings: &[Binding], folder: Option<&str>) -> Vec<Page> {
+    let mut result: Vec<Page> = pages
+        .iter()
+        .filter(|p| p.folder.as_deref() == folder)
+        .cloned()
+        .collect();
+    result.sort_by_key(|p| (p.order, p.id));
+
+    let mut implicit: Vec<usize> = bindings
+        .iter()
+        .filter(|b| b.folder.as_deref() == folder)
+        .map(|b| b.page)
+        .filter(|id| !result.iter().any(|p| p.id == *id))
+        .collect();
+    implicit.sort_unstable();
+    implicit.dedup();
+
+    let next_order = result.last().map(|p| p.order + 1).unwrap_or(0);
+    result.extend(
+        implicit
+            .into_iter()
+            .enumerate()
+            .map(|(i, id)| Page::new(id, folder, next_order + i)),
+    );
+
+    if result.is_empty() {
+        result.push(Page::new(0, folder, 0));
+    }
+    result
+}
+
+/// Page ids in a folder in display order.
+pub fn page_ids(pages: &[Page], bindings: &[Binding], folder: Option<&str>) -> Vec<usize> {
+    pages_in(pages, bindings, folder)
+        .into_iter()
+        .map(|p| p.id)
+        .collect()
+}
+
+/// An id above every page and binding page in the folder.
+pub fn next_page_id(pages: &[Page], bindings: &[Binding], folder: Option<&str>) -> usize {
+    pages_in(pages, bindings, folder)
+        .iter()
+        .map(|p| p.id + 1)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Find a page in the folder's table, adding every implicit page to the table
+/// first so it can be edited.
+fn materialize<'a>(
+    pages: &'a mut Vec<Page>,
+    bindings: &[Binding],
+    folder: Option<&str>,
+    id: usize,
+) -> Result<&'a mut Page> {
+    let ordered = pages_in(pages, bindings, folder);
+    if !ordered.iter().any(|p| p.id == id) {
+        bail!("Page {} not found", id);
+    }
+    pages.retain(|p| p.folder.as_deref() != folder);
+    pages.extend(ordered);
+    Ok(pages
+        .iter_mut()
+        .find(|p| p.id == id && p.folder.as_deref() == folder)
+        .expect("page was just added"))
+}
+
+/// Append a new, empty page to a folder.
+pub fn create_page(
+    pages: &mut Vec<Page>,
+    bindings: &[Binding],
+    folder: Option<&str>,
+    name: &str,
+) -> Page {
+    let ordered = pages_in(pages, bindings, folder);
+    let order = ordered.last().map(|p| p.order + 1).unwrap_or(0);
+    let mut page = Page::new(next_page_id(pages, bindings, folder), folder, order);
+    page.name = name.to_string();
+
+    pages.retain(|p| p.folder.as_deref() != folder);
+    pages.extend(ordered);
+    pages.push(page.clone());
+    page
+}
+
+pub fn rename_page(
+    pages: &mut Vec<Page>,
+    bindings: &[Binding],
+    folder: Option<&str>,
+    id: usize,
+    name: &str,
+) -> Result<()> {
+    materialize(pages, bindings, folder, id)?.name = name.to_string();
+    Ok(())
+}
+
+pub fn set_page_background(
+    pages: &mut Vec<Page>,
+    bindings: &[Binding],
+    folder: Option<&str>,
+    id: usize,
+    background: Option<String>,
+) -> Result<()> {
+    materialize(pages, bindings, folder, id)?.background = background;
+    Ok(())
+}
+
+pub fn set_page_lcd_background(
+    pages: &mut Vec<Page>,
+    bindings: &[Binding],
+    folder: Option<&str>,
+    id: usize,
+    background: Option<String>,
+) -> Result<()> {
+    materialize(pages, bindings, folder, id)?.lcd_background = background;
+    Ok(())
+}
+
+/// Put a folder's pages in the given order. `ids` must list every page once.
+pub fn reorder_pages(
+    pages: &mut Vec<Page>,
+    bindings: &[Binding],
+    folder: Option<&str>,
+    ids: &[usize],
+) -> Result<()> {
+    let mut current = page_ids(pages, bindings, folder);
+    let mut requested = ids.to_vec();
+    current.sort_unstable();
+    requested.sort_unstable();
+    if current != requested {
+        bail!("Page order must list every page exactly once");
+    }
+
+    for (order, id) in ids.iter().enumerate() {
+        materialize(pages, bindings, folder, *id)?.order = order;
+    }
+    Ok(())
+}
+
+/// Copy a page and its bindings to a new page right after it.
+pub fn duplicate_page(
+    pages: &mut Vec<Page>,
+    bindings: &mut Vec<Binding>,
+    folder: Option<&str>,
+    id: usize,
+) -> Result<Page> {
+    let new_id = next_page_id(pages, bindings, folder);
+    let source = materialize(pages, bindings, folder, id)?.clone();
+
+    // Make room directly after the source page
+    for page in pages
+        .iter_mut()
+        .filter(|p| p.folder.as_deref() == folder && p.order > source.order)
+    {
+        page.order += 1;
+    }
+    let copy = Page {
+        id: new_id,
+        name: if source.name.is_empty() {
+            String::new()
+        } else {
+            format!("{} copy", source.name)
+        },
+        order: source.order + 1,
+        ..source
+    };
+    pages.push(copy.clone());
+
+    let copied: Vec<Binding> = bindings
+        .iter()
+        .filter(|b| b.page == id && b.folder.as_deref() == folder)
+        .map(|b| Binding {
+            page: new_id,
+            ..b.clone()
+        })
+        .collect();
+    bindings.extend(copied);
+
+    Ok(copy)
+}
+
+/// Remove a page and every binding on it.
+pub fn delete_page(
+    pages: &mut Vec<Page>,
+    bindings: &mut Vec<Binding>,
+    folder: Option<&str>,
+    id: usize,
+) -> Result<()> {
+    materialize(pages, bindings, folder, id)?;
+    pages.retain(|p| !(p.id == id && p.folder.as_deref() == folder));
+    bindings.retain(|b| !(b.page == id && b.folder.as_deref() == folder));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binding::InputRef;
+    use crate::capability::Capability;
+
+    fn binding(index: usize, page: usize) -> Binding {
+        Binding {
+            page,
+            ..Binding::new(InputRef::Button { index }, Capability::MediaNext)
+        }
+    }
+
+    fn named(id: usize, name: &str, order: usize) -> Page {
+        Page {
+            name: name.to_string(),
+            ..Page::new(id, None, order)
+        }
+    }
+
+    #[test]
+    fn implicit_pages_follow_named_pages() {
+        let pages = vec![named(4, "Lights", 1), named(2, "Stream", 0)];
+        let bindings = vec![binding(0, 0), binding(0, 4), binding(1, 7)];
+        assert_eq!(page_ids(&pages, &bindings, None), vec![2, 4, 0, 7]);
+        assert_eq!(next_page_id(&pages, &bindings, None), 8);
+    }
+
+    #[test]
+    fn empty_folder_has_one_page() {
+        assert_eq!(page_ids(&[], &[], Some("Streaming")), vec![0]);
+    }
+
+    #[test]
+    fn create_and_rename() {
+        let mut pages = vec![];
+        let bindings = vec![binding(0, 0), binding(0, 1)];
+
+        let page = create_page(&mut pages, &bindings, None, "Lights");
+        assert_eq!(page.id, 2);
+        assert_eq!(page_ids(&pages, &bindings, None), vec![0, 1, 2]);
+
+        rename_page(&mut pages, &bindings, None, 0, "Home").unwrap();
+        assert_eq!(pages_in(&pages, &bindings, None)[0].name, "Home");
+        assert!(rename_page(&mut pages, &bindings, None, 9, "Nope").is_err());
+    }
+
+    #[test]
+    fn reorder_keeps_bindings_on_their_page() {
+        let mut pages = vec![];
+        let bindings = vec![binding(0, 0), binding(1, 1), binding(2, 2)];
+
+        reorder_pages(&mut pages, &bindings, None, &[2, 0, 1]).unwrap();
+        assert_eq!(page_ids(&pages, &bindings, None), vec![2, 0, 1]);
+        assert!(reorder_pages(&mut pages, &bindings, None, &[2, 0]).is_err());
+    }
+
+    #[test]
+    fn duplicate_copies_bindings_after_source() {
+        let mut pages = vec![named(0, "Home", 0), named(1, "Lights", 1)];
+        let mut bindings = vec![binding(3, 0), binding(4, 1)];
+
+        let copy = duplicate_page(&mut pages, &mut bindings, None, 0).unwrap();
+        assert_eq!(copy.id, 2);
+        assert_eq!(copy.name, "Home copy");
+        assert_eq!(page_ids(&pages, &bindings, None), vec![0, 2, 1]);
+        assert_eq!(bindings.iter().filter(|b| b.page == 2).count(), 1);
+    }
+
+    #[test]
+    fn delete_removes_page_and_bindings() {
+        let mut pages = vec![named(0, "Home", 0), named(1, "Lights", 1)];
+        let mut bindings = vec![binding(3, 0), binding(4, 1)];
+
+        delete_page(&mut pages, &mut bindings, None, 1).unwrap();
+        assert_eq!(page_ids(&pages, &bindings, None), vec![0]);
+        assert_eq!(bindings.len(), 1);
+        assert!(delete_page(&mut pages, &mut bindings, None, 1).is_err());
+    }
+}
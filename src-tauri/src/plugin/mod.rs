@@ -7,13 +7,93 @@ pub mod registry;
 pub mod types;
 
 pub use registry::PluginRegistry;
-pub use types::{CapabilityMetadata, ParameterDef, ParameterType, PluginConfig, PluginInfo};
+pub use types::{
+    ActionOutcome, BindingValue, CapabilityMetadata, ParameterDef, ParameterType, PluginConfig,
+    PluginInfo,
+};
 
 use crate::binding::Binding;
+use crate::device::OnDevice;
 use crate::input_processor::LogicalEvent;
 use crate::state_manager::SystemState;
 use std::any::Any;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A finished action waiting for its deck's event loop to show it
+#[derive(Debug, Clone)]
+pub struct ActionResult {
+    pub binding: Binding,
+    /// The error holds the message shown to the user
+    pub result: Result<(), String>,
+    /// Whether to flash a checkmark or red X on the key
+    pub flash: bool,
+    queued_at: Instant,
+}
+
+/// How long a result waits for its deck. A deck that disconnects before
+/// a pending action reports back never takes it, so it's dropped instead.
+const ACTION_RESULT_TTL: Duration = Duration::from_secs(30);
+
+static ACTION_RESULTS: Mutex<Vec<ActionResult>> = Mutex::new(Vec::new());
+
+/// State keys reported by plugins that only know active/inactive
+pub const ACTIVE_STATE: &str = "active";
+pub const INACTIVE_STATE: &str = "inactive";
+
+/// `ACTIVE_STATE` or `INACTIVE_STATE`
+pub fn active_state_key(active: bool) -> &'static str {
+    if active {
+        ACTIVE_STATE
+    } else {
+        INACTIVE_STATE
+    }
+}
+
+/// Report the result of an action that returned `ActionOutcome::Pending`.
+pub fn report_action_result(binding: &Binding, result: Result<(), String>) {
+    queue_action_result(ActionResult {
+        binding: binding.clone(),
+        result,
+        flash: true,
+        queued_at: Instant::now(),
+    });
+}
+
+/// Queue the result of an action that finished while handling its event.
+pub fn record_outcome(binding: &Binding, outcome: &ActionOutcome) {
+    let result = match outcome {
+        ActionOutcome::Ok => Ok(()),
+        ActionOutcome::Error(message) => Err(message.clone()),
+        ActionOutcome::Ignored | ActionOutcome::Pending => return,
+    };
+    queue_action_result(ActionResult {
+        binding: binding.clone(),
+        result,
+        flash: true,
+        queued_at: Instant::now(),
+    });
+}
+
+fn queue_action_result(action: ActionResult) {
+    if let Err(message) = &action.result {
+        eprintln!("{}", message);
+    }
+    let mut queued = ACTION_RESULTS.lock().unwrap_or_else(|e| e.into_inner());
+    queued.retain(|queued| queued.queued_at.elapsed() < ACTION_RESULT_TTL);
+    queued.push(action);
+}
+
+/// Take the queued results of actions pressed on a deck. A disconnecting
+/// deck calls this too, to drop the ones it will never show.
+pub fn take_action_results(serial: &str) -> Vec<ActionResult> {
+    let mut queued = ACTION_RESULTS.lock().unwrap_or_else(|e| e.into_inner());
+    let (ours, others) = std::mem::take(&mut *queued)
+        .into_iter()
+        .partition(|action| action.binding.on_device(serial));
+    *queued = others;
+    ours
+}
 
 /// Trait implemented by all plugins.
 ///
@@ -35,23 +115,62 @@ pub trait Plugin: Send + Sync {
 
     /// Handle a logical event for a binding.
     ///
-    /// Returns `true` if this plugin handled the event, `false` otherwise.
-    /// The plugin should check if the binding's capability belongs to it.
+    /// Returns `ActionOutcome::Ignored` if this plugin doesn't act on the
+    /// event, otherwise whether the action succeeded, failed or continues in
+    /// the background. The plugin should check if the binding's capability
+    /// belongs to it.
     fn handle_event(
         &self,
         event: &LogicalEvent,
         binding: &Binding,
         system_state: &Arc<Mutex<SystemState>>,
-    ) -> bool;
+    ) -> ActionOutcome;
 
     /// Check if a capability ID belongs to this plugin.
     fn owns_capability(&self, capability_type: &str) -> bool;
 
-    /// Query the active state for a binding (for button image selection).
+    /// Key of the binding's current state (e.g., "recording", "paused"),
+    /// which picks its image, color and label from `Binding::states`.
+    ///
+    /// Plugins with a plain on/off state report `ACTIVE_STATE` or
+    /// `INACTIVE_STATE`. None if the state isn't known (e.g., the device is
+    /// unreachable).
+    fn state(&self, binding: &Binding, system_state: &SystemState) -> Option<String>;
+
+    /// Whether the binding is in one of its capability's active states
+    /// (e.g., muted, playing). Derived from `state`.
+    fn is_active(&self, binding: &Binding, system_state: &SystemState) -> bool {
+        self.state(binding, system_state)
+            .is_some_and(|state| binding.capability.active_states().contains(&state.as_str()))
+    }
+
+    /// Live text to show on the binding's key in place of its label.
+    ///
+    /// Used by capabilities that display a value (e.g., command output).
+    fn display_text(&self, _binding: &Binding, _system_state: &SystemState) -> Option<String> {
+        None
+    }
+
+    /// Value for a `{key}` or `{key:argument}` placeholder in a label, if this
+    /// plugin provides `key` (see `label_template`).
     ///
-    /// Returns `true` if the binding is in an "active" state (e.g., muted, playing).
-    /// This is used to determine whether to show the alternate button image.
-    fn is_active(&self, binding: &Binding, system_state: &SystemState) -> bool;
+    /// `binding` is the binding whose label is being drawn, so a placeholder
+    /// can refer to the binding's own device (e.g., its OBS input).
+    fn label_value(
+        &self,
+        _binding: &Binding,
+        _key: &str,
+        _argument: Option<&str>,
+        _system_state: &SystemState,
+    ) -> Option<String> {
+        None
+    }
+
+    /// Current value of a binding's capability (e.g., a volume level), drawn
+    /// by bindings that set a `gauge`.
+    fn value(&self, _binding: &Binding, _system_state: &SystemState) -> Option<BindingValue> {
+        None
+    }
 
     /// Initialize the plugin with configuration.
     ///
@@ -120,15 +239,38 @@ mod tests {
     struct MinimalPlugin;
 
     impl Plugin for MinimalPlugin {
-        fn id(&self) -> &'static str { "minimal" }
-        fn name(&self) -> &'static str { "Minimal" }
-        fn category(&self) -> &'static str { "Test" }
-        fn capabilities(&self) -> Vec<CapabilityMetadata> { vec![] }
-        fn handle_event(&self, _: &LogicalEvent, _: &Binding, _: &Arc<Mutex<SystemState>>) -> bool { false }
-        fn owns_capability(&self, _: &str) -> bool { false }
-        fn is_active(&self, _: &Binding, _: &SystemState) -> bool { false }
-        fn as_any(&self) -> &dyn Any { self }
-        fn as_any_mut(&mut self) -> &mut dyn Any { self }
+        fn id(&self) -> &'static str {
+            "minimal"
+        }
+        fn name(&self) -> &'static str {
+            "Minimal"
+        }
+        fn category(&self) -> &'static str {
+            "Test"
+        }
+        fn capabilities(&self) -> Vec<CapabilityMetadata> {
+            vec![]
+        }
+        fn handle_event(
+            &self,
+            _: &LogicalEvent,
+            _: &Binding,
+            _: &Arc<Mutex<SystemState>>,
+        ) -> ActionOutcome {
+            ActionOutcome::Ignored
+        }
+        fn owns_capability(&self, _: &str) -> bool {
+            false
+        }
+        fn state(&self, _: &Binding, _: &SystemState) -> Option<String> {
+            None
+        }
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
         // All other methods use defaults
     }
 
@@ -162,6 +304,26 @@ mod tests {
         assert!(!plugin.is_core());
     }
 
+    #[test]
+    fn finished_actions_flash_on_success() {
+        let binding = Binding {
+            device: Some("FLASH-TEST".to_string()),
+            ..Binding::new(
+                crate::binding::InputRef::Button { index: 0 },
+                crate::capability::Capability::MediaNext,
+            )
+        };
+        record_outcome(&binding, &ActionOutcome::Ok);
+        record_outcome(&binding, &ActionOutcome::Ignored);
+
+        let results: Vec<ActionResult> = take_action_results("FLASH-TEST")
+            .into_iter()
+            .filter(|action| action.binding.device == binding.device)
+            .collect();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].flash && results[0].result.is_ok());
+    }
+
     #[test]
     fn trait_default_initialize_succeeds() {
         let mut plugin = MinimalPlugin;
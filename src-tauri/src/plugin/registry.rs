@@ -1,12 +1,15 @@
 //! Plugin registry for managing loaded plugins.
 
-use super::{CapabilityMetadata, Plugin, PluginConfig, PluginInfo};
+use super::{ActionOutcome, BindingValue, CapabilityMetadata, Plugin, PluginConfig, PluginInfo};
 use crate::binding::Binding;
+use crate::capability::{Capability, MacroStep};
 use crate::commands::CapabilityInfo;
 use crate::input_processor::LogicalEvent;
+use crate::label_template;
 use crate::state_manager::SystemState;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
 
 /// Central registry for managing plugins.
 ///
@@ -148,18 +151,27 @@ impl PluginRegistry {
 
     /// Handle a logical event by dispatching to the appropriate plugin.
     ///
-    /// Returns `true` if a plugin handled the event.
+    /// Returns the plugin's outcome, or `Ignored` if no enabled plugin owns
+    /// the capability.
     pub fn handle_event(
         &self,
         event: &LogicalEvent,
         binding: &Binding,
         system_state: &Arc<Mutex<SystemState>>,
-    ) -> bool {
+    ) -> ActionOutcome {
+        // A macro's steps can belong to any plugin, so the registry runs it
+        if let Capability::Macro { steps } = &binding.capability {
+            return self.run_macro(event, binding, steps, system_state);
+        }
+
         // Get the capability type from the binding
         let capability_type = get_capability_type(&binding.capability);
 
         #[cfg(debug_assertions)]
-        eprintln!("    registry.handle_event: capability_type={}", capability_type);
+        eprintln!(
+            "    registry.handle_event: capability_type={}",
+            capability_type
+        );
 
         // Look up which plugin owns this capability
         let plugin_id = {
@@ -172,15 +184,18 @@ impl PluginRegistry {
 
         let Some(plugin_id) = plugin_id else {
             #[cfg(debug_assertions)]
-            eprintln!("    registry.handle_event: NO PLUGIN FOUND for {}", capability_type);
-            return false;
+            eprintln!(
+                "    registry.handle_event: NO PLUGIN FOUND for {}",
+                capability_type
+            );
+            return ActionOutcome::Ignored;
         };
 
         // Check if plugin is enabled
         {
             let enabled = self.enabled.read().unwrap();
             if !*enabled.get(&plugin_id).unwrap_or(&true) {
-                return false;
+                return ActionOutcome::Ignored;
             }
         }
 
@@ -189,14 +204,101 @@ impl PluginRegistry {
         if let Some(plugin) = plugins.get(&plugin_id) {
             plugin.handle_event(event, binding, system_state)
         } else {
-            false
+            ActionOutcome::Ignored
         }
     }
 
-    /// Check if a binding is in an "active" state.
-    ///
-    /// Used for determining which button image to display.
-    pub fn is_binding_active(&self, binding: &Binding, system_state: &SystemState) -> bool {
+    /// Run a macro's steps in order on the calling thread, each dispatched as
+    /// if the binding had the step's capability. Only presses trigger a macro.
+    /// A failed step doesn't stop the rest; the first failure is returned.
+    /// Steps that finish in the background report their own results.
+    fn run_macro(
+        &self,
+        event: &LogicalEvent,
+        binding: &Binding,
+        steps: &[MacroStep],
+        system_state: &Arc<Mutex<SystemState>>,
+    ) -> ActionOutcome {
+        if !matches!(event, LogicalEvent::Button(e) | LogicalEvent::EncoderPress(e) if e.pressed) {
+            return ActionOutcome::Ignored;
+        }
+
+        let mut outcome = ActionOutcome::Ok;
+        for step in steps {
+            if step.delay_ms > 0 {
+                std::thread::sleep(Duration::from_millis(step.delay_ms));
+            }
+            let step_binding = Binding {
+                capability: step.capability.clone(),
+                ..binding.clone()
+            };
+            match self.handle_event(event, &step_binding, system_state) {
+                ActionOutcome::Ignored => {
+                    eprintln!(
+                        "Macro step {} was not handled",
+                        get_capability_type(&step.capability)
+                    );
+                }
+                error @ ActionOutcome::Error(_) if outcome == ActionOutcome::Ok => outcome = error,
+                _ => {}
+            }
+        }
+        outcome
+    }
+
+    /// Key of a binding's current state (see `Plugin::state`).
+    pub fn binding_state(&self, binding: &Binding, system_state: &SystemState) -> Option<String> {
+        self.with_owning_plugin(binding, |plugin| plugin.state(binding, system_state))
+            .flatten()
+    }
+
+    /// Live text to show on a binding's key in place of its label, if any.
+    pub fn binding_display_text(
+        &self,
+        binding: &Binding,
+        system_state: &SystemState,
+    ) -> Option<String> {
+        self.with_owning_plugin(binding, |plugin| plugin.display_text(binding, system_state))
+            .flatten()
+    }
+
+    /// Current value of a binding's capability, if its plugin reports one.
+    pub fn binding_value(
+        &self,
+        binding: &Binding,
+        system_state: &SystemState,
+    ) -> Option<BindingValue> {
+        self.with_owning_plugin(binding, |plugin| plugin.value(binding, system_state))
+            .flatten()
+    }
+
+    /// A binding's label with its placeholders resolved by the enabled plugins.
+    pub fn resolve_label(
+        &self,
+        binding: &Binding,
+        label: &str,
+        system_state: &SystemState,
+    ) -> String {
+        if !label_template::has_placeholders(label) {
+            return label.to_string();
+        }
+
+        let plugins = self.plugins.read().unwrap();
+        let enabled = self.enabled.read().unwrap();
+        label_template::render(label, |key, argument| {
+            plugins
+                .iter()
+                .filter(|(id, _)| *enabled.get(*id).unwrap_or(&true))
+                .find_map(|(_, plugin)| plugin.label_value(binding, key, argument, system_state))
+        })
+    }
+
+    /// Run `f` with the enabled plugin that owns a binding's capability.
+    fn with_owning_plugin<T>(
+        &self,
+        binding: &Binding,
+        f: impl FnOnce(&dyn Plugin) -> T,
+    ) -> Option<T> {
         let capability_type = get_capability_type(&binding.capability);
 
         let plugin_id = {
@@ -204,24 +306,18 @@ impl PluginRegistry {
             cap_map.get(capability_type).cloned()
         };
 
-        let Some(plugin_id) = plugin_id else {
-            return false;
-        };
+        let plugin_id = plugin_id?;
 
         // Check if plugin is enabled
         {
             let enabled = self.enabled.read().unwrap();
             if !*enabled.get(&plugin_id).unwrap_or(&true) {
-                return false;
+                return None;
             }
         }
 
         let plugins = self.plugins.read().unwrap();
-        if let Some(plugin) = plugins.get(&plugin_id) {
-            plugin.is_active(binding, system_state)
-        } else {
-            false
-        }
+        plugins.get(&plugin_id).map(|plugin| f(plugin.as_ref()))
     }
 
     /// Shutdown all plugins.
@@ -241,8 +337,7 @@ impl Default for PluginRegistry {
 }
 
 /// Extract the capability type string from a Capability enum.
-fn get_capability_type(capability: &crate::capability::Capability) -> &'static str {
-    use crate::capability::Capability;
+fn get_capability_type(capability: &Capability) -> &'static str {
     match capability {
         Capability::SystemAudio { .. } => "SystemAudio",
         Capability::Mute => "Mute",
@@ -257,8 +352,17 @@ fn get_capability_type(capability: &crate::capability::Capability) -> &'static s
         Capability::MediaPrevious => "MediaPrevious",
         Capability::MediaStop => "MediaStop",
         Capability::RunCommand { .. } => "RunCommand",
+        Capability::CommandDisplay { .. } => "CommandDisplay",
         Capability::LaunchApp { .. } => "LaunchApp",
         Capability::OpenURL { .. } => "OpenURL",
+        Capability::SendHotkey { .. } => "SendHotkey",
+        Capability::TypeText { .. } => "TypeText",
+        Capability::OpenFolder { .. } => "OpenFolder",
+        Capability::NextPage => "NextPage",
+        Capability::PreviousPage => "PreviousPage",
+        Capability::GoToPage { .. } => "GoToPage",
+        Capability::PageByEncoder => "PageByEncoder",
+        Capability::Macro { .. } => "Macro",
         Capability::ElgatoKeyLight { .. } => "ElgatoKeyLight",
         // OBS capabilities
         Capability::OBSScene { .. } => "OBSScene",
@@ -276,8 +380,10 @@ fn get_capability_type(capability: &crate::capability::Capability) -> &'static s
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::capability::Capability;
+    use crate::binding::InputRef;
+    use crate::events::ButtonEvent;
     use std::any::Any;
+    use std::collections::BTreeMap;
 
     #[test]
     fn get_capability_type_returns_correct_strings() {
@@ -286,11 +392,22 @@ mod tests {
             "SystemAudio"
         );
         assert_eq!(get_capability_type(&Capability::Mute), "Mute");
-        assert_eq!(get_capability_type(&Capability::MediaPlayPause), "MediaPlayPause");
+        assert_eq!(
+            get_capability_type(&Capability::MediaPlayPause),
+            "MediaPlayPause"
+        );
         assert_eq!(
             get_capability_type(&Capability::RunCommand {
                 command: "test".to_string(),
-                toggle: false
+                toggle: false,
+                state_command: None,
+                state_match: None,
+                on_command: None,
+                off_command: None,
+                cwd: None,
+                env: BTreeMap::new(),
+                timeout_ms: None,
+                kill_on_release: false,
             }),
             "RunCommand"
         );
@@ -303,20 +420,53 @@ mod tests {
     }
 
     impl Plugin for MockPlugin {
-        fn id(&self) -> &'static str { self.id }
-        fn name(&self) -> &'static str { "Mock Plugin" }
-        fn category(&self) -> &'static str { "Test" }
-        fn capabilities(&self) -> Vec<CapabilityMetadata> { vec![] }
-        fn handle_event(&self, _: &LogicalEvent, _: &Binding, _: &Arc<Mutex<SystemState>>) -> bool { false }
-        fn owns_capability(&self, _: &str) -> bool { false }
-        fn is_active(&self, _: &Binding, _: &SystemState) -> bool { false }
-        fn as_any(&self) -> &dyn Any { self }
-        fn as_any_mut(&mut self) -> &mut dyn Any { self }
-        fn version(&self) -> &'static str { "2.0.0" }
-        fn description(&self) -> &'static str { "A mock plugin for testing" }
-        fn documentation(&self) -> &'static str { "# Mock\n\nDocs here." }
-        fn icon(&self) -> &'static str { "🧪" }
-        fn is_core(&self) -> bool { self.is_core }
+        fn id(&self) -> &'static str {
+            self.id
+        }
+        fn name(&self) -> &'static str {
+            "Mock Plugin"
+        }
+        fn category(&self) -> &'static str {
+            "Test"
+        }
+        fn capabilities(&self) -> Vec<CapabilityMetadata> {
+            vec![]
+        }
+        fn handle_event(
+            &self,
+            _: &LogicalEvent,
+            _: &Binding,
+            _: &Arc<Mutex<SystemState>>,
+        ) -> ActionOutcome {
+            ActionOutcome::Ignored
+        }
+        fn owns_capability(&self, _: &str) -> bool {
+            false
+        }
+        fn state(&self, _: &Binding, _: &SystemState) -> Option<String> {
+            None
+        }
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+        fn version(&self) -> &'static str {
+            "2.0.0"
+        }
+        fn description(&self) -> &'static str {
+            "A mock plugin for testing"
+        }
+        fn documentation(&self) -> &'static str {
+            "# Mock\n\nDocs here."
+        }
+        fn icon(&self) -> &'static str {
+            "🧪"
+        }
+        fn is_core(&self) -> bool {
+            self.is_core
+        }
     }
 
     #[test]
@@ -329,7 +479,13 @@ mod tests {
     #[test]
     fn registry_register_adds_plugin() {
         let registry = PluginRegistry::new();
-        registry.register(Box::new(MockPlugin { id: "mock", is_core: false }), None);
+        registry.register(
+            Box::new(MockPlugin {
+                id: "mock",
+                is_core: false,
+            }),
+            None,
+        );
 
         let plugins = registry.get_plugins();
         assert_eq!(plugins.len(), 1);
@@ -339,7 +495,13 @@ mod tests {
     #[test]
     fn get_plugins_includes_metadata_fields() {
         let registry = PluginRegistry::new();
-        registry.register(Box::new(MockPlugin { id: "mock", is_core: false }), None);
+        registry.register(
+            Box::new(MockPlugin {
+                id: "mock",
+                is_core: false,
+            }),
+            None,
+        );
 
         let plugins = registry.get_plugins();
         let plugin = &plugins[0];
@@ -358,7 +520,13 @@ mod tests {
     #[test]
     fn get_plugins_core_flag_set_correctly() {
         let registry = PluginRegistry::new();
-        registry.register(Box::new(MockPlugin { id: "core_mock", is_core: true }), None);
+        registry.register(
+            Box::new(MockPlugin {
+                id: "core_mock",
+                is_core: true,
+            }),
+            None,
+        );
 
         let plugins = registry.get_plugins();
         assert!(plugins[0].is_core);
@@ -367,7 +535,13 @@ mod tests {
     #[test]
     fn set_plugin_enabled_updates_state() {
         let registry = PluginRegistry::new();
-        registry.register(Box::new(MockPlugin { id: "mock", is_core: false }), None);
+        registry.register(
+            Box::new(MockPlugin {
+                id: "mock",
+                is_core: false,
+            }),
+            None,
+        );
 
         // Initially enabled
         assert!(registry.is_plugin_enabled("mock"));
@@ -396,7 +570,13 @@ mod tests {
     #[test]
     fn get_plugins_reflects_enabled_state() {
         let registry = PluginRegistry::new();
-        registry.register(Box::new(MockPlugin { id: "mock", is_core: false }), None);
+        registry.register(
+            Box::new(MockPlugin {
+                id: "mock",
+                is_core: false,
+            }),
+            None,
+        );
 
         // Initially enabled
         let plugins = registry.get_plugins();
@@ -415,8 +595,114 @@ mod tests {
             enabled: false,
             settings: std::collections::HashMap::new(),
         };
-        registry.register(Box::new(MockPlugin { id: "mock", is_core: false }), Some(&config));
+        registry.register(
+            Box::new(MockPlugin {
+                id: "mock",
+                is_core: false,
+            }),
+            Some(&config),
+        );
 
         assert!(!registry.is_plugin_enabled("mock"));
     }
+
+    // Records the capabilities it is asked to handle, in order
+    struct RecordingPlugin {
+        handled: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    impl Plugin for RecordingPlugin {
+        fn id(&self) -> &'static str {
+            "recording"
+        }
+        fn name(&self) -> &'static str {
+            "Recording Plugin"
+        }
+        fn category(&self) -> &'static str {
+            "Test"
+        }
+        fn capabilities(&self) -> Vec<CapabilityMetadata> {
+            ["Mute", "MediaNext"]
+                .into_iter()
+                .map(|id| CapabilityMetadata {
+                    id,
+                    name: id,
+                    description: "",
+                    plugin_id: "recording",
+                    supports_button: true,
+                    supports_encoder: false,
+                    supports_encoder_press: true,
+                    parameters: vec![],
+                })
+                .collect()
+        }
+        fn handle_event(
+            &self,
+            _: &LogicalEvent,
+            binding: &Binding,
+            _: &Arc<Mutex<SystemState>>,
+        ) -> ActionOutcome {
+            let capability_type = get_capability_type(&binding.capability);
+            self.handled.lock().unwrap().push(capability_type);
+            if capability_type == "Mute" {
+                ActionOutcome::Error("mute failed".to_string())
+            } else {
+                ActionOutcome::Ok
+            }
+        }
+        fn owns_capability(&self, _: &str) -> bool {
+            false
+        }
+        fn state(&self, _: &Binding, _: &SystemState) -> Option<String> {
+            None
+        }
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+
+    #[test]
+    fn macro_runs_steps_in_order() {
+        let handled = Arc::new(Mutex::new(Vec::new()));
+        let registry = PluginRegistry::new();
+        registry.register(
+            Box::new(RecordingPlugin {
+                handled: handled.clone(),
+            }),
+            None,
+        );
+
+        let step = |capability, delay_ms| MacroStep {
+            capability,
+            delay_ms,
+        };
+        let steps = vec![
+            step(Capability::MediaNext, 0),
+            step(Capability::Mute, 5),
+            step(Capability::MediaStop, 0),
+            step(Capability::MediaNext, 0),
+        ];
+        let binding = Binding::new(InputRef::Button { index: 0 }, Capability::Macro { steps });
+        let state = Arc::new(Mutex::new(SystemState::default()));
+        let press = |pressed| LogicalEvent::Button(ButtonEvent { index: 0, pressed });
+
+        // Unowned and failed steps are skipped without stopping the macro,
+        // which reports the failure
+        assert_eq!(
+            registry.handle_event(&press(true), &binding, &state),
+            ActionOutcome::Error("mute failed".to_string())
+        );
+        assert_eq!(
+            *handled.lock().unwrap(),
+            vec!["MediaNext", "Mute", "MediaNext"]
+        );
+
+        assert!(!registry
+            .handle_event(&press(false), &binding, &state)
+            .handled());
+        assert_eq!(handled.lock().unwrap().len(), 3);
+    }
 }
@@ -2,6 +2,58 @@
 
 use serde::{Deserialize, Serialize};
 
+/// What came of a plugin handling an event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionOutcome {
+    /// The plugin doesn't act on this event
+    Ignored,
+    /// The action is done
+    Ok,
+    /// The action failed; the message is shown to the user
+    Error(String),
+    /// The action continues in the background, which reports its result
+    /// with `report_action_result` when done
+    Pending,
+}
+
+impl ActionOutcome {
+    /// Whether the plugin acted on the event
+    pub fn handled(&self) -> bool {
+        *self != ActionOutcome::Ignored
+    }
+}
+
+/// A binding's current value (e.g., a volume level), drawn as a gauge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BindingValue {
+    /// Current value, between `min` and `max`
+    pub value: f32,
+    pub min: f32,
+    pub max: f32,
+    /// Readout shown with the gauge (e.g., "42%")
+    pub display: String,
+}
+
+impl BindingValue {
+    /// A 0.0-1.0 level, shown as a percentage
+    pub fn level(level: f32) -> Self {
+        Self {
+            value: level,
+            min: 0.0,
+            max: 1.0,
+            display: format!("{}%", crate::label_template::percent(level)),
+        }
+    }
+
+    /// How far the value is along its range, from 0.0 to 1.0
+    pub fn fraction(&self) -> f32 {
+        if self.max <= self.min {
+            return 0.0;
+        }
+        ((self.value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+    }
+}
+
 /// Metadata describing a capability provided by a plugin.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CapabilityMetadata {
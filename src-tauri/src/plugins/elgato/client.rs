@@ -76,8 +76,8 @@ where
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeyLightState {
     pub on: bool,
-    pub brightness: u8,     // 0-100
-    pub temperature: u16,   // 143-344 (2900K-7000K)
+    pub brightness: u8,   // 0-100
+    pub temperature: u16, // 143-344 (2900K-7000K)
 }
 
 /// Response from the Key Light API
@@ -178,7 +178,11 @@ pub fn toggle(ip: &str, port: u16) -> Result<bool> {
 /// Turn the Key Light on
 pub fn turn_on(ip: &str, port: u16) -> Result<()> {
     let current = get_state(ip, port)?;
-    let brightness = if current.brightness == 0 { 100 } else { current.brightness };
+    let brightness = if current.brightness == 0 {
+        100
+    } else {
+        current.brightness
+    };
     set_state(ip, port, true, brightness)
 }
 
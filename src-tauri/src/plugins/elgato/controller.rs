@@ -37,7 +37,8 @@ pub struct KeyLightController {
 impl KeyLightController {
     /// Create a new controller and start the background worker thread
     pub fn new() -> Self {
-        let pending: Arc<Mutex<HashMap<String, PendingAdjustment>>> = Arc::new(Mutex::new(HashMap::new()));
+        let pending: Arc<Mutex<HashMap<String, PendingAdjustment>>> =
+            Arc::new(Mutex::new(HashMap::new()));
         let has_pending = Arc::new(Mutex::new(false));
 
         // Start background worker
@@ -47,7 +48,10 @@ impl KeyLightController {
             worker_loop(pending_clone, has_pending_clone);
         });
 
-        Self { pending, has_pending }
+        Self {
+            pending,
+            has_pending,
+        }
     }
 
     /// Queue a brightness adjustment (will be debounced and sent in batch)
@@ -55,12 +59,14 @@ impl KeyLightController {
         let key = format!("{}:{}", ip, port);
 
         if let Ok(mut pending) = self.pending.lock() {
-            let entry = pending.entry(key.clone()).or_insert_with(|| PendingAdjustment {
-                delta: 0,
-                first_delta_at: Instant::now(),
-                cached_brightness: None,
-                cached_on: None,
-            });
+            let entry = pending
+                .entry(key.clone())
+                .or_insert_with(|| PendingAdjustment {
+                    delta: 0,
+                    first_delta_at: Instant::now(),
+                    cached_brightness: None,
+                    cached_on: None,
+                });
 
             entry.delta += delta;
 
@@ -86,18 +92,20 @@ impl KeyLightController {
                 entry.cached_on = Some(state.on);
             } else {
                 // Create an entry just for caching
-                pending.insert(key, PendingAdjustment {
-                    delta: 0,
-                    first_delta_at: Instant::now(),
-                    cached_brightness: Some(state.brightness),
-                    cached_on: Some(state.on),
-                });
+                pending.insert(
+                    key,
+                    PendingAdjustment {
+                        delta: 0,
+                        first_delta_at: Instant::now(),
+                        cached_brightness: Some(state.brightness),
+                        cached_on: Some(state.on),
+                    },
+                );
             }
         }
     }
 
     /// Get cached brightness for a light (if available)
-    #[allow(dead_code)]
     pub fn get_cached_brightness(&self, ip: &str, port: u16) -> Option<u8> {
         let key = format!("{}:{}", ip, port);
         self.pending.lock().ok()?.get(&key)?.cached_brightness
@@ -139,13 +147,10 @@ fn worker_loop(
             let mut ready = Vec::new();
 
             for (key, adj) in pending_lock.iter_mut() {
-                if adj.delta != 0 && now.duration_since(adj.first_delta_at) >= Duration::from_millis(DEBOUNCE_MS) {
-                    ready.push((
-                        key.clone(),
-                        adj.delta,
-                        adj.cached_brightness,
-                        adj.cached_on,
-                    ));
+                if adj.delta != 0
+                    && now.duration_since(adj.first_delta_at) >= Duration::from_millis(DEBOUNCE_MS)
+                {
+                    ready.push((key.clone(), adj.delta, adj.cached_brightness, adj.cached_on));
                     // Reset delta but keep cache
                     adj.delta = 0;
                 }
@@ -6,12 +6,18 @@ use crate::binding::Binding;
 use crate::capability::{Capability, KeyLightAction, KEY_LIGHT_BRIGHTNESS_STEP};
 use crate::impl_owns_capability;
 use crate::input_processor::LogicalEvent;
-use crate::plugin::{CapabilityMetadata, ParameterDef, ParameterType, Plugin, PluginConfig};
+use crate::plugin::{
+    report_action_result, ActionOutcome, BindingValue, CapabilityMetadata, ParameterDef,
+    ParameterType, Plugin, PluginConfig,
+};
 use crate::state_manager::SystemState;
 use crate::streamdeck::request_image_sync;
 use std::any::Any;
 use std::sync::{Arc, Mutex, OnceLock};
 
+/// Port a light named without one listens on
+const DEFAULT_PORT: u16 = 9123;
+
 /// Global debounced Key Light controller
 static KEY_LIGHT_CONTROLLER: OnceLock<KeyLightController> = OnceLock::new();
 
@@ -20,6 +26,19 @@ fn get_key_light_controller() -> &'static KeyLightController {
     KEY_LIGHT_CONTROLLER.get_or_init(KeyLightController::new)
 }
 
+/// Brightness of a light: the controller's cached value (last set or read),
+/// otherwise the polled state.
+fn light_brightness(ip: &str, port: u16, system_state: &SystemState) -> Option<u8> {
+    get_key_light_controller()
+        .get_cached_brightness(ip, port)
+        .or_else(|| {
+            system_state
+                .key_lights
+                .get(&format!("{}:{}", ip, port))
+                .map(|s| s.brightness)
+        })
+}
+
 /// Elgato Key Light plugin
 pub struct ElgatoPlugin;
 
@@ -71,43 +90,85 @@ impl Plugin for ElgatoPlugin {
         event: &LogicalEvent,
         binding: &Binding,
         system_state: &Arc<Mutex<SystemState>>,
-    ) -> bool {
+    ) -> ActionOutcome {
         match (&binding.capability, event) {
             // Button press -> toggle
             (Capability::ElgatoKeyLight { ip, port, .. }, LogicalEvent::Button(e)) if e.pressed => {
-                handle_key_light_button(ip, *port, &KeyLightAction::Toggle, system_state);
-                true
+                handle_key_light_button(binding, ip, *port, &KeyLightAction::Toggle, system_state);
+                ActionOutcome::Pending
             }
 
             // Encoder press -> toggle
-            (Capability::ElgatoKeyLight { ip, port, .. }, LogicalEvent::EncoderPress(e)) if e.pressed => {
-                handle_key_light_button(ip, *port, &KeyLightAction::Toggle, system_state);
-                true
+            (Capability::ElgatoKeyLight { ip, port, .. }, LogicalEvent::EncoderPress(e))
+                if e.pressed =>
+            {
+                handle_key_light_button(binding, ip, *port, &KeyLightAction::Toggle, system_state);
+                ActionOutcome::Pending
             }
 
             // Encoder rotation -> brightness
             (Capability::ElgatoKeyLight { ip, port, .. }, LogicalEvent::Encoder(e)) => {
                 handle_key_light_brightness(ip, *port, e.delta);
-                true
+                ActionOutcome::Ok
             }
 
-            _ => false,
+            _ => ActionOutcome::Ignored,
         }
     }
 
     impl_owns_capability!("ElgatoKeyLight");
 
-    fn is_active(&self, binding: &Binding, system_state: &SystemState) -> bool {
-        if let Capability::ElgatoKeyLight { ip, port, .. } = &binding.capability {
-            // Check if key light is on
-            system_state
-                .key_lights
-                .get(&format!("{}:{}", ip, port))
-                .map(|s| s.on)
-                .unwrap_or(false)
-        } else {
-            false
+    /// "on" or "off"; None until the light has been reached.
+    fn state(&self, binding: &Binding, system_state: &SystemState) -> Option<String> {
+        let Capability::ElgatoKeyLight { ip, port, .. } = &binding.capability else {
+            return None;
+        };
+        let light = system_state.key_lights.get(&format!("{}:{}", ip, port))?;
+        Some(if light.on { "on" } else { "off" }.to_string())
+    }
+
+    /// `{keylight.brightness}` is the binding's light's brightness in percent,
+    /// or that of the light named in the argument (`ip` or `ip:port`).
+    fn label_value(
+        &self,
+        binding: &Binding,
+        key: &str,
+        argument: Option<&str>,
+        system_state: &SystemState,
+    ) -> Option<String> {
+        if key != "keylight.brightness" {
+            return None;
         }
+
+        let brightness = match (argument, &binding.capability) {
+            (Some(light), _) => {
+                let (ip, port) = match light.rsplit_once(':') {
+                    Some((ip, port)) => (ip, port.parse().ok()?),
+                    None => (light, DEFAULT_PORT),
+                };
+                light_brightness(ip, port, system_state)
+            }
+            (None, Capability::ElgatoKeyLight { ip, port, .. }) => {
+                light_brightness(ip, *port, system_state)
+            }
+            // Other keys have no light of their own
+            (None, _) => None,
+        };
+        brightness.map(|brightness| brightness.to_string())
+    }
+
+    /// The binding's light's brightness, 0-100.
+    fn value(&self, binding: &Binding, system_state: &SystemState) -> Option<BindingValue> {
+        let Capability::ElgatoKeyLight { ip, port, .. } = &binding.capability else {
+            return None;
+        };
+        let brightness = light_brightness(ip, *port, system_state)?;
+        Some(BindingValue {
+            value: brightness as f32,
+            min: 0.0,
+            max: 100.0,
+            display: format!("{brightness}%"),
+        })
     }
 
     fn initialize(&mut self, _config: &PluginConfig) -> anyhow::Result<()> {
@@ -143,8 +204,15 @@ impl Plugin for ElgatoPlugin {
     }
 }
 
-fn handle_key_light_button(ip: &str, port: u16, action: &KeyLightAction, system_state: &Arc<Mutex<SystemState>>) {
+fn handle_key_light_button(
+    binding: &Binding,
+    ip: &str,
+    port: u16,
+    action: &KeyLightAction,
+    system_state: &Arc<Mutex<SystemState>>,
+) {
     // Spawn background thread to avoid blocking the event loop
+    let binding = binding.clone();
     let ip = ip.to_string();
     let action = action.clone();
     let state = Arc::clone(system_state);
@@ -157,9 +225,10 @@ fn handle_key_light_button(ip: &str, port: u16, action: &KeyLightAction, system_
             KeyLightAction::SetBrightness => Ok(()), // Handled by encoder
         };
 
-        if let Err(e) = result {
-            eprintln!("Key Light error: {e}");
-        }
+        report_action_result(
+            &binding,
+            result.map_err(|e| format!("Key Light error: {e}")),
+        );
 
         // Update key light state and trigger image sync
         match client::get_state(&ip, port) {
@@ -239,7 +308,10 @@ mod tests {
     #[test]
     fn elgato_plugin_metadata_description() {
         let plugin = ElgatoPlugin::new();
-        assert_eq!(plugin.description(), "Control Elgato Key Lights over your network");
+        assert_eq!(
+            plugin.description(),
+            "Control Elgato Key Lights over your network"
+        );
     }
 
     #[test]
@@ -254,6 +326,28 @@ mod tests {
         assert!(!plugin.is_core());
     }
 
+    #[test]
+    fn brightness_placeholder_needs_a_named_light() {
+        let plugin = ElgatoPlugin::new();
+        let mut state = SystemState::default();
+        let light = crate::plugins::elgato::client::KeyLightState {
+            on: true,
+            brightness: 40,
+            temperature: 200,
+        };
+        state.key_lights.insert("10.0.0.7:9123".to_string(), light);
+        let other = Binding::new(
+            crate::binding::InputRef::Button { index: 0 },
+            Capability::MediaNext,
+        );
+
+        let value = |argument| plugin.label_value(&other, "keylight.brightness", argument, &state);
+        assert_eq!(value(None), None);
+        assert_eq!(value(Some("10.0.0.7")).as_deref(), Some("40"));
+        assert_eq!(value(Some("10.0.0.7:9123")).as_deref(), Some("40"));
+        assert_eq!(value(Some("10.0.0.8")), None);
+    }
+
     #[test]
     fn elgato_plugin_documentation_not_empty() {
         let plugin = ElgatoPlugin::new();
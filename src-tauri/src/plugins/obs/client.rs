@@ -221,21 +221,19 @@ fn create_connection(conn: &OBSConnection) -> Result<OBSWebSocket> {
     let url = format!("ws://{}:{}", conn.host, conn.port);
 
     // Connect to OBS WebSocket
-    let (mut socket, _response) = connect(&url)
-        .context("Failed to connect to OBS WebSocket")?;
+    let (mut socket, _response) = connect(&url).context("Failed to connect to OBS WebSocket")?;
 
     // Step 1: Receive Hello
-    let hello_msg = socket.read()
-        .context("Failed to read Hello from OBS")?;
-    let hello: OBSMessage = serde_json::from_str(&hello_msg.to_text()?)
-        .context("Failed to parse Hello message")?;
+    let hello_msg = socket.read().context("Failed to read Hello from OBS")?;
+    let hello: OBSMessage =
+        serde_json::from_str(&hello_msg.to_text()?).context("Failed to parse Hello message")?;
 
     if hello.op != op::HELLO {
         anyhow::bail!("Expected Hello message, got op {}", hello.op);
     }
 
-    let hello_data: Hello = serde_json::from_value(hello.d)
-        .context("Failed to parse Hello data")?;
+    let hello_data: Hello =
+        serde_json::from_value(hello.d).context("Failed to parse Hello data")?;
 
     // Step 2: Send Identify (with optional auth)
     let identify = if let Some(auth) = hello_data.authentication {
@@ -260,28 +258,37 @@ fn create_connection(conn: &OBSConnection) -> Result<OBSWebSocket> {
         })
     };
 
-    socket.send(Message::Text(identify.to_string()))
+    socket
+        .send(Message::Text(identify.to_string()))
         .context("Failed to send Identify")?;
 
     // Step 3: Receive Identified
-    let identified_msg = socket.read()
+    let identified_msg = socket
+        .read()
         .context("Failed to read Identified from OBS")?;
     let identified: OBSMessage = serde_json::from_str(&identified_msg.to_text()?)
         .context("Failed to parse Identified message")?;
 
     if identified.op != op::IDENTIFIED {
-        anyhow::bail!("Authentication failed or unexpected message (op {})", identified.op);
+        anyhow::bail!(
+            "Authentication failed or unexpected message (op {})",
+            identified.op
+        );
     }
 
     // Parse to verify it's valid
-    let _: Identified = serde_json::from_value(identified.d)
-        .context("Failed to parse Identified data")?;
+    let _: Identified =
+        serde_json::from_value(identified.d).context("Failed to parse Identified data")?;
 
     Ok(socket)
 }
 
 /// Send a request using a socket, returning the response
-fn send_request_on_socket(socket: &mut OBSWebSocket, request_type: &str, request_data: Option<&Value>) -> Result<Value> {
+fn send_request_on_socket(
+    socket: &mut OBSWebSocket,
+    request_type: &str,
+    request_data: Option<&Value>,
+) -> Result<Value> {
     let request_id = next_request_id();
     let request = if let Some(data) = request_data {
         json!({
@@ -302,12 +309,12 @@ fn send_request_on_socket(socket: &mut OBSWebSocket, request_type: &str, request
         })
     };
 
-    socket.send(Message::Text(request.to_string()))
+    socket
+        .send(Message::Text(request.to_string()))
         .context("Failed to send request")?;
 
     // Receive Response
-    let response_msg = socket.read()
-        .context("Failed to read response from OBS")?;
+    let response_msg = socket.read().context("Failed to read response from OBS")?;
     let response: OBSMessage = serde_json::from_str(&response_msg.to_text()?)
         .context("Failed to parse response message")?;
 
@@ -320,7 +327,11 @@ fn send_request_on_socket(socket: &mut OBSWebSocket, request_type: &str, request
 
 /// Execute a single request to OBS and return the response.
 /// Uses connection pooling to reuse authenticated WebSocket connections.
-pub fn send_request(conn: &OBSConnection, request_type: &str, request_data: Option<Value>) -> Result<Value> {
+pub fn send_request(
+    conn: &OBSConnection,
+    request_type: &str,
+    request_data: Option<Value>,
+) -> Result<Value> {
     validate_ip(&conn.host)?;
 
     let pool_key = conn.key();
@@ -364,11 +375,13 @@ struct RequestStatus {
 /// Parse response and check for success
 fn check_response(response: &Value) -> Result<()> {
     if let Some(status) = response.get("requestStatus") {
-        let status: RequestStatus = serde_json::from_value(status.clone())
-            .context("Failed to parse request status")?;
+        let status: RequestStatus =
+            serde_json::from_value(status.clone()).context("Failed to parse request status")?;
 
         if !status.result {
-            let msg = status.comment.unwrap_or_else(|| format!("Error code {}", status.code));
+            let msg = status
+                .comment
+                .unwrap_or_else(|| format!("Error code {}", status.code));
             anyhow::bail!("OBS request failed: {}", msg);
         }
     }
@@ -381,9 +394,13 @@ fn check_response(response: &Value) -> Result<()> {
 
 /// Set the current program scene
 pub fn set_current_scene(conn: &OBSConnection, scene_name: &str) -> Result<()> {
-    let response = send_request(conn, "SetCurrentProgramScene", Some(json!({
-        "sceneName": scene_name
-    })))?;
+    let response = send_request(
+        conn,
+        "SetCurrentProgramScene",
+        Some(json!({
+            "sceneName": scene_name
+        })),
+    )?;
     check_response(&response)
 }
 
@@ -457,12 +474,19 @@ pub fn get_record_status(conn: &OBSConnection) -> Result<RecordStatus> {
     let response = send_request(conn, "GetRecordStatus", None)?;
     check_response(&response)?;
 
-    let data = response.get("responseData")
+    let data = response
+        .get("responseData")
         .context("Missing responseData")?;
 
     Ok(RecordStatus {
-        active: data.get("outputActive").and_then(|v| v.as_bool()).unwrap_or(false),
-        paused: data.get("outputPaused").and_then(|v| v.as_bool()).unwrap_or(false),
+        active: data
+            .get("outputActive")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        paused: data
+            .get("outputPaused")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
     })
 }
 
@@ -496,12 +520,20 @@ pub fn toggle_record_pause(conn: &OBSConnection) -> Result<()> {
 
 /// Get source visibility in a scene
 #[allow(dead_code)]
-pub fn get_source_visibility(conn: &OBSConnection, scene_name: &str, source_name: &str) -> Result<bool> {
+pub fn get_source_visibility(
+    conn: &OBSConnection,
+    scene_name: &str,
+    source_name: &str,
+) -> Result<bool> {
     // First, get the scene item ID
-    let response = send_request(conn, "GetSceneItemId", Some(json!({
-        "sceneName": scene_name,
-        "sourceName": source_name
-    })))?;
+    let response = send_request(
+        conn,
+        "GetSceneItemId",
+        Some(json!({
+            "sceneName": scene_name,
+            "sourceName": source_name
+        })),
+    )?;
     check_response(&response)?;
 
     let scene_item_id = response
@@ -511,10 +543,14 @@ pub fn get_source_visibility(conn: &OBSConnection, scene_name: &str, source_name
         .context("Failed to get scene item ID")?;
 
     // Then get the enabled state
-    let response = send_request(conn, "GetSceneItemEnabled", Some(json!({
-        "sceneName": scene_name,
-        "sceneItemId": scene_item_id
-    })))?;
+    let response = send_request(
+        conn,
+        "GetSceneItemEnabled",
+        Some(json!({
+            "sceneName": scene_name,
+            "sceneItemId": scene_item_id
+        })),
+    )?;
     check_response(&response)?;
 
     response
@@ -525,12 +561,20 @@ pub fn get_source_visibility(conn: &OBSConnection, scene_name: &str, source_name
 }
 
 /// Toggle source visibility in a scene
-pub fn toggle_source_visibility(conn: &OBSConnection, scene_name: &str, source_name: &str) -> Result<bool> {
+pub fn toggle_source_visibility(
+    conn: &OBSConnection,
+    scene_name: &str,
+    source_name: &str,
+) -> Result<bool> {
     // First, get the scene item ID
-    let response = send_request(conn, "GetSceneItemId", Some(json!({
-        "sceneName": scene_name,
-        "sourceName": source_name
-    })))?;
+    let response = send_request(
+        conn,
+        "GetSceneItemId",
+        Some(json!({
+            "sceneName": scene_name,
+            "sourceName": source_name
+        })),
+    )?;
     check_response(&response)?;
 
     let scene_item_id = response
@@ -540,10 +584,14 @@ pub fn toggle_source_visibility(conn: &OBSConnection, scene_name: &str, source_n
         .context("Failed to get scene item ID")?;
 
     // Get current state
-    let response = send_request(conn, "GetSceneItemEnabled", Some(json!({
-        "sceneName": scene_name,
-        "sceneItemId": scene_item_id
-    })))?;
+    let response = send_request(
+        conn,
+        "GetSceneItemEnabled",
+        Some(json!({
+            "sceneName": scene_name,
+            "sceneItemId": scene_item_id
+        })),
+    )?;
     check_response(&response)?;
 
     let current = response
@@ -554,11 +602,15 @@ pub fn toggle_source_visibility(conn: &OBSConnection, scene_name: &str, source_n
 
     // Toggle it
     let new_state = !current;
-    let response = send_request(conn, "SetSceneItemEnabled", Some(json!({
-        "sceneName": scene_name,
-        "sceneItemId": scene_item_id,
-        "sceneItemEnabled": new_state
-    })))?;
+    let response = send_request(
+        conn,
+        "SetSceneItemEnabled",
+        Some(json!({
+            "sceneName": scene_name,
+            "sceneItemId": scene_item_id,
+            "sceneItemEnabled": new_state
+        })),
+    )?;
     check_response(&response)?;
 
     Ok(new_state)
@@ -571,9 +623,13 @@ pub fn toggle_source_visibility(conn: &OBSConnection, scene_name: &str, source_n
 /// Get input mute state
 #[allow(dead_code)]
 pub fn get_input_mute(conn: &OBSConnection, input_name: &str) -> Result<bool> {
-    let response = send_request(conn, "GetInputMute", Some(json!({
-        "inputName": input_name
-    })))?;
+    let response = send_request(
+        conn,
+        "GetInputMute",
+        Some(json!({
+            "inputName": input_name
+        })),
+    )?;
     check_response(&response)?;
 
     response
@@ -585,9 +641,13 @@ pub fn get_input_mute(conn: &OBSConnection, input_name: &str) -> Result<bool> {
 
 /// Toggle input mute
 pub fn toggle_input_mute(conn: &OBSConnection, input_name: &str) -> Result<bool> {
-    let response = send_request(conn, "ToggleInputMute", Some(json!({
-        "inputName": input_name
-    })))?;
+    let response = send_request(
+        conn,
+        "ToggleInputMute",
+        Some(json!({
+            "inputName": input_name
+        })),
+    )?;
     check_response(&response)?;
 
     response
@@ -599,9 +659,13 @@ pub fn toggle_input_mute(conn: &OBSConnection, input_name: &str) -> Result<bool>
 
 /// Get input volume (returns value in dB, OBS uses mul internally)
 pub fn get_input_volume(conn: &OBSConnection, input_name: &str) -> Result<f32> {
-    let response = send_request(conn, "GetInputVolume", Some(json!({
-        "inputName": input_name
-    })))?;
+    let response = send_request(
+        conn,
+        "GetInputVolume",
+        Some(json!({
+            "inputName": input_name
+        })),
+    )?;
     check_response(&response)?;
 
     // OBS returns inputVolumeMul (0.0-1.0) and inputVolumeDb
@@ -616,10 +680,14 @@ pub fn get_input_volume(conn: &OBSConnection, input_name: &str) -> Result<f32> {
 /// Set input volume (0.0-1.0 multiplier)
 pub fn set_input_volume(conn: &OBSConnection, input_name: &str, volume_mul: f32) -> Result<()> {
     let volume = volume_mul.clamp(0.0, 1.0);
-    let response = send_request(conn, "SetInputVolume", Some(json!({
-        "inputName": input_name,
-        "inputVolumeMul": volume
-    })))?;
+    let response = send_request(
+        conn,
+        "SetInputVolume",
+        Some(json!({
+            "inputName": input_name,
+            "inputVolumeMul": volume
+        })),
+    )?;
     check_response(&response)
 }
 
@@ -644,9 +712,13 @@ pub fn toggle_studio_mode(conn: &OBSConnection) -> Result<bool> {
     let current = get_studio_mode(conn)?;
     let new_state = !current;
 
-    let response = send_request(conn, "SetStudioModeEnabled", Some(json!({
-        "studioModeEnabled": new_state
-    })))?;
+    let response = send_request(
+        conn,
+        "SetStudioModeEnabled",
+        Some(json!({
+            "studioModeEnabled": new_state
+        })),
+    )?;
     check_response(&response)?;
 
     Ok(new_state)
@@ -750,7 +822,9 @@ mod tests {
 
         // Verify the result is a valid base64 string of expected length (SHA256 = 32 bytes = 44 chars base64)
         assert_eq!(auth.len(), 44);
-        assert!(base64::engine::general_purpose::STANDARD.decode(&auth).is_ok());
+        assert!(base64::engine::general_purpose::STANDARD
+            .decode(&auth)
+            .is_ok());
 
         // Verify deterministic output (same inputs = same output)
         let auth2 = generate_auth_string(password, challenge, salt);
@@ -4,7 +4,6 @@
 //! preventing lag when turning the encoder quickly.
 
 use super::client::{self, OBSConnection};
-use crate::state_manager::{OBSState, SystemState};
 use crate::streamdeck::request_image_sync;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
@@ -14,6 +13,9 @@ use std::time::{Duration, Instant};
 /// Debounce window - accumulate deltas for this long before sending
 const DEBOUNCE_MS: u64 = 80;
 
+/// How long to wait before retrying a failed volume read
+const FETCH_RETRY: Duration = Duration::from_secs(5);
+
 /// Pending volume adjustment for a specific input
 #[derive(Debug)]
 struct PendingAdjustment {
@@ -35,26 +37,29 @@ pub struct OBSAudioController {
     pending: Arc<Mutex<HashMap<String, PendingAdjustment>>>,
     /// Flag to signal the worker thread to process pending adjustments
     has_pending: Arc<Mutex<bool>>,
-    /// Reference to system state for updating OBS state
-    #[allow(dead_code)]
-    system_state: Arc<Mutex<SystemState>>,
+    /// When each uncached input's volume was last read from OBS
+    fetches: Mutex<HashMap<String, Instant>>,
 }
 
 impl OBSAudioController {
     /// Create a new controller and start the background worker thread
-    pub fn new(system_state: Arc<Mutex<SystemState>>) -> Self {
-        let pending: Arc<Mutex<HashMap<String, PendingAdjustment>>> = Arc::new(Mutex::new(HashMap::new()));
+    pub fn new() -> Self {
+        let pending: Arc<Mutex<HashMap<String, PendingAdjustment>>> =
+            Arc::new(Mutex::new(HashMap::new()));
         let has_pending = Arc::new(Mutex::new(false));
 
         // Start background worker
         let pending_clone = pending.clone();
         let has_pending_clone = has_pending.clone();
-        let state_clone = system_state.clone();
         thread::spawn(move || {
-            worker_loop(pending_clone, has_pending_clone, state_clone);
+            worker_loop(pending_clone, has_pending_clone);
         });
 
-        Self { pending, has_pending, system_state }
+        Self {
+            pending,
+            has_pending,
+            fetches: Mutex::new(HashMap::new()),
+        }
     }
 
     /// Queue a volume adjustment (will be debounced and sent in batch)
@@ -62,13 +67,15 @@ impl OBSAudioController {
         let key = format!("{}:{}:{}", conn.host, conn.port, input_name);
 
         if let Ok(mut pending) = self.pending.lock() {
-            let entry = pending.entry(key.clone()).or_insert_with(|| PendingAdjustment {
-                conn: conn.clone(),
-                input_name: input_name.to_string(),
-                delta: 0.0,
-                first_delta_at: Instant::now(),
-                cached_volume: None,
-            });
+            let entry = pending
+                .entry(key.clone())
+                .or_insert_with(|| PendingAdjustment {
+                    conn: conn.clone(),
+                    input_name: input_name.to_string(),
+                    delta: 0.0,
+                    first_delta_at: Instant::now(),
+                    cached_volume: None,
+                });
 
             entry.delta += delta;
 
@@ -87,41 +94,77 @@ impl OBSAudioController {
     /// Update cached volume for an input (call after mute toggle)
     #[allow(dead_code)]
     pub fn update_cached_volume(&self, conn: &OBSConnection, input_name: &str, volume: f32) {
-        let key = format!("{}:{}:{}", conn.host, conn.port, input_name);
-
-        if let Ok(mut pending) = self.pending.lock() {
-            if let Some(entry) = pending.get_mut(&key) {
-                entry.cached_volume = Some(volume);
-            } else {
-                // Create an entry just for caching
-                pending.insert(key, PendingAdjustment {
-                    conn: conn.clone(),
-                    input_name: input_name.to_string(),
-                    delta: 0.0,
-                    first_delta_at: Instant::now(),
-                    cached_volume: Some(volume),
-                });
-            }
-        }
+        cache_volume(&self.pending, conn, input_name, volume);
     }
 
     /// Get cached volume for an input (if available)
-    #[allow(dead_code)]
     pub fn get_cached_volume(&self, conn: &OBSConnection, input_name: &str) -> Option<f32> {
         let key = format!("{}:{}:{}", conn.host, conn.port, input_name);
         self.pending.lock().ok()?.get(&key)?.cached_volume
     }
 
-    /// Update OBS state after an action
-    #[allow(dead_code)]
-    pub fn update_obs_state<F>(&self, conn: &OBSConnection, update_fn: F)
-    where
-        F: FnOnce(&mut OBSState),
-    {
-        if let Ok(mut state) = self.system_state.lock() {
-            let key = conn.key();
-            let obs_state = state.obs_states.entry(key).or_insert_with(OBSState::default);
-            update_fn(obs_state);
+    /// Get cached volume for an input. If nothing is cached yet, read it from
+    /// OBS in the background (at most once per `FETCH_RETRY`) and sync images
+    /// when it arrives.
+    pub fn get_or_fetch_volume(&self, conn: &OBSConnection, input_name: &str) -> Option<f32> {
+        if let Some(volume) = self.get_cached_volume(conn, input_name) {
+            return Some(volume);
+        }
+
+        let key = format!("{}:{}:{}", conn.host, conn.port, input_name);
+        {
+            let mut fetches = self.fetches.lock().ok()?;
+            if fetches
+                .get(&key)
+                .is_some_and(|at| at.elapsed() < FETCH_RETRY)
+            {
+                return None;
+            }
+            fetches.insert(key, Instant::now());
+        }
+
+        let pending = self.pending.clone();
+        let conn = conn.clone();
+        let input_name = input_name.to_string();
+        thread::spawn(move || {
+            if let Ok(volume) = client::get_input_volume(&conn, &input_name) {
+                cache_volume(&pending, &conn, &input_name, volume);
+                request_image_sync();
+            }
+        });
+        None
+    }
+}
+
+impl Default for OBSAudioController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Store an input's volume, creating an entry just for caching if needed
+fn cache_volume(
+    pending: &Mutex<HashMap<String, PendingAdjustment>>,
+    conn: &OBSConnection,
+    input_name: &str,
+    volume: f32,
+) {
+    let key = format!("{}:{}:{}", conn.host, conn.port, input_name);
+
+    if let Ok(mut pending) = pending.lock() {
+        if let Some(entry) = pending.get_mut(&key) {
+            entry.cached_volume = Some(volume);
+        } else {
+            pending.insert(
+                key,
+                PendingAdjustment {
+                    conn: conn.clone(),
+                    input_name: input_name.to_string(),
+                    delta: 0.0,
+                    first_delta_at: Instant::now(),
+                    cached_volume: Some(volume),
+                },
+            );
         }
     }
 }
@@ -130,7 +173,6 @@ impl OBSAudioController {
 fn worker_loop(
     pending: Arc<Mutex<HashMap<String, PendingAdjustment>>>,
     has_pending: Arc<Mutex<bool>>,
-    system_state: Arc<Mutex<SystemState>>,
 ) {
     loop {
         thread::sleep(Duration::from_millis(20)); // Check every 20ms
@@ -196,13 +238,6 @@ fn worker_loop(
                         adj.cached_volume = Some(new_volume);
                     }
                 }
-
-                // Update system state
-                if let Ok(mut state) = system_state.lock() {
-                    let obs_key = conn.key();
-                    // Ensure OBSState exists but don't modify mute state here
-                    state.obs_states.entry(obs_key).or_insert_with(OBSState::default);
-                }
             }
         }
 
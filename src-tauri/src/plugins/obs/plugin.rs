@@ -3,12 +3,14 @@
 use super::client::{self, OBSConnection};
 use super::controller::OBSAudioController;
 use crate::binding::Binding;
-use crate::capability::{
-    Capability, OBSRecordAction, OBSReplayAction, OBSStreamAction,
-};
+use crate::capability::{Capability, OBSRecordAction, OBSReplayAction, OBSStreamAction};
 use crate::impl_owns_capability;
 use crate::input_processor::LogicalEvent;
-use crate::plugin::{CapabilityMetadata, ParameterDef, ParameterType, Plugin, PluginConfig};
+use crate::label_template;
+use crate::plugin::{
+    active_state_key, report_action_result, ActionOutcome, BindingValue, CapabilityMetadata,
+    ParameterDef, ParameterType, Plugin, PluginConfig,
+};
 use crate::state_manager::{OBSState, SystemState};
 use crate::streamdeck::request_image_sync;
 use std::any::Any;
@@ -29,8 +31,171 @@ fn is_press_event(event: &LogicalEvent) -> bool {
 static OBS_AUDIO_CONTROLLER: OnceLock<OBSAudioController> = OnceLock::new();
 
 /// Get or initialize the OBS audio controller
-fn get_audio_controller(system_state: &Arc<Mutex<SystemState>>) -> &'static OBSAudioController {
-    OBS_AUDIO_CONTROLLER.get_or_init(|| OBSAudioController::new(Arc::clone(system_state)))
+fn get_audio_controller() -> &'static OBSAudioController {
+    OBS_AUDIO_CONTROLLER.get_or_init(OBSAudioController::new)
+}
+
+/// Quietest level a volume gauge shows; silence reads -inf
+const MIN_VOLUME_DB: f32 = -60.0;
+
+/// A volume multiplier (0.0-1.0) as decibels on the mixer's -60 to 0 dB range.
+fn volume_db(volume_mul: f32) -> BindingValue {
+    let db = 20.0 * volume_mul.log10();
+    BindingValue {
+        value: db.max(MIN_VOLUME_DB),
+        min: MIN_VOLUME_DB,
+        max: 0.0,
+        display: if db.is_finite() {
+            format!("{db:.1} dB")
+        } else {
+            "-inf dB".to_string()
+        },
+    }
+}
+
+/// The OBS instance an OBS binding talks to
+fn binding_connection(binding: &Binding) -> Option<OBSConnection> {
+    match &binding.capability {
+        Capability::OBSScene {
+            host,
+            port,
+            password,
+            ..
+        }
+        | Capability::OBSStream {
+            host,
+            port,
+            password,
+            ..
+        }
+        | Capability::OBSRecord {
+            host,
+            port,
+            password,
+            ..
+        }
+        | Capability::OBSSourceVisibility {
+            host,
+            port,
+            password,
+            ..
+        }
+        | Capability::OBSAudio {
+            host,
+            port,
+            password,
+            ..
+        }
+        | Capability::OBSStudioMode {
+            host,
+            port,
+            password,
+        }
+        | Capability::OBSReplayBuffer {
+            host,
+            port,
+            password,
+            ..
+        }
+        | Capability::OBSVirtualCam {
+            host,
+            port,
+            password,
+        }
+        | Capability::OBSTransition {
+            host,
+            port,
+            password,
+        } => Some(OBSConnection::new(host, *port, password.clone())),
+        _ => None,
+    }
+}
+
+/// Whether an on/off OBS binding (scene, source, audio, ...) is on
+fn is_on(binding: &Binding, system_state: &SystemState) -> bool {
+    match &binding.capability {
+        Capability::OBSScene {
+            host, port, scene, ..
+        } => {
+            let key = format!("{}:{}", host, port);
+            system_state
+                .obs_states
+                .get(&key)
+                .map(|s| s.current_scene == *scene)
+                .unwrap_or(false)
+        }
+        Capability::OBSStream { host, port, .. } => {
+            let key = format!("{}:{}", host, port);
+            system_state
+                .obs_states
+                .get(&key)
+                .map(|s| s.streaming)
+                .unwrap_or(false)
+        }
+        Capability::OBSRecord { host, port, .. } => {
+            let key = format!("{}:{}", host, port);
+            system_state
+                .obs_states
+                .get(&key)
+                .map(|s| s.recording)
+                .unwrap_or(false)
+        }
+        Capability::OBSSourceVisibility {
+            host,
+            port,
+            scene,
+            source,
+            ..
+        } => {
+            let key = format!("{}:{}", host, port);
+            let source_key = format!("{}:{}", scene, source);
+            system_state
+                .obs_states
+                .get(&key)
+                .and_then(|s| s.source_visibility.get(&source_key))
+                .copied()
+                .unwrap_or(false)
+        }
+        Capability::OBSAudio {
+            host,
+            port,
+            input_name,
+            ..
+        } => {
+            let key = format!("{}:{}", host, port);
+            system_state
+                .obs_states
+                .get(&key)
+                .and_then(|s| s.muted_inputs.get(input_name))
+                .copied()
+                .unwrap_or(false)
+        }
+        Capability::OBSStudioMode { host, port, .. } => {
+            let key = format!("{}:{}", host, port);
+            system_state
+                .obs_states
+                .get(&key)
+                .map(|s| s.studio_mode)
+                .unwrap_or(false)
+        }
+        Capability::OBSReplayBuffer { host, port, .. } => {
+            let key = format!("{}:{}", host, port);
+            system_state
+                .obs_states
+                .get(&key)
+                .map(|s| s.replay_buffer)
+                .unwrap_or(false)
+        }
+        Capability::OBSVirtualCam { host, port, .. } => {
+            let key = format!("{}:{}", host, port);
+            system_state
+                .obs_states
+                .get(&key)
+                .map(|s| s.virtual_cam)
+                .unwrap_or(false)
+        }
+        _ => false,
+    }
 }
 
 /// OBS Studio plugin
@@ -183,11 +348,7 @@ impl Plugin for OBSPlugin {
                 supports_button: true,
                 supports_encoder: false,
                 supports_encoder_press: true,
-                parameters: vec![
-                    obs_host_param(),
-                    obs_port_param(),
-                    obs_password_param(),
-                ],
+                parameters: vec![obs_host_param(), obs_port_param(), obs_password_param()],
             },
             CapabilityMetadata {
                 id: "OBSReplayBuffer",
@@ -217,11 +378,7 @@ impl Plugin for OBSPlugin {
                 supports_button: true,
                 supports_encoder: false,
                 supports_encoder_press: true,
-                parameters: vec![
-                    obs_host_param(),
-                    obs_port_param(),
-                    obs_password_param(),
-                ],
+                parameters: vec![obs_host_param(), obs_port_param(), obs_password_param()],
             },
             CapabilityMetadata {
                 id: "OBSTransition",
@@ -231,11 +388,7 @@ impl Plugin for OBSPlugin {
                 supports_button: true,
                 supports_encoder: false,
                 supports_encoder_press: true,
-                parameters: vec![
-                    obs_host_param(),
-                    obs_port_param(),
-                    obs_password_param(),
-                ],
+                parameters: vec![obs_host_param(), obs_port_param(), obs_password_param()],
             },
         ]
     }
@@ -245,72 +398,139 @@ impl Plugin for OBSPlugin {
         event: &LogicalEvent,
         binding: &Binding,
         system_state: &Arc<Mutex<SystemState>>,
-    ) -> bool {
+    ) -> ActionOutcome {
         match &binding.capability {
             // OBSScene - press to switch scene
-            Capability::OBSScene { host, port, password, scene } if is_press_event(event) => {
-                handle_scene_switch(host, *port, password.clone(), scene, system_state);
-                true
+            Capability::OBSScene {
+                host,
+                port,
+                password,
+                scene,
+            } if is_press_event(event) => {
+                handle_scene_switch(binding, host, *port, password.clone(), scene, system_state);
+                ActionOutcome::Pending
             }
 
             // OBSStream - press to control streaming
-            Capability::OBSStream { host, port, password, action } if is_press_event(event) => {
-                handle_stream_action(host, *port, password.clone(), action, system_state);
-                true
+            Capability::OBSStream {
+                host,
+                port,
+                password,
+                action,
+            } if is_press_event(event) => {
+                handle_stream_action(binding, host, *port, password.clone(), action, system_state);
+                ActionOutcome::Pending
             }
 
             // OBSRecord - press to control recording
-            Capability::OBSRecord { host, port, password, action } if is_press_event(event) => {
-                handle_record_action(host, *port, password.clone(), action, system_state);
-                true
+            Capability::OBSRecord {
+                host,
+                port,
+                password,
+                action,
+            } if is_press_event(event) => {
+                handle_record_action(binding, host, *port, password.clone(), action, system_state);
+                ActionOutcome::Pending
             }
 
             // OBSSourceVisibility - press to toggle source visibility
-            Capability::OBSSourceVisibility { host, port, password, scene, source } if is_press_event(event) => {
-                handle_source_visibility(host, *port, password.clone(), scene, source, system_state);
-                true
+            Capability::OBSSourceVisibility {
+                host,
+                port,
+                password,
+                scene,
+                source,
+            } if is_press_event(event) => {
+                handle_source_visibility(
+                    binding,
+                    host,
+                    *port,
+                    password.clone(),
+                    scene,
+                    source,
+                    system_state,
+                );
+                ActionOutcome::Pending
             }
 
             // OBSAudio - encoder rotation for volume
-            Capability::OBSAudio { host, port, password, input_name, step } => {
+            Capability::OBSAudio {
+                host,
+                port,
+                password,
+                input_name,
+                step,
+            } => {
                 match event {
                     LogicalEvent::Encoder(e) => {
-                        handle_audio_volume(host, *port, password.clone(), input_name, *step, e.delta, system_state);
-                        true
+                        // Debounced; errors are only logged
+                        handle_audio_volume(
+                            host,
+                            *port,
+                            password.clone(),
+                            input_name,
+                            *step,
+                            e.delta,
+                        );
+                        ActionOutcome::Ok
                     }
                     _ if is_press_event(event) => {
-                        handle_audio_mute(host, *port, password.clone(), input_name, system_state);
-                        true
+                        handle_audio_mute(
+                            binding,
+                            host,
+                            *port,
+                            password.clone(),
+                            input_name,
+                            system_state,
+                        );
+                        ActionOutcome::Pending
                     }
-                    _ => false,
+                    _ => ActionOutcome::Ignored,
                 }
             }
 
             // OBSStudioMode - press to toggle studio mode
-            Capability::OBSStudioMode { host, port, password } if is_press_event(event) => {
-                handle_studio_mode(host, *port, password.clone(), system_state);
-                true
+            Capability::OBSStudioMode {
+                host,
+                port,
+                password,
+            } if is_press_event(event) => {
+                handle_studio_mode(binding, host, *port, password.clone(), system_state);
+                ActionOutcome::Pending
             }
 
             // OBSReplayBuffer - press to control replay buffer
-            Capability::OBSReplayBuffer { host, port, password, action } if is_press_event(event) => {
-                handle_replay_action(host, *port, password.clone(), action, system_state);
-                true
+            Capability::OBSReplayBuffer {
+                host,
+                port,
+                password,
+                action,
+            } if is_press_event(event) => {
+                handle_replay_action(binding, host, *port, password.clone(), action, system_state);
+                ActionOutcome::Pending
             }
 
             // OBSVirtualCam - press to toggle virtual camera
-            Capability::OBSVirtualCam { host, port, password } if is_press_event(event) => {
-                handle_virtual_cam(host, *port, password.clone(), system_state);
-                true
+            Capability::OBSVirtualCam {
+                host,
+                port,
+                password,
+            } if is_press_event(event) => {
+                handle_virtual_cam(binding, host, *port, password.clone(), system_state);
+                ActionOutcome::Pending
             }
 
             // OBSTransition - press to trigger transition
-            Capability::OBSTransition { host, port, password } if is_press_event(event) => {
-                handle_transition(host, *port, password.clone());
-                true
+            Capability::OBSTransition {
+                host,
+                port,
+                password,
+            } if is_press_event(event) => {
+                handle_transition(binding, host, *port, password.clone());
+                ActionOutcome::Pending
             }
 
-            _ => false,
+            _ => ActionOutcome::Ignored,
         }
     }
 
@@ -326,76 +546,76 @@ impl Plugin for OBSPlugin {
         "OBSTransition"
     );
 
-    fn is_active(&self, binding: &Binding, system_state: &SystemState) -> bool {
-        match &binding.capability {
-            Capability::OBSScene { host, port, scene, .. } => {
-                let key = format!("{}:{}", host, port);
-                system_state
-                    .obs_states
-                    .get(&key)
-                    .map(|s| s.current_scene == *scene)
-                    .unwrap_or(false)
-            }
-            Capability::OBSStream { host, port, .. } => {
-                let key = format!("{}:{}", host, port);
-                system_state
-                    .obs_states
-                    .get(&key)
-                    .map(|s| s.streaming)
-                    .unwrap_or(false)
-            }
-            Capability::OBSRecord { host, port, .. } => {
-                let key = format!("{}:{}", host, port);
-                system_state
-                    .obs_states
-                    .get(&key)
-                    .map(|s| s.recording)
-                    .unwrap_or(false)
-            }
-            Capability::OBSSourceVisibility { host, port, scene, source, .. } => {
-                let key = format!("{}:{}", host, port);
-                let source_key = format!("{}:{}", scene, source);
-                system_state
-                    .obs_states
-                    .get(&key)
-                    .and_then(|s| s.source_visibility.get(&source_key))
-                    .copied()
-                    .unwrap_or(false)
-            }
-            Capability::OBSAudio { host, port, input_name, .. } => {
-                let key = format!("{}:{}", host, port);
-                system_state
-                    .obs_states
-                    .get(&key)
-                    .and_then(|s| s.muted_inputs.get(input_name))
-                    .copied()
-                    .unwrap_or(false)
+    /// Recording is "recording", "paused" or "idle" and streaming is "live"
+    /// or "offline"; the rest report `ACTIVE_STATE` or `INACTIVE_STATE`.
+    fn state(&self, binding: &Binding, system_state: &SystemState) -> Option<String> {
+        let obs =
+            binding_connection(binding).and_then(|conn| system_state.obs_states.get(&conn.key()));
+        let state = match &binding.capability {
+            Capability::OBSRecord { .. } => {
+                let obs = obs?;
+                if obs.recording_paused {
+                    "paused"
+                } else if obs.recording {
+                    "recording"
+                } else {
+                    "idle"
+                }
             }
-            Capability::OBSStudioMode { host, port, .. } => {
-                let key = format!("{}:{}", host, port);
-                system_state
-                    .obs_states
-                    .get(&key)
-                    .map(|s| s.studio_mode)
-                    .unwrap_or(false)
+            Capability::OBSStream { .. } => {
+                if obs?.streaming {
+                    "live"
+                } else {
+                    "offline"
+                }
             }
-            Capability::OBSReplayBuffer { host, port, .. } => {
-                let key = format!("{}:{}", host, port);
-                system_state
-                    .obs_states
-                    .get(&key)
-                    .map(|s| s.replay_buffer)
-                    .unwrap_or(false)
+            _ => active_state_key(is_on(binding, system_state)),
+        };
+        Some(state.to_string())
+    }
+
+    /// `{obs.scene}` is the binding's OBS instance's current scene (any known
+    /// instance for other bindings). `{obs.volume}` is the volume of an
+    /// OBSAudio binding's input, or of the input named in the argument, in
+    /// percent.
+    fn label_value(
+        &self,
+        binding: &Binding,
+        key: &str,
+        argument: Option<&str>,
+        system_state: &SystemState,
+    ) -> Option<String> {
+        let conn = binding_connection(binding);
+        match key {
+            "obs.scene" => {
+                let obs = match &conn {
+                    Some(conn) => system_state.obs_states.get(&conn.key()),
+                    None => system_state.obs_states.values().next(),
+                };
+                obs.map(|s| s.current_scene.clone())
+                    .filter(|scene| !scene.is_empty())
             }
-            Capability::OBSVirtualCam { host, port, .. } => {
-                let key = format!("{}:{}", host, port);
-                system_state
-                    .obs_states
-                    .get(&key)
-                    .map(|s| s.virtual_cam)
-                    .unwrap_or(false)
+            "obs.volume" => {
+                let input_name = match (argument, &binding.capability) {
+                    (Some(input_name), _) => input_name.trim(),
+                    (None, Capability::OBSAudio { input_name, .. }) => input_name,
+                    _ => return None,
+                };
+                get_audio_controller()
+                    .get_or_fetch_volume(&conn?, input_name)
+                    .map(label_template::percent)
             }
-            _ => false,
+            _ => None,
+        }
+    }
+
+    /// An audio input's volume in dB, as on the OBS mixer.
+    fn value(&self, binding: &Binding, _system_state: &SystemState) -> Option<BindingValue> {
+        match &binding.capability {
+            Capability::OBSAudio { input_name, .. } => get_audio_controller()
+                .get_or_fetch_volume(&binding_connection(binding)?, input_name)
+                .map(volume_db),
+            _ => None,
         }
     }
 
@@ -462,10 +682,11 @@ fn obs_password_param() -> ParameterDef {
 }
 
 // ─────────────────────────────────────────────────────────────────
-// Event handlers (spawn background threads)
+// Event handlers (spawn background threads and report their result)
 // ─────────────────────────────────────────────────────────────────
 
 fn handle_scene_switch(
+    binding: &Binding,
     host: &str,
     port: u16,
     password: Option<String>,
@@ -475,10 +696,11 @@ fn handle_scene_switch(
     let conn = OBSConnection::new(host, port, password);
     let scene = scene.to_string();
     let state = Arc::clone(system_state);
+    let binding = binding.clone();
 
     std::thread::spawn(move || {
         if let Err(e) = client::set_current_scene(&conn, &scene) {
-            eprintln!("OBS scene switch error: {e}");
+            report_action_result(&binding, Err(format!("OBS scene switch error: {e}")));
             return;
         }
 
@@ -489,11 +711,14 @@ fn handle_scene_switch(
             obs_state.current_scene = scene;
         }
 
+        report_action_result(&binding, Ok(()));
+
         request_image_sync();
     });
 }
 
 fn handle_stream_action(
+    binding: &Binding,
     host: &str,
     port: u16,
     password: Option<String>,
@@ -503,6 +728,7 @@ fn handle_stream_action(
     let conn = OBSConnection::new(host, port, password);
     let action = action.clone();
     let state = Arc::clone(system_state);
+    let binding = binding.clone();
 
     std::thread::spawn(move || {
         let result = match action {
@@ -514,9 +740,8 @@ fn handle_stream_action(
         match result {
             Ok(new_state) => {
                 // Get actual state if not returned by the action
-                let streaming = new_state.unwrap_or_else(|| {
-                    client::get_stream_status(&conn).unwrap_or(false)
-                });
+                let streaming =
+                    new_state.unwrap_or_else(|| client::get_stream_status(&conn).unwrap_or(false));
 
                 // Update state
                 if let Ok(mut s) = state.lock() {
@@ -525,14 +750,17 @@ fn handle_stream_action(
                     obs_state.streaming = streaming;
                 }
 
+                report_action_result(&binding, Ok(()));
+
                 request_image_sync();
             }
-            Err(e) => eprintln!("OBS stream error: {e}"),
+            Err(e) => report_action_result(&binding, Err(format!("OBS stream error: {e}"))),
         }
     });
 }
 
 fn handle_record_action(
+    binding: &Binding,
     host: &str,
     port: u16,
     password: Option<String>,
@@ -542,6 +770,7 @@ fn handle_record_action(
     let conn = OBSConnection::new(host, port, password);
     let action = action.clone();
     let state = Arc::clone(system_state);
+    let binding = binding.clone();
 
     std::thread::spawn(move || {
         let result = match action {
@@ -552,7 +781,7 @@ fn handle_record_action(
         };
 
         if let Err(e) = result {
-            eprintln!("OBS record error: {e}");
+            report_action_result(&binding, Err(format!("OBS record error: {e}")));
             return;
         }
 
@@ -566,11 +795,14 @@ fn handle_record_action(
             }
         }
 
+        report_action_result(&binding, Ok(()));
+
         request_image_sync();
     });
 }
 
 fn handle_source_visibility(
+    binding: &Binding,
     host: &str,
     port: u16,
     password: Option<String>,
@@ -582,9 +814,10 @@ fn handle_source_visibility(
     let scene = scene.to_string();
     let source = source.to_string();
     let state = Arc::clone(system_state);
+    let binding = binding.clone();
 
-    std::thread::spawn(move || {
-        match client::toggle_source_visibility(&conn, &scene, &source) {
+    std::thread::spawn(
+        move || match client::toggle_source_visibility(&conn, &scene, &source) {
             Ok(new_visible) => {
                 if let Ok(mut s) = state.lock() {
                     let key = conn.key();
@@ -592,11 +825,14 @@ fn handle_source_visibility(
                     let source_key = format!("{}:{}", scene, source);
                     obs_state.source_visibility.insert(source_key, new_visible);
                 }
+                report_action_result(&binding, Ok(()));
                 request_image_sync();
             }
-            Err(e) => eprintln!("OBS source visibility error: {e}"),
-        }
-    });
+            Err(e) => {
+                report_action_result(&binding, Err(format!("OBS source visibility error: {e}")))
+            }
+        },
+    );
 }
 
 fn handle_audio_volume(
@@ -606,7 +842,6 @@ fn handle_audio_volume(
     input_name: &str,
     step: f32,
     delta: i8,
-    system_state: &Arc<Mutex<SystemState>>,
 ) {
     if delta == 0 {
         return;
@@ -616,11 +851,12 @@ fn handle_audio_volume(
     let volume_delta = step * delta as f32;
 
     // Use the debounced controller
-    let controller = get_audio_controller(system_state);
+    let controller = get_audio_controller();
     controller.queue_volume_delta(&conn, input_name, volume_delta);
 }
 
 fn handle_audio_mute(
+    binding: &Binding,
     host: &str,
     port: u16,
     password: Option<String>,
@@ -630,23 +866,26 @@ fn handle_audio_mute(
     let conn = OBSConnection::new(host, port, password);
     let input_name = input_name.to_string();
     let state = Arc::clone(system_state);
+    let binding = binding.clone();
 
-    std::thread::spawn(move || {
-        match client::toggle_input_mute(&conn, &input_name) {
+    std::thread::spawn(
+        move || match client::toggle_input_mute(&conn, &input_name) {
             Ok(muted) => {
                 if let Ok(mut s) = state.lock() {
                     let key = conn.key();
                     let obs_state = s.obs_states.entry(key).or_insert_with(OBSState::default);
                     obs_state.muted_inputs.insert(input_name.clone(), muted);
                 }
+                report_action_result(&binding, Ok(()));
                 request_image_sync();
             }
-            Err(e) => eprintln!("OBS mute error: {e}"),
-        }
-    });
+            Err(e) => report_action_result(&binding, Err(format!("OBS mute error: {e}"))),
+        },
+    );
 }
 
 fn handle_studio_mode(
+    binding: &Binding,
     host: &str,
     port: u16,
     password: Option<String>,
@@ -654,23 +893,24 @@ fn handle_studio_mode(
 ) {
     let conn = OBSConnection::new(host, port, password);
     let state = Arc::clone(system_state);
+    let binding = binding.clone();
 
-    std::thread::spawn(move || {
-        match client::toggle_studio_mode(&conn) {
-            Ok(enabled) => {
-                if let Ok(mut s) = state.lock() {
-                    let key = conn.key();
-                    let obs_state = s.obs_states.entry(key).or_insert_with(OBSState::default);
-                    obs_state.studio_mode = enabled;
-                }
-                request_image_sync();
+    std::thread::spawn(move || match client::toggle_studio_mode(&conn) {
+        Ok(enabled) => {
+            if let Ok(mut s) = state.lock() {
+                let key = conn.key();
+                let obs_state = s.obs_states.entry(key).or_insert_with(OBSState::default);
+                obs_state.studio_mode = enabled;
             }
-            Err(e) => eprintln!("OBS studio mode error: {e}"),
+            report_action_result(&binding, Ok(()));
+            request_image_sync();
         }
+        Err(e) => report_action_result(&binding, Err(format!("OBS studio mode error: {e}"))),
     });
 }
 
 fn handle_replay_action(
+    binding: &Binding,
     host: &str,
     port: u16,
     password: Option<String>,
@@ -680,6 +920,7 @@ fn handle_replay_action(
     let conn = OBSConnection::new(host, port, password);
     let action = action.clone();
     let state = Arc::clone(system_state);
+    let binding = binding.clone();
 
     std::thread::spawn(move || {
         let result = match action {
@@ -692,9 +933,8 @@ fn handle_replay_action(
         match result {
             Ok(new_state) => {
                 // Get actual state if not returned
-                let active = new_state.unwrap_or_else(|| {
-                    client::get_replay_buffer_status(&conn).unwrap_or(false)
-                });
+                let active = new_state
+                    .unwrap_or_else(|| client::get_replay_buffer_status(&conn).unwrap_or(false));
 
                 if let Ok(mut s) = state.lock() {
                     let key = conn.key();
@@ -702,14 +942,17 @@ fn handle_replay_action(
                     obs_state.replay_buffer = active;
                 }
 
+                report_action_result(&binding, Ok(()));
+
                 request_image_sync();
             }
-            Err(e) => eprintln!("OBS replay buffer error: {e}"),
+            Err(e) => report_action_result(&binding, Err(format!("OBS replay buffer error: {e}"))),
         }
     });
 }
 
 fn handle_virtual_cam(
+    binding: &Binding,
     host: &str,
     port: u16,
     password: Option<String>,
@@ -717,29 +960,30 @@ fn handle_virtual_cam(
 ) {
     let conn = OBSConnection::new(host, port, password);
     let state = Arc::clone(system_state);
+    let binding = binding.clone();
 
-    std::thread::spawn(move || {
-        match client::toggle_virtual_cam(&conn) {
-            Ok(active) => {
-                if let Ok(mut s) = state.lock() {
-                    let key = conn.key();
-                    let obs_state = s.obs_states.entry(key).or_insert_with(OBSState::default);
-                    obs_state.virtual_cam = active;
-                }
-                request_image_sync();
+    std::thread::spawn(move || match client::toggle_virtual_cam(&conn) {
+        Ok(active) => {
+            if let Ok(mut s) = state.lock() {
+                let key = conn.key();
+                let obs_state = s.obs_states.entry(key).or_insert_with(OBSState::default);
+                obs_state.virtual_cam = active;
             }
-            Err(e) => eprintln!("OBS virtual cam error: {e}"),
+            report_action_result(&binding, Ok(()));
+            request_image_sync();
         }
+        Err(e) => report_action_result(&binding, Err(format!("OBS virtual cam error: {e}"))),
     });
 }
 
-fn handle_transition(host: &str, port: u16, password: Option<String>) {
+fn handle_transition(binding: &Binding, host: &str, port: u16, password: Option<String>) {
     let conn = OBSConnection::new(host, port, password);
+    let binding = binding.clone();
 
     std::thread::spawn(move || {
-        if let Err(e) = client::trigger_transition(&conn) {
-            eprintln!("OBS transition error: {e}");
-        }
+        let result =
+            client::trigger_transition(&conn).map_err(|e| format!("OBS transition error: {e}"));
+        report_action_result(&binding, result);
     });
 }
 
@@ -809,4 +1053,21 @@ mod tests {
         let plugin = OBSPlugin::new();
         assert!(!plugin.is_core());
     }
+
+    #[test]
+    fn volume_is_shown_in_decibels() {
+        let full = volume_db(1.0);
+        assert_eq!((full.value, full.display.as_str()), (0.0, "0.0 dB"));
+        assert_eq!(full.fraction(), 1.0);
+
+        let half = volume_db(0.5);
+        assert_eq!(half.display, "-6.0 dB");
+
+        let silent = volume_db(0.0);
+        assert_eq!(
+            (silent.value, silent.display.as_str()),
+            (MIN_VOLUME_DB, "-inf dB")
+        );
+        assert_eq!(silent.fraction(), 0.0);
+    }
 }
@@ -1,6 +1,8 @@
 //! State management for stateful capabilities (mute, media playback, etc.)
 //!
 //! Polls system state every 2 seconds and emits events when state changes.
+//! CommandDisplay commands run on their own refresh intervals, and RunCommand
+//! exits are emitted as `command:exit`.
 
 #[cfg(feature = "plugin-elgato")]
 use crate::plugins::elgato::client::{self as elgato_key_light, KeyLightState};
@@ -38,22 +40,58 @@ pub struct OBSState {
     /// Source visibility: "scene:source" -> is_visible
     pub source_visibility: HashMap<String, bool>,
 }
-use std::process::Command;
+use crate::binding::Binding;
+use crate::capability::Capability;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Read;
+use std::process::{Command, Stdio};
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
-use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter};
 
+/// How long a RunCommand state probe may run before it is killed
+const PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Result of running a RunCommand `state_command` probe
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CommandOutput {
+    pub success: bool,
+    /// Trimmed stdout
+    pub stdout: String,
+}
+
+impl CommandOutput {
+    /// Whether the probe reports active: its output equals `state_match`, or
+    /// without one, it exited 0.
+    pub fn is_active(&self, state_match: Option<&str>) -> bool {
+        match state_match {
+            Some(expected) => self.stdout == expected.trim(),
+            None => self.success,
+        }
+    }
+}
+
 /// Current state of stateful capabilities
 #[derive(Debug, Clone, Default)]
 pub struct SystemState {
     pub is_muted: bool,
     pub is_mic_muted: bool,
     pub is_playing: bool,
+    /// Media is paused (neither playing nor paused = stopped or no player)
+    pub is_paused: bool,
+    /// Default output volume (0.0-1.0, None if wpctl couldn't be read)
+    pub volume: Option<f32>,
+    /// Default input volume (0.0-1.0, None if wpctl couldn't be read)
+    pub mic_volume: Option<f32>,
     /// Key light states: "ip:port" -> KeyLightState
     pub key_lights: HashMap<String, KeyLightState>,
     /// Toggle states for RunCommand with toggle=true: "input_key:page" -> is_active
     pub toggle_states: HashMap<String, bool>,
+    /// Last result of each RunCommand state probe: command -> output
+    pub command_outputs: HashMap<String, CommandOutput>,
+    /// First line printed by each CommandDisplay command: command -> text
+    pub display_outputs: HashMap<String, String>,
     /// OBS states: "host:port" -> OBSState
     #[allow(dead_code)] // Used by plugin-obs feature
     pub obs_states: HashMap<String, OBSState>,
@@ -67,51 +105,39 @@ pub fn request_state_check() {
     CHECK_STATE_NOW.store(true, Ordering::SeqCst);
 }
 
-/// Check if audio is currently muted via wpctl
-pub fn check_mute_state() -> bool {
+/// Read a PipeWire node's volume and mute state via wpctl.
+/// Returns None if wpctl isn't available or the output can't be parsed.
+pub fn check_volume(node: &str) -> Option<(f32, bool)> {
     let output = Command::new("wpctl")
-        .args(["get-volume", "@DEFAULT_AUDIO_SINK@"])
-        .output();
-
-    match output {
-        Ok(out) => {
-            let stdout = String::from_utf8_lossy(&out.stdout);
-            // Output looks like "Volume: 0.50" or "Volume: 0.50 [MUTED]"
-            stdout.contains("[MUTED]")
-        }
-        Err(_) => false,
-    }
+        .args(["get-volume", node])
+        .output()
+        .ok()?;
+    parse_wpctl_volume(&String::from_utf8_lossy(&output.stdout))
 }
 
-/// Check if microphone is currently muted via wpctl
-pub fn check_mic_mute_state() -> bool {
-    let output = Command::new("wpctl")
-        .args(["get-volume", "@DEFAULT_AUDIO_SOURCE@"])
-        .output();
-
-    match output {
-        Ok(out) => {
-            let stdout = String::from_utf8_lossy(&out.stdout);
-            // Output looks like "Volume: 0.50" or "Volume: 0.50 [MUTED]"
-            stdout.contains("[MUTED]")
-        }
-        Err(_) => false,
-    }
+/// Parse wpctl output, which looks like "Volume: 0.50" or "Volume: 0.50 [MUTED]"
+fn parse_wpctl_volume(stdout: &str) -> Option<(f32, bool)> {
+    let volume = stdout
+        .split_whitespace()
+        .find_map(|word| word.parse::<f32>().ok())?;
+    Some((volume, stdout.contains("[MUTED]")))
 }
 
-/// Check if media is currently playing via playerctl
-pub fn check_playing_state() -> bool {
-    let output = Command::new("playerctl")
-        .arg("status")
-        .output();
+/// Check whether media is playing or paused via playerctl: (playing, paused)
+pub fn check_playback_state() -> (bool, bool) {
+    let output = Command::new("playerctl").arg("status").output();
 
     match output {
         Ok(out) => {
             let stdout = String::from_utf8_lossy(&out.stdout);
             // Output is "Playing", "Paused", or "Stopped"
-            stdout.trim() == "Playing"
+            match stdout.trim() {
+                "Playing" => (true, false),
+                "Paused" => (false, true),
+                _ => (false, false),
+            }
         }
-        Err(_) => false,
+        Err(_) => (false, false),
     }
 }
 
@@ -119,20 +145,198 @@ pub fn check_playing_state() -> bool {
 /// Runs state checks in parallel for better performance.
 pub fn get_current_state() -> SystemState {
     // Run all state checks in parallel
-    let mute_handle = std::thread::spawn(check_mute_state);
-    let mic_handle = std::thread::spawn(check_mic_mute_state);
-    let playing_handle = std::thread::spawn(check_playing_state);
+    let sink_handle = std::thread::spawn(|| check_volume("@DEFAULT_AUDIO_SINK@"));
+    let source_handle = std::thread::spawn(|| check_volume("@DEFAULT_AUDIO_SOURCE@"));
+    let playback_handle = std::thread::spawn(check_playback_state);
+    let sink = sink_handle.join().ok().flatten();
+    let source = source_handle.join().ok().flatten();
+    let (is_playing, is_paused) = playback_handle.join().unwrap_or((false, false));
 
     SystemState {
-        is_muted: mute_handle.join().unwrap_or(false),
-        is_mic_muted: mic_handle.join().unwrap_or(false),
-        is_playing: playing_handle.join().unwrap_or(false),
+        is_muted: sink.is_some_and(|(_, muted)| muted),
+        is_mic_muted: source.is_some_and(|(_, muted)| muted),
+        is_playing,
+        is_paused,
+        volume: sink.map(|(volume, _)| volume),
+        mic_volume: source.map(|(volume, _)| volume),
         key_lights: HashMap::new(),
         toggle_states: HashMap::new(),
+        command_outputs: HashMap::new(),
+        display_outputs: HashMap::new(),
         obs_states: HashMap::new(),
     }
 }
 
+/// Run a state probe without a shell, killing it after `PROBE_TIMEOUT`.
+/// Returns None if it can't be run or times out.
+pub fn check_command_state(command: &str) -> Option<CommandOutput> {
+    run_with_timeout(command, PROBE_TIMEOUT)
+}
+
+/// Run a command without a shell and capture its output, killing it after
+/// `timeout`. Returns None if it can't be run or times out.
+fn run_with_timeout(command: &str, timeout: Duration) -> Option<CommandOutput> {
+    let args = shlex::split(command).filter(|args| !args.is_empty())?;
+    let mut child = Command::new(&args[0])
+        .args(&args[1..])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    // Drain stdout while polling, or a command that fills the pipe would
+    // block until the timeout kills it.
+    let mut stdout = child.stdout.take()?;
+    let (sender, output) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = stdout.read_to_end(&mut buf);
+        let _ = sender.send(buf);
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(10)),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    };
+
+    // A background child that inherited stdout keeps the pipe open after
+    // the command exits; give up on the output rather than wait for it
+    let stdout = output
+        .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+        .ok()?;
+    Some(CommandOutput {
+        success: status.success(),
+        stdout: String::from_utf8_lossy(&stdout).trim().to_string(),
+    })
+}
+
+/// Every distinct state probe used by a binding.
+fn state_commands(bindings: &[Binding]) -> BTreeSet<String> {
+    bindings
+        .iter()
+        .filter_map(|b| match &b.capability {
+            Capability::RunCommand {
+                state_command: Some(probe),
+                ..
+            } => Some(probe.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Run the probes in parallel; probes that fail are left out.
+fn check_command_states(commands: BTreeSet<String>) -> HashMap<String, CommandOutput> {
+    let handles: Vec<_> = commands
+        .into_iter()
+        .map(|command| {
+            std::thread::spawn(move || {
+                check_command_state(&command).map(|output| (command, output))
+            })
+        })
+        .collect();
+    handles
+        .into_iter()
+        .filter_map(|h| h.join().ok().flatten())
+        .collect()
+}
+
+/// Text stored for a CommandDisplay command that failed to run or timed out
+const DISPLAY_ERROR: &str = "?";
+
+/// When each CommandDisplay command last started, and whether it is still running
+static DISPLAY_RUNS: Mutex<BTreeMap<String, (Instant, bool)>> = Mutex::new(BTreeMap::new());
+
+/// Every distinct CommandDisplay command with its refresh interval and timeout.
+fn display_commands(bindings: &[Binding]) -> BTreeMap<String, (Duration, Duration)> {
+    bindings
+        .iter()
+        .filter_map(|b| match &b.capability {
+            Capability::CommandDisplay {
+                command,
+                interval_ms,
+                timeout_ms,
+                ..
+            } => Some((
+                command.clone(),
+                (
+                    Duration::from_millis(*interval_ms),
+                    Duration::from_millis(*timeout_ms),
+                ),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Start the CommandDisplay commands that have never run or whose refresh
+/// interval has elapsed.
+fn refresh_due_displays(state: &Arc<Mutex<SystemState>>, bindings: &Arc<Mutex<Vec<Binding>>>) {
+    let commands = display_commands(&bindings.lock().unwrap_or_else(|e| e.into_inner()));
+    for (command, (interval, timeout)) in commands {
+        let due = match DISPLAY_RUNS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&command)
+        {
+            Some((started, _)) => !interval.is_zero() && started.elapsed() >= interval,
+            None => true,
+        };
+        if due {
+            refresh_command_display(&command, timeout, state);
+        }
+    }
+}
+
+/// Run a CommandDisplay command in the background and store the first line it
+/// prints, syncing images if it changed. Does nothing if it is already running.
+pub fn refresh_command_display(command: &str, timeout: Duration, state: &Arc<Mutex<SystemState>>) {
+    {
+        let mut runs = DISPLAY_RUNS.lock().unwrap_or_else(|e| e.into_inner());
+        if runs.get(command).is_some_and(|(_, running)| *running) {
+            return;
+        }
+        runs.insert(command.to_string(), (Instant::now(), true));
+    }
+
+    let command = command.to_string();
+    let state = Arc::clone(state);
+    std::thread::spawn(move || {
+        let text = match run_with_timeout(&command, timeout) {
+            Some(output) => output
+                .stdout
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+            None => DISPLAY_ERROR.to_string(),
+        };
+        if let Some((_, running)) = DISPLAY_RUNS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_mut(&command)
+        {
+            *running = false;
+        }
+
+        let mut current = state.lock().unwrap_or_else(|e| e.into_inner());
+        if current.display_outputs.get(&command) != Some(&text) {
+            current.display_outputs.insert(command, text);
+            drop(current);
+            crate::streamdeck::request_image_sync();
+        }
+    });
+}
+
 /// Check key light state for a specific light
 /// (Reserved for future key light state polling)
 #[allow(dead_code)]
@@ -153,6 +357,7 @@ pub struct StateChangeEvent {
     pub is_muted: bool,
     pub is_mic_muted: bool,
     pub is_playing: bool,
+    pub is_paused: bool,
     pub key_lights: HashMap<String, KeyLightState>,
 }
 
@@ -160,83 +365,91 @@ pub struct StateChangeEvent {
 static TICK_COUNT: AtomicU32 = AtomicU32::new(0);
 
 /// Run the state polling loop
-pub fn run_state_poller(app: AppHandle, state: Arc<Mutex<SystemState>>) {
+pub fn run_state_poller(
+    app: AppHandle,
+    state: Arc<Mutex<SystemState>>,
+    bindings: Arc<Mutex<Vec<Binding>>>,
+) {
     let check_interval = Duration::from_millis(100);
 
     loop {
         // Check if immediate check requested
-        let should_check = CHECK_STATE_NOW.swap(false, Ordering::SeqCst);
-
-        if should_check {
-            let mut new_state = get_current_state();
-            let mut current = state.lock().unwrap();
-
-            // Copy over existing key light states (they're checked on demand)
-            new_state.key_lights = current.key_lights.clone();
-
-            let state_changed = new_state.is_muted != current.is_muted
-                || new_state.is_mic_muted != current.is_mic_muted
-                || new_state.is_playing != current.is_playing;
-
-            if state_changed {
-                *current = new_state.clone();
-                drop(current); // Release lock before emitting
+        if CHECK_STATE_NOW.swap(false, Ordering::SeqCst) {
+            poll_state(&app, &state, &bindings);
+        }
 
-                // Emit state change event
-                let _ = app.emit("state:change", StateChangeEvent {
-                    is_muted: new_state.is_muted,
-                    is_mic_muted: new_state.is_mic_muted,
-                    is_playing: new_state.is_playing,
-                    key_lights: new_state.key_lights,
-                });
+        refresh_due_displays(&state, &bindings);
 
-                // Request image sync to update hardware
-                crate::streamdeck::request_image_sync();
-            }
+        // Report finished RunCommand children to the frontend
+        for exit in crate::core::commands::take_command_exits() {
+            let _ = app.emit("command:exit", exit);
         }
 
         std::thread::sleep(check_interval);
 
         // Every 2 seconds, do a regular poll
         let tick = TICK_COUNT.fetch_add(1, Ordering::Relaxed);
-        if tick >= 19 { // 20 * 100ms = 2s (0-19 = 20 ticks)
+        if tick >= 19 {
+            // 20 * 100ms = 2s (0-19 = 20 ticks)
             TICK_COUNT.store(0, Ordering::Relaxed);
-
-            let mut new_state = get_current_state();
-            let mut current = state.lock().unwrap();
-
-            // Copy over existing key light states
-            new_state.key_lights = current.key_lights.clone();
-
-            let state_changed = new_state.is_muted != current.is_muted
-                || new_state.is_mic_muted != current.is_mic_muted
-                || new_state.is_playing != current.is_playing;
-
-            if state_changed {
-                *current = new_state.clone();
-                drop(current);
-
-                let _ = app.emit("state:change", StateChangeEvent {
-                    is_muted: new_state.is_muted,
-                    is_mic_muted: new_state.is_mic_muted,
-                    is_playing: new_state.is_playing,
-                    key_lights: new_state.key_lights,
-                });
-
-                crate::streamdeck::request_image_sync();
-            }
+            poll_state(&app, &state, &bindings);
         }
     }
 }
 
+/// Poll system state and command probes, emitting an event and syncing
+/// images if anything changed.
+fn poll_state(
+    app: &AppHandle,
+    state: &Arc<Mutex<SystemState>>,
+    bindings: &Arc<Mutex<Vec<Binding>>>,
+) {
+    let probes = state_commands(&bindings.lock().unwrap_or_else(|e| e.into_inner()));
+    let probes = std::thread::spawn(move || check_command_states(probes));
+    let new_state = get_current_state();
+    let command_outputs = probes.join().unwrap_or_default();
+
+    let mut current = state.lock().unwrap_or_else(|e| e.into_inner());
+    let state_changed = new_state.is_muted != current.is_muted
+        || new_state.is_mic_muted != current.is_mic_muted
+        || new_state.is_playing != current.is_playing
+        || new_state.is_paused != current.is_paused
+        || new_state.volume != current.volume
+        || new_state.mic_volume != current.mic_volume
+        || command_outputs != current.command_outputs;
+    if !state_changed {
+        return;
+    }
+
+    // Only the polled fields change; key lights, toggles and OBS are
+    // tracked elsewhere
+    current.is_muted = new_state.is_muted;
+    current.is_mic_muted = new_state.is_mic_muted;
+    current.is_playing = new_state.is_playing;
+    current.is_paused = new_state.is_paused;
+    current.volume = new_state.volume;
+    current.mic_volume = new_state.mic_volume;
+    current.command_outputs = command_outputs;
+    let event = StateChangeEvent {
+        is_muted: current.is_muted,
+        is_mic_muted: current.is_mic_muted,
+        is_playing: current.is_playing,
+        is_paused: current.is_paused,
+        key_lights: current.key_lights.clone(),
+    };
+    drop(current); // Release lock before emitting
+
+    // Emit state change event
+    let _ = app.emit("state:change", event);
+
+    // Request image sync to update hardware
+    crate::streamdeck::request_image_sync();
+}
+
 /// Update key light state in the system state
 /// (Reserved for future key light state polling)
 #[allow(dead_code)]
-pub fn update_key_light_state(
-    state: &Arc<Mutex<SystemState>>,
-    ip: &str,
-    port: u16,
-) {
+pub fn update_key_light_state(state: &Arc<Mutex<SystemState>>, ip: &str, port: u16) {
     if let Some(light_state) = check_key_light_state(ip, port) {
         if let Ok(mut current) = state.lock() {
             let key = format!("{}:{}", ip, port);
@@ -244,3 +457,52 @@ pub fn update_key_light_state(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn probe_exit_code_and_output() {
+        let ok = check_command_state("true").unwrap();
+        assert!(ok.is_active(None));
+        assert!(!check_command_state("false").unwrap().is_active(None));
+        assert!(check_command_state("definitely-not-a-command-xyz").is_none());
+
+        let output = check_command_state("echo '  active '").unwrap();
+        assert_eq!(output.stdout, "active");
+        assert!(output.is_active(Some("active")));
+        assert!(!output.is_active(Some("inactive")));
+    }
+
+    #[test]
+    fn wpctl_volume_is_parsed() {
+        assert_eq!(parse_wpctl_volume("Volume: 0.50\n"), Some((0.5, false)));
+        assert_eq!(
+            parse_wpctl_volume("Volume: 0.35 [MUTED]\n"),
+            Some((0.35, true))
+        );
+        assert_eq!(parse_wpctl_volume(""), None);
+    }
+
+    #[test]
+    fn slow_commands_are_killed() {
+        let started = Instant::now();
+        assert!(run_with_timeout("sleep 5", Duration::from_millis(50)).is_none());
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn large_output_does_not_block() {
+        let output = run_with_timeout("head -c 200000 /dev/zero", Duration::from_secs(2))
+            .expect("command should finish before the timeout");
+        assert!(output.success);
+    }
+
+    #[test]
+    fn background_children_do_not_hold_up_the_output() {
+        let started = Instant::now();
+        assert!(run_with_timeout("sh -c 'sleep 5 &'", Duration::from_millis(200)).is_none());
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+}
@@ -1,17 +1,36 @@
-use crate::binding::{Binding, InputRef};
-use crate::button_renderer::{button_size_for_kind, encoder_lcd_size_for_kind, ButtonRenderer, LcdRenderer};
-use crate::device::DeviceInfo;
-use crate::events::{ConnectionStatusEvent, PageChangeEvent};
+use crate::binding::{Binding, Gauge, Gesture, InputRef, StateStyle};
+use crate::button_renderer::{
+    button_size_for_kind, encoder_lcd_size_for_kind, ButtonRenderer, LcdRenderer,
+};
+use crate::commands::AppState;
+use crate::device::{for_device, on_deck, DeviceInfo, OnDevice};
+use crate::events::{ActionResultEvent, ConnectionStatusEvent, DeviceEvent, PageChangeEvent};
 use crate::hotplug;
-use crate::input_processor::{detect_swipe_direction, InputProcessor, LogicalEvent, SwipeDirection};
-use crate::plugin::PluginRegistry;
+use crate::image_cache::Frames;
+use crate::input_processor::{
+    chord_sets, detect_swipe_direction, gesture_configs, touch_section, HeldInput, InputProcessor,
+    LogicalEvent, SwipeDirection,
+};
+use crate::label_template;
+use crate::navigation::{self, take_navigation_requests, Navigation, NavigationRequest, BACK_KEY};
+use crate::page::Page;
+use crate::plugin::{
+    record_outcome, take_action_results, ActionResult, BindingValue, PluginRegistry,
+};
 use crate::state_manager::SystemState;
 use anyhow::{Context, Result};
-use elgato_streamdeck::{images::ImageRect, info::Kind, list_devices, StreamDeck, StreamDeckInput};
+use elgato_streamdeck::{
+    images::ImageRect, info::Kind, list_devices, StreamDeck, StreamDeckError, StreamDeckInput,
+};
 use hidapi::HidApi;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use image::{DynamicImage, Rgba};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter};
 
 /// Interval for checking device reconnection when polling
@@ -22,72 +41,256 @@ const RECONNECT_MAX_WAIT: Duration = Duration::from_secs(2);
 /// Timeout for reading input from Stream Deck (affects responsiveness)
 const INPUT_POLL_TIMEOUT: Duration = Duration::from_millis(50);
 
-/// Flag to signal when button images need to be re-synced.
-pub static SYNC_IMAGES_FLAG: AtomicBool = AtomicBool::new(false);
+/// How often label templates and gauges are re-resolved between syncs, for
+/// values that change without a state change (e.g., the clock)
+const LIVE_VALUE_INTERVAL: Duration = Duration::from_secs(1);
 
-/// Request a sync of button images to hardware.
+/// How long a success/failure badge stays on a key
+const FLASH_DURATION: Duration = Duration::from_millis(800);
+
+/// Bumped on every image sync request; each deck resyncs when it changes.
+static SYNC_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Bindings pressed by keys and encoders still held down, by deck and input.
+/// The release goes to them, after their press handlers, so an undo or a kill
+/// on release runs last.
+static HELD_PRESSES: Mutex<BTreeMap<HeldKey, Vec<HeldPress>>> = Mutex::new(BTreeMap::new());
+
+/// A deck's serial and one of its inputs
+type HeldKey = (String, HeldInput);
+
+/// A binding a held key pressed, and the thread handling the press
+struct HeldPress {
+    binding: Binding,
+    handle: JoinHandle<()>,
+}
+
+/// Keys marked for redraw with `request_key_sync`, per connected deck
+static DIRTY_KEYS: Mutex<BTreeMap<String, Vec<DirtyKey>>> = Mutex::new(BTreeMap::new());
+
+/// Request a sync of button images to hardware (on every deck).
 pub fn request_image_sync() {
-    SYNC_IMAGES_FLAG.store(true, Ordering::SeqCst);
-}
-
-pub fn run(
-    app: AppHandle,
-    device_info_state: Arc<Mutex<Option<DeviceInfo>>>,
-    bindings_state: Arc<Mutex<Vec<Binding>>>,
-    system_state: Arc<Mutex<SystemState>>,
-    current_page: Arc<Mutex<usize>>,
-    plugin_registry: Arc<PluginRegistry>,
-) -> Result<()> {
-    // Outer loop handles connection/reconnection
-    loop {
-        // Try to connect to a Stream Deck
-        let connection = try_connect(&app, &device_info_state);
-
-        match connection {
-            Ok((mut deck, device_kind, button_renderer, lcd_renderer)) => {
-                // Initial image sync on connect
-                {
-                    let bindings = bindings_state.lock().ok();
-                    let state = system_state.lock().ok();
-                    let page = *current_page.lock().unwrap_or_else(|e| e.into_inner());
-                    if let (Some(bindings), Some(state)) = (bindings, state) {
-                        if let Some(ref renderer) = button_renderer {
-                            sync_button_images(&mut deck, &bindings, renderer, device_kind, &state, page, &plugin_registry);
-                        }
-                        if let Some(ref renderer) = lcd_renderer {
-                            sync_lcd_images(&mut deck, &bindings, renderer, device_kind, &state, page, &plugin_registry);
-                        }
-                    }
-                }
+    SYNC_GENERATION.fetch_add(1, Ordering::SeqCst);
+}
 
-                // Run the event loop until disconnected
-                let disconnect_reason = run_event_loop(
-                    &app,
-                    &mut deck,
-                    device_kind,
-                    &button_renderer,
-                    &lcd_renderer,
-                    &bindings_state,
-                    &system_state,
-                    &current_page,
-                    &plugin_registry,
-                );
-
-                // Device disconnected
-                eprintln!("Stream Deck disconnected: {}", disconnect_reason);
-                emit_connection_status(&app, false, None);
-
-                // Clear device info
-                if let Ok(mut state) = device_info_state.lock() {
-                    *state = None;
-                }
+/// Request a redraw of just the key (or LCD section, for encoders) a binding
+/// is on, re-sending it even if it looks unchanged. Goes to the binding's deck,
+/// or every deck for bindings not tagged with one. Cheaper than
+/// `request_image_sync` when a change only affects one binding.
+pub fn request_key_sync(binding: &Binding) {
+    let surface = match binding.input {
+        InputRef::Button { index } => Surface::Key(index as u8),
+        InputRef::Encoder { index } | InputRef::EncoderPress { index } => {
+            Surface::LcdSection(index as u8)
+        }
+        _ => return,
+    };
+    let dirty = DirtyKey {
+        folder: binding.folder.clone(),
+        page: binding.page,
+        surface,
+    };
+
+    let mut queues = DIRTY_KEYS.lock().unwrap_or_else(|e| e.into_inner());
+    match binding.device.as_deref() {
+        Some(serial) => {
+            if let Some(queue) = queues.get_mut(serial) {
+                queue.push(dirty);
             }
-            Err(_) => {
-                // No device found, wait before retrying
+        }
+        None => {
+            for queue in queues.values_mut() {
+                queue.push(dirty.clone());
             }
         }
+    }
+}
+
+/// Take the keys marked dirty on a deck (starts queueing for it).
+fn take_dirty_keys(serial: &str) -> Vec<DirtyKey> {
+    let mut queues = DIRTY_KEYS.lock().unwrap_or_else(|e| e.into_inner());
+    std::mem::take(queues.entry(serial.to_string()).or_default())
+}
+
+/// A key or LCD section on one page, marked for redraw
+#[derive(Debug, Clone, PartialEq)]
+struct DirtyKey {
+    folder: Option<String>,
+    page: usize,
+    surface: Surface,
+}
+
+/// Something on a deck that shows an image
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Surface {
+    Key(u8),
+    LcdSection(u8),
+}
+
+/// A connected deck and the renderers for its key and LCD sizes
+struct Deck {
+    serial: String,
+    kind: Kind,
+    device: StreamDeck,
+    button_renderer: Option<ButtonRenderer>,
+    lcd_renderer: Option<LcdRenderer>,
+    /// Result badges currently shown on keys
+    flashes: Vec<Flash>,
+    /// Label templates and gauge values on the current page as last drawn
+    live_values: Vec<String>,
+    /// Animated key images by binding slot; only those on the current page run
+    animations: HashMap<KeySlot, KeyAnimation>,
+    /// Hash of the image last sent to each key and LCD section, so syncs only
+    /// upload what changed
+    sent: HashMap<Surface, u64>,
+}
+
+/// Record `img` (None = blank) as shown on `surface`. Returns false if it
+/// already was, so the upload can be skipped.
+fn mark_sent(
+    sent: &mut HashMap<Surface, u64>,
+    surface: Surface,
+    img: Option<&DynamicImage>,
+) -> bool {
+    let mut hasher = DefaultHasher::new();
+    img.map(|img| (img.width(), img.height(), img.as_bytes()))
+        .hash(&mut hasher);
+    let hash = hasher.finish();
+    sent.insert(surface, hash) != Some(hash)
+}
+
+/// Show `img` on a key (None = blank) unless it already shows it.
+/// Needs a `flush` afterwards like `set_button_image`.
+fn send_key_image(
+    device: &StreamDeck,
+    sent: &mut HashMap<Surface, u64>,
+    key: u8,
+    img: Option<DynamicImage>,
+) -> Result<(), StreamDeckError> {
+    let surface = Surface::Key(key);
+    if !mark_sent(sent, surface, img.as_ref()) {
+        return Ok(());
+    }
+    let result = match img {
+        Some(img) => device.set_button_image(key, img),
+        None => device.clear_button_image(key),
+    };
+    if result.is_err() {
+        sent.remove(&surface);
+    }
+    result
+}
+
+/// Write an encoder's LCD section at `x` unless it already shows `img`.
+fn send_lcd_image(
+    device: &StreamDeck,
+    sent: &mut HashMap<Surface, u64>,
+    section: u8,
+    x: u16,
+    img: DynamicImage,
+) -> Result<(), StreamDeckError> {
+    let surface = Surface::LcdSection(section);
+    if !mark_sent(sent, surface, Some(&img)) {
+        return Ok(());
+    }
+    let result = ImageRect::from_image(img).and_then(|rect| device.write_lcd(x, 0, &rect));
+    if result.is_err() {
+        sent.remove(&surface);
+    }
+    result
+}
 
-        // Wait before trying to reconnect, but check hotplug flag frequently
+/// A key binding's folder, page and key index
+type KeySlot = (Option<String>, usize, usize);
+
+fn key_slot(binding: &Binding) -> Option<KeySlot> {
+    match binding.input {
+        InputRef::Button { index } => Some((binding.folder.clone(), binding.page, index)),
+        _ => None,
+    }
+}
+
+/// An animated image on a key, stepped by the deck's event loop
+struct KeyAnimation {
+    key: u8,
+    /// Binding as drawn, with its effective image and resolved label
+    binding: Binding,
+    frames: Frames,
+    frame: usize,
+    /// When the next frame is due (None while the key is off the current page)
+    next_at: Option<Instant>,
+    /// Drawn over every frame
+    gauge: Option<KeyGauge>,
+}
+
+impl KeyAnimation {
+    /// Step to the next frame if it is due. Returns whether the frame changed.
+    fn advance(&mut self, now: Instant) -> bool {
+        let Some(next_at) = self.next_at.filter(|next_at| *next_at <= now) else {
+            return false;
+        };
+        self.frame = (self.frame + 1) % self.frames.len();
+        // After a stall, carry on from now instead of racing to catch up
+        let delay = self.frames[self.frame].delay;
+        self.next_at = Some(if now - next_at > delay {
+            now + delay
+        } else {
+            next_at + delay
+        });
+        true
+    }
+}
+
+/// A binding's value drawn over its key
+#[derive(Debug, Clone)]
+struct KeyGauge {
+    gauge: Gauge,
+    value: BindingValue,
+    color: Option<String>,
+}
+
+impl KeyGauge {
+    fn draw(&self, renderer: &ButtonRenderer, img: DynamicImage) -> DynamicImage {
+        let mut rgba = img.to_rgba8();
+        renderer.add_gauge(&mut rgba, self.gauge, &self.value, self.color.as_deref());
+        DynamicImage::ImageRgba8(rgba)
+    }
+}
+
+/// A success or failure badge shown on a binding's key until `until`
+struct Flash {
+    folder: Option<String>,
+    page: usize,
+    index: usize,
+    ok: bool,
+    until: Instant,
+}
+
+impl Flash {
+    /// The key the badge is drawn on
+    fn key(&self) -> DirtyKey {
+        DirtyKey {
+            folder: self.folder.clone(),
+            page: self.page,
+            surface: Surface::Key(self.index as u8),
+        }
+    }
+
+    fn is_for(&self, binding: &Binding) -> bool {
+        matches!(binding.input, InputRef::Button { index } if index == self.index)
+            && binding.page == self.page
+            && binding.folder == self.folder
+    }
+}
+
+/// Connect every Stream Deck as it shows up and run each one on its own
+/// event loop thread.
+pub fn run(app: AppHandle, state: AppState) -> Result<()> {
+    loop {
+        // Decks that fail to open are retried on the next scan
+        let _ = connect_new_decks(&app, &state);
+
+        // Wait before scanning again, but check hotplug flag frequently
         // to respond quickly when a device is connected
         wait_for_device_or_timeout();
     }
@@ -109,86 +312,156 @@ fn wait_for_device_or_timeout() {
     }
 }
 
-/// Attempt to connect to a Stream Deck device
-fn try_connect(
-    app: &AppHandle,
-    device_info_state: &Arc<Mutex<Option<DeviceInfo>>>,
-) -> Result<(StreamDeck, Kind, Option<ButtonRenderer>, Option<LcdRenderer>)> {
+/// Connect to every Stream Deck that doesn't have an event loop yet
+fn connect_new_decks(app: &AppHandle, state: &AppState) -> Result<()> {
     let hid = HidApi::new().context("hid init failed")?;
-    let devices = list_devices(&hid);
 
-    if devices.is_empty() {
-        anyhow::bail!("No Stream Deck found");
-    }
-
-    let (kind, serial) = &devices[0];
-    let deck = StreamDeck::connect(&hid, *kind, serial)?;
-    let device_kind = *kind;
+    for (kind, serial) in list_devices(&hid) {
+        let running = state
+            .devices
+            .lock()
+            .map(|devices| devices.iter().any(|d| d.serial == serial))
+            .unwrap_or(true);
+        if running {
+            continue;
+        }
+        let Ok(device) = StreamDeck::connect(&hid, kind, &serial) else {
+            continue;
+        };
 
-    // Update device info state
-    let info = DeviceInfo::from_kind(device_kind);
-    {
-        if let Ok(mut state) = device_info_state.lock() {
-            *state = Some(info.clone());
+        // Update device info state
+        let info = DeviceInfo {
+            serial: serial.clone(),
+            ..DeviceInfo::from_kind(kind)
+        };
+        if let Ok(mut devices) = state.devices.lock() {
+            devices.push(info.clone());
+            devices.sort_by(|a, b| a.serial.cmp(&b.serial));
         }
-    }
 
-    // Emit connection event
-    emit_connection_status(app, true, Some(info.model.clone()));
+        // Emit connection event
+        emit_connection_status(app, &serial, true, Some(info.model.clone()));
 
-    // Create renderers
-    let button_renderer = match create_button_renderer(device_kind) {
-        Ok(r) => Some(r),
-        Err(e) => {
-            eprintln!("Failed to create button renderer: {e}");
-            None
-        }
-    };
+        // Create renderers
+        let button_renderer = match create_button_renderer(kind) {
+            Ok(Some(r)) => Some(r),
+            Ok(None) => None,
+            Err(e) => {
+                eprintln!("Failed to create button renderer: {e}");
+                None
+            }
+        };
 
-    let lcd_renderer = match create_lcd_renderer(device_kind) {
-        Ok(Some(r)) => Some(r),
-        Ok(None) => None,
-        Err(e) => {
-            eprintln!("Failed to create LCD renderer: {e}");
-            None
-        }
-    };
+        let lcd_renderer = match create_lcd_renderer(kind) {
+            Ok(Some(r)) => Some(r),
+            Ok(None) => None,
+            Err(e) => {
+                eprintln!("Failed to create LCD renderer: {e}");
+                None
+            }
+        };
 
-    Ok((deck, device_kind, button_renderer, lcd_renderer))
+        let deck = Deck {
+            serial,
+            kind,
+            device,
+            button_renderer,
+            lcd_renderer,
+            flashes: Vec::new(),
+            live_values: Vec::new(),
+            animations: HashMap::new(),
+            sent: HashMap::new(),
+        };
+        let app = app.clone();
+        let state = state.clone();
+        std::thread::spawn(move || run_deck(&app, &state, deck));
+    }
+    Ok(())
+}
+
+/// Run one deck until it disconnects, then forget it
+fn run_deck(app: &AppHandle, state: &AppState, mut deck: Deck) {
+    let disconnect_reason = run_event_loop(app, state, &mut deck);
+
+    eprintln!(
+        "Stream Deck {} disconnected: {}",
+        deck.serial, disconnect_reason
+    );
+    emit_connection_status(app, &deck.serial, false, None);
+    navigation::close_navigation_queue(&deck.serial);
+    DIRTY_KEYS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&deck.serial);
+    take_action_results(&deck.serial);
+
+    // Clear device info
+    if let Ok(mut devices) = state.devices.lock() {
+        devices.retain(|d| d.serial != deck.serial);
+    }
 }
 
 /// Run the main event loop, returns error message when disconnected
-fn run_event_loop(
-    app: &AppHandle,
-    deck: &mut StreamDeck,
-    device_kind: Kind,
-    button_renderer: &Option<ButtonRenderer>,
-    lcd_renderer: &Option<LcdRenderer>,
-    bindings_state: &Arc<Mutex<Vec<Binding>>>,
-    system_state: &Arc<Mutex<SystemState>>,
-    current_page: &Arc<Mutex<usize>>,
-    plugin_registry: &Arc<PluginRegistry>,
-) -> String {
-    let mut processor = InputProcessor::default();
+fn run_event_loop(app: &AppHandle, state: &AppState, deck: &mut Deck) -> String {
+    let mut processor = if deck.kind.is_visual() {
+        InputProcessor::default()
+    } else {
+        InputProcessor::pedal()
+    };
+    // Nothing synced yet, so the first iteration draws the deck
+    let mut synced_generation = None;
+    let mut live_checked_at = Instant::now();
+    // Page the chord recognition was last set up for
+    let mut chord_page = None;
 
     loop {
-        // Check for image sync requests
-        if SYNC_IMAGES_FLAG.swap(false, Ordering::SeqCst) {
-            let bindings = bindings_state.lock().ok();
-            let state = system_state.lock().ok();
-            let page = *current_page.lock().unwrap_or_else(|e| e.into_inner());
-            if let (Some(bindings), Some(state)) = (bindings, state) {
-                if let Some(ref renderer) = button_renderer {
-                    sync_button_images(deck, &bindings, renderer, device_kind, &state, page, plugin_registry);
-                }
-                if let Some(ref renderer) = lcd_renderer {
-                    sync_lcd_images(deck, &bindings, renderer, device_kind, &state, page, plugin_registry);
-                }
+        // Apply page and folder navigation queued by capability handlers and the focus watcher
+        let requests = take_navigation_requests(&deck.serial);
+        if !requests.is_empty() && apply_navigation(app, state, &deck.serial, &requests) {
+            synced_generation = None;
+        }
+
+        // Keys plugins marked dirty, plus keys whose result badge came or went
+        let mut dirty = take_dirty_keys(&deck.serial);
+
+        // Show finished actions: emit them and badge their keys for a moment
+        dirty.extend(show_action_results(app, deck));
+
+        // Re-draw when a value shown by a label template or gauge changes.
+        // State changes request a sync on their own; this catches the rest
+        let has_live_values =
+            !deck.live_values.is_empty() || deck.animations.values().any(|a| a.gauge.is_some());
+        if has_live_values && live_checked_at.elapsed() >= LIVE_VALUE_INTERVAL {
+            live_checked_at = Instant::now();
+            update_animated_gauges(state, deck);
+            if live_values_changed(state, deck) {
+                synced_generation = None;
             }
         }
 
+        // Check for image sync requests; dirty keys are redrawn either way
+        let generation = SYNC_GENERATION.load(Ordering::SeqCst);
+        if synced_generation != Some(generation) {
+            synced_generation = Some(generation);
+            sync_images(state, deck, &dirty, true);
+        } else if !dirty.is_empty() {
+            sync_images(state, deck, &dirty, false);
+        }
+
+        // Push due animation frames, and wake up in time for the next one
+        advance_animations(deck);
+        let timeout = deck
+            .animations
+            .values()
+            .filter_map(|animation| animation.next_at)
+            .min()
+            .map_or(INPUT_POLL_TIMEOUT, |due| {
+                due.saturating_duration_since(Instant::now())
+                    .clamp(Duration::from_millis(1), INPUT_POLL_TIMEOUT)
+            });
+
         // Read input with timeout
-        let input = match deck.read_input(Some(INPUT_POLL_TIMEOUT)) {
+        let input = match deck.device.read_input(Some(timeout)) {
             Ok(input) => input,
             Err(e) => {
                 // Device disconnected or error
@@ -197,122 +470,450 @@ fn run_event_loop(
         };
 
         // Get current page first (quick lock)
-        let page = *current_page.lock().unwrap_or_else(|e| e.into_inner());
+        let nav = state.navigation_for(&deck.serial);
 
-        // Get bindings for current page only (avoids cloning entire vector)
-        // Only clone bindings that match the current page
-        let page_bindings: Vec<Binding> = bindings_state
+        // Get bindings for this deck's current page only (avoids cloning entire vector),
+        // tagged with the deck so handlers know where they were pressed
+        // (inside a folder the back key replaces whatever is bound there)
+        let page_bindings: Vec<Binding> = state
+            .bindings
             .lock()
             .ok()
-            .map(|b| b.iter().filter(|binding| binding.page == page).cloned().collect())
+            .map(|b| {
+                b.iter()
+                    .filter(|binding| binding.on_device(&deck.serial))
+                    .filter(|binding| {
+                        nav.shows(binding) && !(nav.in_folder() && is_back_key(binding))
+                    })
+                    .map(|binding| Binding {
+                        device: Some(deck.serial.clone()),
+                        ..binding.clone()
+                    })
+                    .collect()
+            })
             .unwrap_or_default();
 
+        // Keep gesture and chord recognition in sync with the bindings on this page;
+        // presses held back for a chord on the previous page are dropped
+        let page = (nav.folder().map(String::from), nav.page());
+        if chord_page.as_ref() != Some(&page) {
+            processor.clear_held_presses();
+            chord_page = Some(page);
+        }
+        processor.set_gesture_configs(gesture_configs(&page_bindings));
+        processor.set_chords(chord_sets(&page_bindings));
+
+        let serial = deck.serial.as_str();
+        let system_state = &state.system_state;
+        let plugin_registry = &state.plugin_registry;
         match input {
             StreamDeckInput::ButtonStateChange(states) => {
                 for event in processor.process_buttons(&states) {
-                    emit_event(app, event.clone());
-                    handle_logical_event(event, &page_bindings, system_state, plugin_registry);
+                    emit_event(app, serial, event.clone());
+                    if nav.in_folder() && is_back_press(&event) {
+                        navigation::request_navigation(Some(serial), NavigationRequest::Back);
+                        continue;
+                    }
+                    handle_logical_event(
+                        serial,
+                        event,
+                        &page_bindings,
+                        system_state,
+                        plugin_registry,
+                    );
                 }
             }
 
             StreamDeckInput::EncoderTwist(deltas) => {
                 for event in processor.process_encoders(&deltas) {
-                    emit_event(app, event.clone());
-                    handle_logical_event(event, &page_bindings, system_state, plugin_registry);
+                    emit_event(app, serial, event.clone());
+                    handle_logical_event(
+                        serial,
+                        event,
+                        &page_bindings,
+                        system_state,
+                        plugin_registry,
+                    );
                 }
             }
 
             StreamDeckInput::TouchScreenSwipe(start, end) => {
                 let event = processor.process_swipe(start, end);
-                emit_event(app, event.clone());
+                emit_event(app, serial, event.clone());
 
                 #[cfg(debug_assertions)]
                 eprintln!("Swipe detected: start={:?}, end={:?}", start, end);
 
                 // Check for page navigation swipe
                 if let Some(direction) = detect_swipe_direction(start, end) {
-                    // Need all bindings for max page calculation (not just current page)
-                    let max_binding_page = bindings_state
-                        .lock()
-                        .ok()
-                        .map(|b| get_max_page(&b))
-                        .unwrap_or(0);
-                    // Allow navigation to one empty page beyond the last page with bindings
-                    // e.g., if bindings on pages 0,1 -> can navigate to 0, 1, 2 (empty)
-                    let max_allowed_page = max_binding_page + 1;
-                    let page_count = max_allowed_page + 1; // Total pages for display
-
-                    #[cfg(debug_assertions)]
-                    eprintln!("Swipe direction: {:?}, current_page={}, max_binding_page={}, max_allowed={}", direction, page, max_binding_page, max_allowed_page);
-
                     // Linear navigation - no wrapping
-                    let new_page = match direction {
-                        SwipeDirection::Left => {
-                            // Swipe left = next page (capped at max)
-                            if page >= max_allowed_page { page } else { page + 1 }
-                        }
-                        SwipeDirection::Right => {
-                            // Swipe right = previous page (capped at 0)
-                            if page == 0 { 0 } else { page - 1 }
-                        }
+                    let request = match direction {
+                        // Swipe left = next page
+                        SwipeDirection::Left => NavigationRequest::NextPage,
+                        // Swipe right = previous page
+                        SwipeDirection::Right => NavigationRequest::PreviousPage,
                     };
 
                     #[cfg(debug_assertions)]
-                    eprintln!("Page change: {} -> {}", page, new_page);
-
-                    // Only update if page actually changed
-                    if new_page != page {
-                        if let Ok(mut p) = current_page.lock() {
-                            *p = new_page;
-                        }
-                        emit_page_change(app, new_page, page_count);
-                        request_image_sync();
+                    eprintln!("Swipe direction: {:?}", direction);
+
+                    if apply_navigation(app, state, serial, &[request]) {
+                        synced_generation = None;
                     }
                 } else {
                     #[cfg(debug_assertions)]
                     eprintln!("Swipe too short or vertical, not a page change");
                     // Not a page navigation swipe, handle normally
-                    handle_logical_event(event, &page_bindings, system_state, plugin_registry);
+                    handle_logical_event(
+                        serial,
+                        event,
+                        &page_bindings,
+                        system_state,
+                        plugin_registry,
+                    );
                 }
             }
 
+            StreamDeckInput::TouchScreenPress(x, y) => {
+                let section = touch_section_for_kind(deck.kind, x);
+                let event = processor.process_touch_tap((x, y), section);
+                emit_event(app, serial, event.clone());
+                handle_logical_event(serial, event, &page_bindings, system_state, plugin_registry);
+            }
+
+            StreamDeckInput::TouchScreenLongPress(x, y) => {
+                let section = touch_section_for_kind(deck.kind, x);
+                let event = processor.process_touch_long_press((x, y), section);
+                emit_event(app, serial, event.clone());
+                handle_logical_event(serial, event, &page_bindings, system_state, plugin_registry);
+            }
+
             StreamDeckInput::EncoderStateChange(states) => {
                 #[cfg(debug_assertions)]
                 println!("RAW encoder state: {:?}", states);
 
                 for event in processor.process_encoder_presses(&states) {
-                    emit_event(app, event.clone());
-                    handle_logical_event(event, &page_bindings, system_state, plugin_registry);
+                    emit_event(app, serial, event.clone());
+                    handle_logical_event(
+                        serial,
+                        event,
+                        &page_bindings,
+                        system_state,
+                        plugin_registry,
+                    );
                 }
             }
 
             _ => {}
         }
+
+        // Time-based gestures (long-press, auto-repeat, delayed taps, expired chords)
+        for event in processor.tick(Instant::now()) {
+            emit_event(app, serial, event.clone());
+            handle_logical_event(serial, event, &page_bindings, system_state, plugin_registry);
+        }
+    }
+}
+
+/// Emit queued action results for a deck and add badges for those that
+/// flash, dropping expired badges. Returns the keys that need redrawing.
+fn show_action_results(app: &AppHandle, deck: &mut Deck) -> Vec<DirtyKey> {
+    let now = Instant::now();
+    let mut changed = Vec::new();
+    for action in take_action_results(&deck.serial) {
+        emit_action_result(app, &deck.serial, &action);
+        let InputRef::Button { index } = action.binding.input else {
+            continue;
+        };
+        if action.flash {
+            let flash = Flash {
+                folder: action.binding.folder,
+                page: action.binding.page,
+                index,
+                ok: action.result.is_ok(),
+                until: now + FLASH_DURATION,
+            };
+            deck.flashes.retain(|f| {
+                (&f.folder, f.page, f.index) != (&flash.folder, flash.page, flash.index)
+            });
+            changed.push(flash.key());
+            deck.flashes.push(flash);
+        }
+    }
+
+    deck.flashes.retain(|flash| {
+        if flash.until <= now {
+            changed.push(flash.key());
+        }
+        flash.until > now
+    });
+    changed
+}
+
+/// Push the next frame of every animation that is due on the current page.
+fn advance_animations(deck: &mut Deck) {
+    let Some(renderer) = &deck.button_renderer else {
+        return;
+    };
+    let now = Instant::now();
+    let mut changed = false;
+    for animation in deck.animations.values_mut() {
+        if !animation.advance(now) {
+            continue;
+        }
+        let frame = animation.frames[animation.frame].image.clone();
+        let mut img = renderer.render_with_image(&animation.binding, frame);
+        if let Some(gauge) = &animation.gauge {
+            img = gauge.draw(renderer, img);
+        }
+        if let Some(flash) = deck
+            .flashes
+            .iter()
+            .find(|flash| flash.is_for(&animation.binding))
+        {
+            let mut rgba = img.to_rgba8();
+            renderer.add_result_badge(&mut rgba, flash.ok);
+            img = DynamicImage::ImageRgba8(rgba);
+        }
+        if let Err(e) = send_key_image(&deck.device, &mut deck.sent, animation.key, Some(img)) {
+            eprintln!(
+                "Failed to set button {} animation frame: {e}",
+                animation.key
+            );
+        }
+        changed = true;
+    }
+
+    if changed {
+        if let Err(e) = deck.device.flush() {
+            eprintln!("Failed to flush animation frames: {e}");
+        }
     }
 }
 
-/// Get the maximum page number from bindings (0 if no bindings)
-fn get_max_page(bindings: &[Binding]) -> usize {
-    bindings.iter().map(|b| b.page).max().unwrap_or(0)
+/// Encoder section under a touch-strip x-coordinate (same layout as `sync_lcd_images`).
+fn touch_section_for_kind(kind: Kind, x: u16) -> usize {
+    let section_w = encoder_lcd_size_for_kind(kind).map(|(w, _)| w).unwrap_or(0);
+    touch_section(x, section_w, kind.encoder_count() as usize)
+}
+
+/// Get the number of pages in the open folder (at least 1)
+fn get_page_count(pages: &[Page], bindings: &[Binding], nav: &Navigation) -> usize {
+    nav.pages_here(pages, bindings).len()
+}
+
+/// Apply navigation requests to one deck, emitting a page change if it moved.
+/// Returns whether the deck needs redrawing.
+fn apply_navigation(
+    app: &AppHandle,
+    state: &AppState,
+    serial: &str,
+    requests: &[NavigationRequest],
+) -> bool {
+    let bindings = state
+        .bindings
+        .lock()
+        .map(|b| for_device(&b, serial))
+        .unwrap_or_default();
+    let pages = state
+        .pages
+        .lock()
+        .map(|p| for_device(&p, serial))
+        .unwrap_or_default();
+    let mut navs = state.navigation.lock().unwrap_or_else(|e| e.into_inner());
+    let nav = navs.entry(serial.to_string()).or_default();
+    let mut changed = false;
+    for request in requests {
+        changed |= nav.apply(request, &pages, &bindings);
+    }
+    if changed {
+        #[cfg(debug_assertions)]
+        eprintln!(
+            "Page change on {}: {:?} page {}",
+            serial,
+            nav.folder(),
+            nav.page()
+        );
+
+        emit_page_change(app, serial, nav, get_page_count(&pages, &bindings, nav));
+    }
+    changed
+}
+
+/// Redraw one deck's keys and LCD strip for its current page (`full`), or
+/// only the `dirty` ones. Dirty keys are re-sent even if they look unchanged.
+fn sync_images(state: &AppState, deck: &mut Deck, dirty: &[DirtyKey], full: bool) {
+    let nav = state.navigation_for(&deck.serial);
+    let dirty: Vec<Surface> = dirty
+        .iter()
+        .filter(|key| key.folder.as_deref() == nav.folder() && key.page == nav.page())
+        .map(|key| key.surface)
+        .collect();
+    if !full && dirty.is_empty() {
+        return;
+    }
+    for surface in &dirty {
+        deck.sent.remove(surface);
+    }
+    let keys: Vec<u8> = dirty
+        .iter()
+        .filter_map(|surface| match surface {
+            Surface::Key(key) => Some(*key),
+            Surface::LcdSection(_) => None,
+        })
+        .collect();
+    let lcd = full
+        || dirty
+            .iter()
+            .any(|surface| matches!(surface, Surface::LcdSection(_)));
+
+    let bindings = state
+        .bindings
+        .lock()
+        .map(|b| on_deck(&b, &deck.serial))
+        .unwrap_or_default();
+    let pages = state
+        .pages
+        .lock()
+        .map(|p| for_device(&p, &deck.serial))
+        .unwrap_or_default();
+    if let Ok(system_state) = state.system_state.lock() {
+        let registry = &state.plugin_registry;
+        if full || !keys.is_empty() {
+            let keys = (!full).then_some(keys.as_slice());
+            sync_button_images(deck, &bindings, &pages, &nav, &system_state, registry, keys);
+        }
+        if lcd {
+            sync_lcd_images(deck, &bindings, &pages, &nav, &system_state, registry);
+        }
+        deck.live_values = live_values(&bindings, &nav, &deck.animations, &system_state, registry);
+    }
+}
+
+/// Shown labels with placeholders on the current page, resolved, and the
+/// values of its gauges. Gauges on animated keys are left out: they're
+/// updated in place (see `update_animated_gauges`).
+fn live_values(
+    bindings: &[Binding],
+    nav: &Navigation,
+    animations: &HashMap<KeySlot, KeyAnimation>,
+    system_state: &SystemState,
+    registry: &PluginRegistry,
+) -> Vec<String> {
+    let labels = bindings
+        .iter()
+        .filter(|b| nav.shows(b) && b.show_label.unwrap_or(false))
+        .filter_map(|b| {
+            let label = appearance(b, system_state, registry).label;
+            let label = label.filter(|label| label_template::has_placeholders(label))?;
+            Some(registry.resolve_label(b, label, system_state))
+        });
+    let gauges = bindings
+        .iter()
+        .filter(|b| nav.shows(b) && b.gauge.is_some())
+        .filter(|b| key_slot(b).is_none_or(|slot| !animations.contains_key(&slot)))
+        .map(|b| {
+            registry
+                .binding_value(b, system_state)
+                .map_or(String::new(), |value| value.value.to_string())
+        });
+    labels.chain(gauges).collect()
+}
+
+/// Whether a label template or gauge on the current page shows a different
+/// value than when the deck was last drawn.
+fn live_values_changed(state: &AppState, deck: &Deck) -> bool {
+    let bindings = state
+        .bindings
+        .lock()
+        .map(|b| on_deck(&b, &deck.serial))
+        .unwrap_or_default();
+    let nav = state.navigation_for(&deck.serial);
+    let Ok(system_state) = state.system_state.lock() else {
+        return false;
+    };
+    let registry = &state.plugin_registry;
+    live_values(&bindings, &nav, &deck.animations, &system_state, registry) != deck.live_values
+}
+
+/// Give animated keys' gauges their current value and color, drawn from the
+/// next frame on, so a value change doesn't resync the key and restart it.
+fn update_animated_gauges(state: &AppState, deck: &mut Deck) {
+    let Ok(system_state) = state.system_state.lock() else {
+        return;
+    };
+    let registry = &state.plugin_registry;
+    for animation in deck.animations.values_mut() {
+        let Some(gauge) = &mut animation.gauge else {
+            continue;
+        };
+        if let Some(value) = registry.binding_value(&animation.binding, &system_state) {
+            gauge.value = value;
+        }
+        gauge.color = gauge_color(&animation.binding, &system_state, registry);
+    }
+}
+
+/// Whether a binding sits on the key reserved for "back" inside folders
+fn is_back_key(binding: &Binding) -> bool {
+    matches!(binding.input, InputRef::Button { index } if index == BACK_KEY)
+}
+
+/// Whether an event is a press of the back key
+fn is_back_press(event: &LogicalEvent) -> bool {
+    matches!(event, LogicalEvent::Button(e) if e.index == BACK_KEY && e.pressed)
 }
 
 /// Emit page change event to frontend
-fn emit_page_change(app: &AppHandle, page: usize, page_count: usize) {
-    let _ = app.emit("streamdeck:page", PageChangeEvent { page, page_count });
+fn emit_page_change(app: &AppHandle, serial: &str, nav: &Navigation, page_count: usize) {
+    let current = nav.current_page();
+    let _ = app.emit(
+        "streamdeck:page",
+        PageChangeEvent {
+            device: serial.to_string(),
+            page: current.page,
+            page_count,
+            folder: current.folder,
+            breadcrumb: current.breadcrumb,
+        },
+    );
 }
 
 /// Emit connection status event to frontend
-fn emit_connection_status(app: &AppHandle, connected: bool, model: Option<String>) {
+fn emit_connection_status(app: &AppHandle, serial: &str, connected: bool, model: Option<String>) {
     let _ = app.emit(
         "streamdeck:connection",
-        ConnectionStatusEvent { connected, model },
+        ConnectionStatusEvent {
+            device: serial.to_string(),
+            connected,
+            model,
+        },
+    );
+}
+
+/// Emit an action result event to frontend
+fn emit_action_result(app: &AppHandle, serial: &str, action: &ActionResult) {
+    let _ = app.emit(
+        "action:result",
+        ActionResultEvent {
+            device: serial.to_string(),
+            input: action.binding.input.clone(),
+            page: action.binding.page,
+            folder: action.binding.folder.clone(),
+            capability: action.binding.capability.clone(),
+            ok: action.result.is_ok(),
+            error: action.result.clone().err(),
+        },
     );
 }
 
-/// Create a button renderer for the given device kind.
-fn create_button_renderer(kind: Kind) -> Result<ButtonRenderer> {
+/// Create a button renderer for the given device kind (if it has key images).
+fn create_button_renderer(kind: Kind) -> Result<Option<ButtonRenderer>> {
+    if !kind.is_visual() {
+        return Ok(None);
+    }
     let (w, h) = button_size_for_kind(kind);
-    ButtonRenderer::new(w, h)
+    Ok(Some(ButtonRenderer::new(w, h)?))
 }
 
 /// Create an LCD renderer for the given device kind (if it has an LCD strip).
@@ -323,84 +924,265 @@ fn create_lcd_renderer(kind: Kind) -> Result<Option<LcdRenderer>> {
     }
 }
 
-/// Get the effective image and color for a binding based on current system state.
-/// Uses the plugin registry to determine active state.
-/// Returns (image_path, icon_color) - using alt variants when in active state.
-fn get_effective_image_and_color<'a>(
+/// How a binding looks in its current state
+#[derive(Debug, Clone, Copy)]
+struct Appearance<'a> {
+    image: Option<&'a str>,
+    color: Option<&'a str>,
+    background_color: Option<&'a str>,
+    label: Option<&'a str>,
+}
+
+impl Appearance<'_> {
+    /// `binding` as drawn with this look and `label` (already resolved)
+    fn apply(&self, binding: &Binding, label: Option<String>) -> Binding {
+        Binding {
+            button_image: self.image.map(String::from),
+            button_image_alt: None, // Not needed for rendering
+            icon_color: self.color.map(String::from),
+            icon_color_alt: None, // Not needed for rendering
+            background_color: self.background_color.map(String::from),
+            label,
+            ..binding.clone()
+        }
+    }
+}
+
+/// The style a binding sets for the state its plugin reports, if any.
+fn state_style<'a>(
     binding: &'a Binding,
     state: &SystemState,
-    registry: &PluginRegistry
-) -> (Option<&'a str>, Option<&'a str>) {
-    // Check if this capability has an "active" state via plugin
-    let is_active = registry.is_binding_active(binding, state);
+    registry: &PluginRegistry,
+) -> Option<&'a StateStyle> {
+    if binding.states.is_empty() {
+        return None;
+    }
+    registry
+        .binding_state(binding, state)
+        .and_then(|key| binding.states.get(&key))
+}
 
-    // If active and we have an alt image, use alt image and alt color
-    if is_active {
-        if let Some(ref alt) = binding.button_image_alt {
-            let color = binding.icon_color_alt.as_deref()
-                .or(binding.icon_color.as_deref()); // Fall back to default color if no alt color
-            return (Some(alt.as_str()), color);
-        }
+/// Get the effective look of a binding based on current system state: the
+/// style of its current named state (which includes the alt image, see
+/// `Binding::add_alt_states`) over its own.
+fn appearance<'a>(
+    binding: &'a Binding,
+    state: &SystemState,
+    registry: &PluginRegistry,
+) -> Appearance<'a> {
+    let base = Appearance {
+        image: binding.button_image.as_deref(),
+        color: binding.icon_color.as_deref(),
+        background_color: binding.background_color.as_deref(),
+        label: binding.label.as_deref(),
+    };
+
+    if let Some(style) = state_style(binding, state, registry) {
+        return Appearance {
+            image: style.button_image.as_deref().or(base.image),
+            color: style.icon_color.as_deref().or(base.color),
+            background_color: style.background_color.as_deref().or(base.background_color),
+            label: style.label.as_deref().or(base.label),
+        };
     }
 
-    // Otherwise use the default image and default color
-    (binding.button_image.as_deref(), binding.icon_color.as_deref())
+    base
 }
 
-/// Sync all button images from bindings to hardware.
+/// Color of a binding's gauge: its current state's color (e.g., the alternate
+/// icon color while muted), else its icon color.
+fn gauge_color(
+    binding: &Binding,
+    state: &SystemState,
+    registry: &PluginRegistry,
+) -> Option<String> {
+    state_style(binding, state, registry)
+        .and_then(|style| style.icon_color.clone())
+        .or_else(|| binding.icon_color.clone())
+}
+
+/// Which of several bindings on the same key provides its image (lower wins).
+/// Plain and tap bindings are the key's primary action.
+fn display_priority(binding: &Binding) -> u8 {
+    match binding.gesture {
+        None | Some(Gesture::Tap) => 0,
+        Some(Gesture::HoldRepeat { .. }) => 1,
+        Some(Gesture::LongPress { .. }) => 2,
+        Some(Gesture::DoubleTap) => 3,
+    }
+}
+
+/// Sync button images from bindings to hardware: every key, or only `keys`.
+/// Keys already showing their image aren't sent again.
 fn sync_button_images(
-    deck: &mut StreamDeck,
+    deck: &mut Deck,
     bindings: &[Binding],
-    renderer: &ButtonRenderer,
-    kind: Kind,
+    pages: &[Page],
+    nav: &Navigation,
     state: &SystemState,
-    current_page: usize,
     registry: &PluginRegistry,
+    keys: Option<&[u8]>,
 ) {
+    let Some(renderer) = &deck.button_renderer else {
+        return;
+    };
+    let kind = deck.kind;
+    let flashes = &deck.flashes;
+    let animations = &mut deck.animations;
+    let sent = &mut deck.sent;
+    let deck = &deck.device;
     let button_count = kind.key_count();
+    let wanted = |key: u8| key < button_count && keys.is_none_or(|keys| keys.contains(&key));
+
+    // Forget animations whose binding is gone; the rest pause unless shown below
+    if keys.is_none() {
+        animations.retain(|slot, _| bindings.iter().any(|b| key_slot(b).as_ref() == Some(slot)));
+    }
+    let mut animated = Vec::new();
+
+    // Blank buttons show the page background, if any
+    let background = nav.background(pages).and_then(|source| {
+        renderer
+            .render_background(source)
+            .map_err(|e| eprintln!("Failed to render page background: {e}"))
+            .ok()
+    });
 
     // Track which buttons have been set
     let mut buttons_set = vec![false; button_count as usize];
 
-    // Filter to current page
-    for binding in bindings.iter().filter(|b| b.page == current_page) {
+    // Inside a folder the back key replaces whatever is bound there
+    if nav.in_folder() && wanted(BACK_KEY as u8) {
+        let img = renderer.create_back_button();
+        if let Err(e) = send_key_image(deck, sent, BACK_KEY as u8, Some(img)) {
+            eprintln!("Failed to set back button image: {e}");
+        } else {
+            buttons_set[BACK_KEY] = true;
+        }
+    }
+
+    // Filter to current page, one binding per key (a key may carry several gestures)
+    let mut key_bindings: HashMap<usize, &Binding> = HashMap::new();
+    for binding in bindings
+        .iter()
+        .filter(|b| nav.shows(b) && !(nav.in_folder() && is_back_key(b)))
+    {
+        if let InputRef::Button { index } = &binding.input {
+            match key_bindings.get(index) {
+                Some(existing) if display_priority(existing) <= display_priority(binding) => {}
+                _ => {
+                    key_bindings.insert(*index, binding);
+                }
+            }
+        }
+    }
+
+    for binding in key_bindings.into_values() {
         if let InputRef::Button { index } = &binding.input {
             let key = *index as u8;
-            if key >= button_count {
+            if !wanted(key) {
                 continue;
             }
 
-            // Get effective image and color based on state
-            let (effective_image, effective_color) = get_effective_image_and_color(binding, state, registry);
+            // Get effective image, colors and label based on state
+            let look = appearance(binding, state, registry);
+
+            // Live text (e.g., command output) replaces the label
+            let display_text = registry.binding_display_text(binding, state);
 
-            // Create a modified binding with the effective image and color for rendering
+            // Create a modified binding with the effective look for rendering
+            let label = display_text.clone().or_else(|| {
+                look.label
+                    .map(|label| registry.resolve_label(binding, label, state))
+            });
             let render_binding = Binding {
-                input: binding.input.clone(),
-                capability: binding.capability.clone(),
-                page: binding.page,
-                icon: binding.icon.clone(),
-                label: binding.label.clone(),
-                button_image: effective_image.map(String::from),
-                button_image_alt: None, // Not needed for rendering
-                show_label: binding.show_label,
-                icon_color: effective_color.map(String::from),
-                icon_color_alt: None, // Not needed for rendering
+                show_label: if display_text.is_some() {
+                    Some(true)
+                } else {
+                    binding.show_label
+                },
+                ..look.apply(binding, label)
             };
 
-            match renderer.render_binding(&render_binding) {
+            let key_gauge = binding.gauge.and_then(|gauge| {
+                Some(KeyGauge {
+                    gauge,
+                    value: registry.binding_value(binding, state)?,
+                    color: gauge_color(binding, state, registry),
+                })
+            });
+
+            // Animated images start (or resume) their timer; the rest are drawn once.
+            // Without an image, live text is drawn as a text-only key
+            let slot = (binding.folder.clone(), binding.page, *index);
+            let rendered = match renderer.load_animation(&render_binding) {
+                Ok(Some(frames)) => {
+                    // Keep the position of an animation that was already running
+                    let previous = animations
+                        .remove(&slot)
+                        .filter(|a| a.binding.button_image == render_binding.button_image);
+                    let frame = previous.as_ref().map_or(0, |a| a.frame % frames.len());
+                    let next_at = previous
+                        .and_then(|a| a.next_at)
+                        .unwrap_or_else(|| Instant::now() + frames[frame].delay);
+                    let img =
+                        renderer.render_with_image(&render_binding, frames[frame].image.clone());
+                    let animation = KeyAnimation {
+                        key,
+                        binding: render_binding,
+                        frames,
+                        frame,
+                        next_at: Some(next_at),
+                        gauge: key_gauge.clone(),
+                    };
+                    animations.insert(slot.clone(), animation);
+                    animated.push(slot);
+                    Ok(Some(img))
+                }
+                result => {
+                    // A GIF that won't decode as an animation may still load as a still
+                    if let Err(e) = result {
+                        eprintln!("Failed to load button {key} animation: {e}");
+                    }
+                    animations.remove(&slot);
+                    renderer.render_binding(&render_binding)
+                }
+            };
+
+            // A gauge goes over the key's image, or the page background without one
+            let rendered = match (rendered, &key_gauge) {
+                (Ok(img), Some(gauge)) => {
+                    let img = img.or_else(|| background.clone()).unwrap_or_else(|| {
+                        renderer.create_solid_button(Rgba([0, 0, 0, 255]), None)
+                    });
+                    Ok(Some(gauge.draw(renderer, img)))
+                }
+                (result, _) => result,
+            };
+
+            // A recent action result is badged over whatever the key shows
+            let rendered = match (rendered, flashes.iter().find(|flash| flash.is_for(binding))) {
+                (Ok(img), Some(flash)) => {
+                    let img = img.or_else(|| background.clone()).unwrap_or_else(|| {
+                        renderer.create_solid_button(Rgba([0, 0, 0, 255]), None)
+                    });
+                    let mut rgba = img.to_rgba8();
+                    renderer.add_result_badge(&mut rgba, flash.ok);
+                    Ok(Some(DynamicImage::ImageRgba8(rgba)))
+                }
+                (result, _) => result,
+            };
+
+            match rendered {
                 Ok(Some(img)) => {
-                    if let Err(e) = deck.set_button_image(key, img) {
+                    if let Err(e) = send_key_image(deck, sent, key, Some(img)) {
                         eprintln!("Failed to set button {key} image: {e}");
                     } else {
                         buttons_set[*index] = true;
                     }
                 }
                 Ok(None) => {
-                    // No button_image configured, clear the button
-                    if let Err(e) = deck.clear_button_image(key) {
-                        eprintln!("Failed to clear button {key}: {e}");
-                    }
-                    buttons_set[*index] = true;
+                    // No button_image configured, left blank below
                 }
                 Err(e) => {
                     eprintln!("Failed to render button {key} image: {e}");
@@ -409,12 +1191,21 @@ fn sync_button_images(
         }
     }
 
-    // Clear buttons that don't have bindings
+    // Animations on other pages keep their frame until their page is back
+    if keys.is_none() {
+        for (slot, animation) in animations.iter_mut() {
+            if !animated.contains(slot) {
+                animation.next_at = None;
+            }
+        }
+    }
+
+    // Clear buttons that don't have an image
     for (index, was_set) in buttons_set.iter().enumerate() {
-        if !was_set {
-            let key = index as u8;
-            if let Err(e) = deck.clear_button_image(key) {
-                eprintln!("Failed to clear unbound button {key}: {e}");
+        let key = index as u8;
+        if !was_set && wanted(key) {
+            if let Err(e) = send_key_image(deck, sent, key, background.clone()) {
+                eprintln!("Failed to clear button {key}: {e}");
             }
         }
     }
@@ -426,16 +1217,22 @@ fn sync_button_images(
 }
 
 /// Sync encoder images to the LCD strip.
-/// Priority: EncoderPress image > Encoder (rotation) image > empty
+/// Priority: EncoderPress image > Encoder (rotation) image > EncoderPress label
+/// > Encoder label > empty
 fn sync_lcd_images(
-    deck: &mut StreamDeck,
+    deck: &mut Deck,
     bindings: &[Binding],
-    renderer: &LcdRenderer,
-    kind: Kind,
+    pages: &[Page],
+    nav: &Navigation,
     state: &SystemState,
-    current_page: usize,
     registry: &PluginRegistry,
 ) {
+    let Some(renderer) = &deck.lcd_renderer else {
+        return;
+    };
+    let kind = deck.kind;
+    let sent = &mut deck.sent;
+    let deck = &deck.device;
     let encoder_count = kind.encoder_count();
     if encoder_count == 0 {
         return;
@@ -445,10 +1242,28 @@ fn sync_lcd_images(
         return;
     };
 
+    // Page strip background, cut into one slice per section
+    let backgrounds = nav.lcd_background(pages).and_then(|source| {
+        renderer
+            .render_strip_background(source, encoder_count as u32)
+            .map_err(|e| eprintln!("Failed to load LCD background {source}: {e}"))
+            .ok()
+    });
+
     // Filter to current page
-    let page_bindings: Vec<_> = bindings.iter().filter(|b| b.page == current_page).collect();
+    let page_bindings: Vec<_> = bindings.iter().filter(|b| nav.shows(b)).collect();
 
     for encoder_idx in 0..encoder_count {
+        let background = backgrounds
+            .as_ref()
+            .and_then(|b| b.get(encoder_idx as usize));
+        let empty = || {
+            background.map_or_else(
+                || renderer.create_empty(),
+                |b| DynamicImage::ImageRgba8(b.clone()),
+            )
+        };
+
         // Find the EncoderPress binding for this encoder (primary)
         let press_binding = page_bindings.iter().find(|b| {
             matches!(&b.input, InputRef::EncoderPress { index } if *index == encoder_idx as usize)
@@ -462,74 +1277,64 @@ fn sync_lcd_images(
         // Calculate X position for this encoder section
         let x = (encoder_idx as u32 * section_w) as u16;
 
-        // Determine which binding has an image (considering state)
-        let (binding_to_use, effective_image, effective_color) = {
-            // Try press binding first
-            if let Some(b) = press_binding {
-                let (img, color) = get_effective_image_and_color(b, state, registry);
-                if img.is_some() {
-                    (Some(b), img, color)
-                } else if let Some(rb) = rotate_binding {
-                    let (rimg, rcolor) = get_effective_image_and_color(rb, state, registry);
-                    (Some(rb), rimg, rcolor)
-                } else {
-                    (None, None, None)
-                }
-            } else if let Some(rb) = rotate_binding {
-                let (rimg, rcolor) = get_effective_image_and_color(rb, state, registry);
-                (Some(rb), rimg, rcolor)
-            } else {
-                (None, None, None)
-            }
-        };
-
-        match (binding_to_use, effective_image) {
-            (Some(binding), Some(img_path)) => {
-                // Create a modified binding with the effective image and color for rendering
+        // Prefer a gauge showing the encoder's value (set by rotating, so the
+        // rotation binding comes first), then a binding with an image
+        // (considering state), then one with a label
+        let with_gauge = [rotate_binding, press_binding]
+            .into_iter()
+            .flatten()
+            .find_map(|b| {
+                let gauge = b.gauge?;
+                let value = registry.binding_value(b, state)?;
+                Some((b, gauge, value))
+            });
+        let candidates = [press_binding, rotate_binding];
+        let looks = candidates.map(|b| b.map(|b| (b, appearance(b, state, registry))));
+        let selected = looks
+            .into_iter()
+            .flatten()
+            .find(|(_, look)| look.image.is_some())
+            .or_else(|| {
+                looks.into_iter().flatten().find(|(b, look)| {
+                    b.show_label.unwrap_or(false)
+                        && look.label.is_some_and(|l| !l.trim().is_empty())
+                })
+            });
+
+        let img = match (with_gauge, selected) {
+            (Some((binding, gauge, value)), _) => {
+                let look = appearance(binding, state, registry);
+                let label = look
+                    .label
+                    .map(|label| registry.resolve_label(binding, label, state));
                 let render_binding = Binding {
-                    input: binding.input.clone(),
-                    capability: binding.capability.clone(),
-                    page: binding.page,
-                    icon: binding.icon.clone(),
-                    label: binding.label.clone(),
-                    button_image: Some(img_path.to_string()),
-                    button_image_alt: None,
-                    show_label: binding.show_label,
-                    icon_color: effective_color.map(String::from),
-                    icon_color_alt: None,
+                    icon_color: gauge_color(binding, state, registry),
+                    ..look.apply(binding, label)
                 };
-
-                match renderer.render_binding(&render_binding) {
-                    Ok(Some(img)) => {
-                        match ImageRect::from_image(img) {
-                            Ok(rect) => {
-                                if let Err(e) = deck.write_lcd(x, 0, &rect) {
-                                    eprintln!("Failed to write LCD for encoder {encoder_idx}: {e}");
-                                }
-                            }
-                            Err(e) => {
-                                eprintln!("Failed to convert image to LCD rect for encoder {encoder_idx}: {e}");
-                            }
-                        }
-                    }
-                    Ok(None) => {
-                        let empty = renderer.create_empty();
-                        if let Ok(rect) = ImageRect::from_image(empty) {
-                            let _ = deck.write_lcd(x, 0, &rect);
-                        }
-                    }
+                renderer.render_gauge(&render_binding, &value, gauge, background)
+            }
+            (None, Some((binding, look))) => {
+                // Create a modified binding with the effective look for rendering
+                let label = look
+                    .label
+                    .map(|label| registry.resolve_label(binding, label, state));
+                let render_binding = look.apply(binding, label);
+
+                match renderer.render_binding(&render_binding, background) {
+                    Ok(Some(img)) => img,
+                    Ok(None) => empty(),
                     Err(e) => {
                         eprintln!("Failed to render LCD image for encoder {encoder_idx}: {e}");
+                        continue;
                     }
                 }
             }
-            _ => {
-                // No binding with image, write empty section
-                let empty = renderer.create_empty();
-                if let Ok(rect) = ImageRect::from_image(empty) {
-                    let _ = deck.write_lcd(x, 0, &rect);
-                }
-            }
+            // Nothing to show, write the bare background
+            (None, None) => empty(),
+        };
+
+        if let Err(e) = send_lcd_image(deck, sent, encoder_idx, x, img) {
+            eprintln!("Failed to write LCD for encoder {encoder_idx}: {e}");
         }
     }
 }
@@ -538,14 +1343,50 @@ fn sync_lcd_images(
 /// Plugin handlers are spawned in separate threads to avoid blocking the event loop
 /// (important for network-dependent plugins like OBS/Elgato that may timeout).
 fn handle_logical_event(
+    serial: &str,
     event: LogicalEvent,
     bindings: &[Binding],
     system_state: &Arc<Mutex<SystemState>>,
     plugin_registry: &Arc<PluginRegistry>,
 ) {
     #[cfg(debug_assertions)]
-    eprintln!("handle_logical_event: {:?}, {} bindings on page", event, bindings.len());
+    eprintln!(
+        "handle_logical_event: {:?}, {} bindings on page",
+        event,
+        bindings.len()
+    );
+
+    // A release goes to the bindings its press reached, even if the page has
+    // changed while the key was held (swipe, folder, focus switch), and runs
+    // once the press has been handled. Momentary bindings undo their action
+    // then by pressing the reverse capability; without a reverse the release
+    // reaches the plugin as usual (e.g. to kill a command started by the press).
+    let held_key = event.held_input().map(|input| (serial.to_string(), input));
+    if let Some(press) = event.release_as_press() {
+        let held = held_key
+            .and_then(|key| held_presses().remove(&key))
+            .unwrap_or_default();
+        for HeldPress { binding, handle } in held {
+            let (event, binding) = match binding
+                .capability
+                .reverse()
+                .filter(|_| binding.is_momentary())
+            {
+                Some(capability) => (
+                    press.pedal_as_button(),
+                    Binding {
+                        capability,
+                        ..binding
+                    },
+                ),
+                None => (event.pedal_as_button(), binding),
+            };
+            spawn_handler(event, binding, system_state, plugin_registry, Some(handle));
+        }
+        return;
+    }
 
+    let mut held = Vec::new();
     for binding in bindings {
         if !binding.matches(&event) {
             continue;
@@ -554,37 +1395,88 @@ fn handle_logical_event(
         #[cfg(debug_assertions)]
         eprintln!("  -> matched binding: {:?}", binding.capability);
 
-        // Clone what we need for the spawned thread
-        let event = event.clone();
-        let binding = binding.clone();
-        let system_state = Arc::clone(system_state);
-        let plugin_registry = Arc::clone(plugin_registry);
+        // Gesture and chord bindings reach plugins as a plain press, pedals
+        // as their key, touch-strip bindings as a press of the encoder below,
+        // and encoder rotation scaled by the binding's acceleration curve
+        let handler_event = match &event {
+            LogicalEvent::Encoder(e) => e.accelerated(binding.acceleration.as_ref()),
+            LogicalEvent::Gesture(e) => e.as_button_press(),
+            LogicalEvent::Chord(e) => e.as_button_press(),
+            LogicalEvent::TouchTap(e) | LogicalEvent::TouchLongPress(e) => e.as_encoder_press(),
+            other => other.pedal_as_button(),
+        };
+        let handle = spawn_handler(
+            handler_event,
+            binding.clone(),
+            system_state,
+            plugin_registry,
+            None,
+        );
+        if event.is_press() {
+            held.push(HeldPress {
+                binding: binding.clone(),
+                handle,
+            });
+        }
+    }
+    if let (false, Some(key)) = (held.is_empty(), held_key) {
+        held_presses().insert(key, held);
+    }
+}
 
-        // Spawn handler in separate thread to avoid blocking event loop
-        // This is critical for network-dependent plugins (OBS, Elgato) that may timeout
-        std::thread::spawn(move || {
-            let _handled = plugin_registry.handle_event(&event, &binding, &system_state);
+/// Run a binding's handler on its own thread, after `after` has finished.
+fn spawn_handler(
+    event: LogicalEvent,
+    binding: Binding,
+    system_state: &Arc<Mutex<SystemState>>,
+    plugin_registry: &Arc<PluginRegistry>,
+    after: Option<JoinHandle<()>>,
+) -> JoinHandle<()> {
+    let system_state = Arc::clone(system_state);
+    let plugin_registry = Arc::clone(plugin_registry);
+
+    // Spawn handler in separate thread to avoid blocking event loop
+    // This is critical for network-dependent plugins (OBS, Elgato) that may timeout
+    std::thread::spawn(move || {
+        if let Some(previous) = after {
+            let _ = previous.join();
+        }
+        let outcome = plugin_registry.handle_event(&event, &binding, &system_state);
+        record_outcome(&binding, &outcome);
 
-            #[cfg(debug_assertions)]
-            eprintln!("  -> handled: {}", _handled);
-        });
-    }
+        #[cfg(debug_assertions)]
+        eprintln!("  -> outcome: {:?}", outcome);
+    })
 }
 
-fn emit_event(app: &AppHandle, event: LogicalEvent) {
+fn held_presses() -> MutexGuard<'static, BTreeMap<HeldKey, Vec<HeldPress>>> {
+    HELD_PRESSES.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn emit_event(app: &AppHandle, serial: &str, event: LogicalEvent) {
+    fn emit<T: Serialize + Clone>(app: &AppHandle, name: &str, serial: &str, event: T) {
+        app.emit(
+            name,
+            DeviceEvent {
+                device: serial.to_string(),
+                event,
+            },
+        )
+        .ok();
+    }
+
     match event {
-        LogicalEvent::Button(e) => {
-            app.emit("streamdeck:button", e).ok();
-        }
-        LogicalEvent::Encoder(e) => {
-            app.emit("streamdeck:encoder", e).ok();
-        }
-        LogicalEvent::EncoderPress(e) => {
-            app.emit("streamdeck:encoder-press", e).ok();
-        }
-        LogicalEvent::Swipe(e) => {
-            app.emit("streamdeck:swipe", e).ok();
+        // The app shows pedals as the deck's keys
+        LogicalEvent::Button(e) | LogicalEvent::Pedal(e) => {
+            emit(app, "streamdeck:button", serial, e)
         }
+        LogicalEvent::Encoder(e) => emit(app, "streamdeck:encoder", serial, e),
+        LogicalEvent::EncoderPress(e) => emit(app, "streamdeck:encoder-press", serial, e),
+        LogicalEvent::Swipe(e) => emit(app, "streamdeck:swipe", serial, e),
+        LogicalEvent::Gesture(e) => emit(app, "streamdeck:gesture", serial, e),
+        LogicalEvent::Chord(e) => emit(app, "streamdeck:chord", serial, e),
+        LogicalEvent::TouchTap(e) => emit(app, "streamdeck:touch-tap", serial, e),
+        LogicalEvent::TouchLongPress(e) => emit(app, "streamdeck:touch-long-press", serial, e),
     }
 }
 
@@ -592,97 +1484,307 @@ fn emit_event(app: &AppHandle, event: LogicalEvent) {
 mod tests {
     use super::*;
     use crate::capability::Capability;
+    use crate::events::ButtonEvent;
+    use crate::plugin::{ActionOutcome, CapabilityMetadata, Plugin};
 
     #[test]
-    fn test_get_max_page_empty() {
+    fn test_get_page_count_empty() {
         let bindings: Vec<Binding> = vec![];
-        assert_eq!(get_max_page(&bindings), 0);
+        assert_eq!(get_page_count(&[], &bindings, &Navigation::default()), 1);
     }
 
     #[test]
-    fn test_get_max_page_single_page() {
-        let bindings = vec![
-            Binding {
-                input: InputRef::Button { index: 0 },
-                capability: Capability::MediaPlayPause,
-                page: 0,
-                icon: None,
-                label: None,
-                button_image: None,
-                button_image_alt: None,
-                show_label: None,
-                icon_color: None,
-                icon_color_alt: None,
-            },
-        ];
-        assert_eq!(get_max_page(&bindings), 0);
+    fn test_get_page_count_single_page() {
+        let bindings = vec![Binding::new(
+            InputRef::Button { index: 0 },
+            Capability::MediaPlayPause,
+        )];
+        assert_eq!(get_page_count(&[], &bindings, &Navigation::default()), 1);
     }
 
     #[test]
-    fn test_get_max_page_multiple_pages() {
+    fn test_get_page_count_multiple_pages() {
         let bindings = vec![
+            Binding::new(InputRef::Button { index: 0 }, Capability::MediaPlayPause),
             Binding {
-                input: InputRef::Button { index: 0 },
-                capability: Capability::MediaPlayPause,
-                page: 0,
-                icon: None,
-                label: None,
-                button_image: None,
-                button_image_alt: None,
-                show_label: None,
-                icon_color: None,
-                icon_color_alt: None,
-            },
-            Binding {
-                input: InputRef::Button { index: 1 },
-                capability: Capability::MediaNext,
                 page: 2,
-                icon: None,
-                label: None,
-                button_image: None,
-                button_image_alt: None,
-                show_label: None,
-                icon_color: None,
-                icon_color_alt: None,
+                ..Binding::new(InputRef::Button { index: 1 }, Capability::MediaNext)
             },
             Binding {
-                input: InputRef::Button { index: 2 },
-                capability: Capability::MediaPrevious,
                 page: 1,
-                icon: None,
-                label: None,
-                button_image: None,
-                button_image_alt: None,
-                show_label: None,
-                icon_color: None,
-                icon_color_alt: None,
+                ..Binding::new(InputRef::Button { index: 2 }, Capability::MediaPrevious)
             },
         ];
-        assert_eq!(get_max_page(&bindings), 2);
+        assert_eq!(get_page_count(&[], &bindings, &Navigation::default()), 3);
     }
 
     #[test]
-    fn test_sync_images_flag_initial_state() {
-        // Flag should be false initially (or after being consumed)
-        SYNC_IMAGES_FLAG.store(false, Ordering::SeqCst);
-        assert!(!SYNC_IMAGES_FLAG.load(Ordering::SeqCst));
+    fn test_get_page_count_ignores_other_folders() {
+        let binding = |page: usize, folder: Option<&str>| Binding {
+            page,
+            folder: folder.map(String::from),
+            ..Binding::new(InputRef::Button { index: 1 }, Capability::MediaNext)
+        };
+        let bindings = vec![
+            binding(3, None),
+            binding(0, Some("Streaming")),
+            binding(1, Some("Streaming")),
+        ];
+        let mut nav = Navigation::default();
+
+        assert_eq!(get_page_count(&[], &bindings, &nav), 1);
+        nav.open_folder("Streaming");
+        assert_eq!(get_page_count(&[], &bindings, &nav), 2);
+        nav.open_folder("Empty");
+        assert_eq!(get_page_count(&[], &bindings, &nav), 1);
     }
 
     #[test]
-    fn test_request_image_sync_sets_flag() {
-        SYNC_IMAGES_FLAG.store(false, Ordering::SeqCst);
+    fn test_request_image_sync_bumps_generation() {
+        // Every deck loop sees the change, not just the first to look
+        let before = SYNC_GENERATION.load(Ordering::SeqCst);
         request_image_sync();
-        assert!(SYNC_IMAGES_FLAG.load(Ordering::SeqCst));
-        // Clean up
-        SYNC_IMAGES_FLAG.store(false, Ordering::SeqCst);
+        let after = SYNC_GENERATION.load(Ordering::SeqCst);
+        assert_ne!(before, after);
+        assert_eq!(SYNC_GENERATION.load(Ordering::SeqCst), after);
     }
 
     #[test]
-    fn test_sync_images_flag_swap_clears() {
-        SYNC_IMAGES_FLAG.store(true, Ordering::SeqCst);
-        let was_set = SYNC_IMAGES_FLAG.swap(false, Ordering::SeqCst);
-        assert!(was_set);
-        assert!(!SYNC_IMAGES_FLAG.load(Ordering::SeqCst));
+    fn test_animation_steps_through_frames() {
+        let frame = |ms| crate::image_cache::Frame {
+            image: DynamicImage::new_rgba8(1, 1),
+            delay: Duration::from_millis(ms),
+        };
+        let binding: Binding = toml::from_str(
+            r#"
+            input = { type = "Button", index = 2 }
+            capability = { type = "MediaPlayPause" }
+            "#,
+        )
+        .unwrap();
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let mut animation = KeyAnimation {
+            key: 2,
+            binding,
+            frames: vec![frame(100), frame(50)].into(),
+            frame: 0,
+            next_at: Some(at(100)),
+            gauge: None,
+        };
+
+        assert!(!animation.advance(at(99)));
+        assert!(animation.advance(at(100)));
+        assert_eq!((animation.frame, animation.next_at), (1, Some(at(150))));
+        assert!(animation.advance(at(160)));
+        assert_eq!((animation.frame, animation.next_at), (0, Some(at(250))));
+
+        // After a stall the timer restarts from now
+        assert!(animation.advance(at(1000)));
+        assert_eq!(animation.next_at, Some(at(1050)));
+
+        // Paused while off the page
+        animation.next_at = None;
+        assert!(!animation.advance(at(5000)));
+        assert_eq!(animation.frame, 1);
+    }
+
+    #[test]
+    fn test_unchanged_images_are_not_resent() {
+        let mut sent = HashMap::new();
+        let red =
+            DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(4, 4, Rgba([255, 0, 0, 255])));
+        let blue =
+            DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(4, 4, Rgba([0, 0, 255, 255])));
+
+        assert!(mark_sent(&mut sent, Surface::Key(0), Some(&red)));
+        assert!(!mark_sent(&mut sent, Surface::Key(0), Some(&red)));
+        // Same image on another key or the LCD is a separate upload
+        assert!(mark_sent(&mut sent, Surface::Key(1), Some(&red)));
+        assert!(mark_sent(&mut sent, Surface::LcdSection(0), Some(&red)));
+        assert!(mark_sent(&mut sent, Surface::Key(0), Some(&blue)));
+        assert!(mark_sent(&mut sent, Surface::Key(0), None));
+        assert!(!mark_sent(&mut sent, Surface::Key(0), None));
+    }
+
+    #[test]
+    fn test_key_sync_goes_to_the_bindings_deck() {
+        let binding = |extra: &str| -> Binding {
+            toml::from_str(&format!(
+                "page = 2\ncapability = {{ type = \"MediaPlayPause\" }}\n{extra}"
+            ))
+            .unwrap()
+        };
+        take_dirty_keys("KEYSYNC-A");
+        take_dirty_keys("KEYSYNC-B");
+
+        request_key_sync(&binding(
+            "input = { type = \"Button\", index = 3 }\ndevice = \"KEYSYNC-A\"",
+        ));
+        request_key_sync(&binding(
+            "input = { type = \"EncoderPress\", index = 1 }\nfolder = \"Stream\"",
+        ));
+        request_key_sync(&binding("input = { type = \"Swipe\" }"));
+
+        let key = DirtyKey {
+            folder: None,
+            page: 2,
+            surface: Surface::Key(3),
+        };
+        let section = DirtyKey {
+            folder: Some("Stream".to_string()),
+            page: 2,
+            surface: Surface::LcdSection(1),
+        };
+        assert_eq!(take_dirty_keys("KEYSYNC-A"), [key, section.clone()]);
+        assert_eq!(take_dirty_keys("KEYSYNC-B"), [section]);
+        assert!(take_dirty_keys("KEYSYNC-A").is_empty());
+    }
+
+    #[test]
+    fn test_named_state_overrides_alt_image() {
+        let registry = PluginRegistry::new();
+        registry.register(Box::new(crate::core::CorePlugin::new()), None);
+        let mut binding: Binding = toml::from_str(
+            r##"
+            input = { type = "Button", index = 0 }
+            capability = { type = "MediaPlayPause" }
+            page = 0
+            button_image = "play.png"
+            button_image_alt = "pause.png"
+            icon_color = "#ffffff"
+            label = "Media"
+
+            [states.paused]
+            icon_color = "#ffaa00"
+            label = "Paused"
+            "##,
+        )
+        .unwrap();
+        binding.add_alt_states();
+        let playing = SystemState {
+            is_playing: true,
+            ..Default::default()
+        };
+        let paused = SystemState {
+            is_paused: true,
+            ..Default::default()
+        };
+
+        // The alt image is the style of the active "playing" state
+        let look = appearance(&binding, &playing, &registry);
+        assert_eq!(
+            (look.image, look.color, look.label),
+            (Some("pause.png"), Some("#ffffff"), Some("Media"))
+        );
+
+        // Unset fields of a state fall back to the binding's own
+        let look = appearance(&binding, &paused, &registry);
+        assert_eq!(
+            (look.image, look.color, look.label),
+            (Some("play.png"), Some("#ffaa00"), Some("Paused"))
+        );
+        assert_eq!(
+            gauge_color(&binding, &paused, &registry).as_deref(),
+            Some("#ffaa00")
+        );
+
+        binding.states.clear();
+        let look = appearance(&binding, &paused, &registry);
+        assert_eq!((look.image, look.label), (Some("play.png"), Some("Media")));
+
+        // Bindings without states of their own are "active" or "inactive"
+        let mut next = Binding::new(InputRef::Button { index: 1 }, Capability::MediaNext);
+        next.states.insert(
+            crate::plugin::INACTIVE_STATE.to_string(),
+            StateStyle {
+                label: Some("Idle".to_string()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(appearance(&next, &paused, &registry).label, Some("Idle"));
+    }
+
+    // Records the presses and releases it handles
+    struct RecordingPlugin {
+        handled: Arc<Mutex<Vec<bool>>>,
+    }
+
+    impl Plugin for RecordingPlugin {
+        fn id(&self) -> &'static str {
+            "recording"
+        }
+        fn name(&self) -> &'static str {
+            "Recording Plugin"
+        }
+        fn category(&self) -> &'static str {
+            "Test"
+        }
+        fn capabilities(&self) -> Vec<CapabilityMetadata> {
+            vec![CapabilityMetadata {
+                id: "MicMute",
+                name: "MicMute",
+                description: "",
+                plugin_id: "recording",
+                supports_button: true,
+                supports_encoder: false,
+                supports_encoder_press: true,
+                parameters: vec![],
+            }]
+        }
+        fn handle_event(
+            &self,
+            event: &LogicalEvent,
+            _: &Binding,
+            _: &Arc<Mutex<SystemState>>,
+        ) -> ActionOutcome {
+            if let LogicalEvent::Button(e) = event {
+                self.handled.lock().unwrap().push(e.pressed);
+            }
+            ActionOutcome::Ok
+        }
+        fn owns_capability(&self, _: &str) -> bool {
+            false
+        }
+        fn state(&self, _: &Binding, _: &SystemState) -> Option<String> {
+            None
+        }
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+    }
+
+    #[test]
+    fn test_release_undoes_press_after_page_change() {
+        let handled = Arc::new(Mutex::new(Vec::new()));
+        let registry = Arc::new(PluginRegistry::new());
+        registry.register(
+            Box::new(RecordingPlugin {
+                handled: handled.clone(),
+            }),
+            None,
+        );
+        let state = Arc::new(Mutex::new(SystemState::default()));
+        let button = |pressed| LogicalEvent::Button(ButtonEvent { index: 4, pressed });
+        let push_to_talk = Binding {
+            momentary: Some(true),
+            ..Binding::new(InputRef::Button { index: 4 }, Capability::MicMute)
+        };
+
+        handle_logical_event("HELD-A", button(true), &[push_to_talk], &state, &registry);
+        // The page changed while held: nothing is bound there any more
+        handle_logical_event("HELD-A", button(false), &[], &state, &registry);
+
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while handled.lock().unwrap().len() < 2 && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        // The undo is a second press of the toggle, after the first
+        assert_eq!(*handled.lock().unwrap(), [true, true]);
+        assert!(!held_presses().keys().any(|key| key.0 == "HELD-A"));
     }
 
     #[test]
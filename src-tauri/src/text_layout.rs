@@ -0,0 +1,308 @@
+//! Label layout shared by the key and LCD renderers.
+//!
+//! Lines are measured glyph by glyph with the font's advances and kerning,
+//! wrapped at word boundaries and shrunk until they fit the area. Text that
+//! still doesn't fit at `MIN_FONT_SIZE` is broken mid-word and cut off with an
+//! ellipsis, always on character boundaries.
+
+use crate::binding::LabelPosition;
+use ab_glyph::{Font, PxScale, ScaleFont};
+use image::{Rgba, RgbaImage};
+use imageproc::drawing::draw_text_mut;
+
+/// Smallest size text is shrunk to before it gets cut off
+const MIN_FONT_SIZE: f32 = 8.0;
+/// Space kept between text and the edges of the image
+const PADDING: f32 = 3.0;
+/// Appended to text cut off to fit
+const ELLIPSIS: &str = "…";
+/// Drawn under text without an outline so it stays readable on images
+const SHADOW: Rgba<u8> = Rgba([0, 0, 0, 200]);
+
+/// How a block of text is drawn.
+#[derive(Debug, Clone, Copy)]
+pub struct TextStyle {
+    pub color: Rgba<u8>,
+    /// Outline around each glyph (None = drop shadow)
+    pub outline: Option<Rgba<u8>>,
+    /// Size to start from; shrunk to fit
+    pub font_size: f32,
+    pub position: LabelPosition,
+}
+
+/// Text broken into lines that fit an area at `font_size`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLayout {
+    pub lines: Vec<String>,
+    pub font_size: f32,
+}
+
+/// Width of one line of text in pixels.
+pub fn text_width(font: &impl Font, scale: PxScale, text: &str) -> f32 {
+    let font = font.as_scaled(scale);
+    let mut previous = None;
+    let mut width = 0.0;
+    for c in text.chars() {
+        let id = font.glyph_id(c);
+        if let Some(previous) = previous {
+            width += font.kern(previous, id);
+        }
+        width += font.h_advance(id);
+        previous = Some(id);
+    }
+    width
+}
+
+/// Height of one line of text in pixels.
+fn line_height(font: &impl Font, scale: PxScale) -> f32 {
+    let font = font.as_scaled(scale);
+    font.height() + font.line_gap()
+}
+
+/// Lay out `text` (one paragraph per `\n`) to fit `max_width` x `max_height`,
+/// starting at `font_size` and shrinking as needed.
+pub fn fit(
+    font: &impl Font,
+    text: &str,
+    max_width: f32,
+    max_height: f32,
+    font_size: f32,
+) -> TextLayout {
+    let mut font_size = font_size.max(MIN_FONT_SIZE);
+    loop {
+        let scale = PxScale::from(font_size);
+        if let Some(lines) = wrap(font, scale, text, max_width, false) {
+            if lines.len() as f32 * line_height(font, scale) <= max_height {
+                return TextLayout { lines, font_size };
+            }
+        }
+        if font_size <= MIN_FONT_SIZE {
+            break;
+        }
+        font_size = (font_size - 1.0).max(MIN_FONT_SIZE);
+    }
+
+    // Too much text even at the smallest size: break words and cut off the rest
+    let scale = PxScale::from(font_size);
+    let mut lines = wrap(font, scale, text, max_width, true).unwrap_or_default();
+    let max_lines = ((max_height / line_height(font, scale)) as usize).max(1);
+    if lines.len() > max_lines {
+        lines.truncate(max_lines);
+        if let Some(last) = lines.last_mut() {
+            *last = ellipsize(font, scale, last, max_width);
+        }
+    }
+    TextLayout { lines, font_size }
+}
+
+/// Word-wrap each paragraph to `max_width`. A word wider than a whole line
+/// fails the wrap unless `break_words` is set, in which case it is split
+/// between characters.
+fn wrap(
+    font: &impl Font,
+    scale: PxScale,
+    text: &str,
+    max_width: f32,
+    break_words: bool,
+) -> Option<Vec<String>> {
+    let fits = |s: &str| text_width(font, scale, s) <= max_width;
+    let mut lines = Vec::new();
+
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if line.is_empty() {
+                word.to_string()
+            } else {
+                format!("{line} {word}")
+            };
+            if fits(&candidate) {
+                line = candidate;
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            if fits(word) {
+                line = word.to_string();
+            } else if break_words {
+                for c in word.chars() {
+                    if !line.is_empty() && !fits(&format!("{line}{c}")) {
+                        lines.push(std::mem::take(&mut line));
+                    }
+                    line.push(c);
+                }
+            } else {
+                return None;
+            }
+        }
+        lines.push(line);
+    }
+
+    Some(lines)
+}
+
+/// Drop characters from the end of `text` until it fits, keeping a trailing
+/// ellipsis.
+fn ellipsize(font: &impl Font, scale: PxScale, text: &str, max_width: f32) -> String {
+    let mut text = text.trim_end().to_string();
+    while !text.is_empty() && text_width(font, scale, &format!("{text}{ELLIPSIS}")) > max_width {
+        text.pop();
+    }
+    format!("{}{ELLIPSIS}", text.trim_end())
+}
+
+/// Draw `text` across the width of `img`, fitted to the image and placed at
+/// `style.position`.
+pub fn draw_text(img: &mut RgbaImage, font: &impl Font, text: &str, style: &TextStyle) {
+    if text.trim().is_empty() {
+        return;
+    }
+
+    let (w, h) = (img.width() as f32, img.height() as f32);
+    let layout = fit(
+        font,
+        text,
+        w - PADDING * 2.0,
+        h - PADDING * 2.0,
+        style.font_size,
+    );
+    let scale = PxScale::from(layout.font_size);
+    let line_height = line_height(font, scale);
+    let block_height = line_height * layout.lines.len() as f32;
+    let top = match style.position {
+        LabelPosition::Top => PADDING,
+        LabelPosition::Middle => (h - block_height) / 2.0,
+        LabelPosition::Bottom => h - PADDING - block_height,
+    };
+
+    // Outline radius grows with the text so it stays visible on large labels
+    let radius = (layout.font_size / 12.0).round().max(1.0) as i32;
+    for (i, line) in layout.lines.iter().enumerate() {
+        let x = ((w - text_width(font, scale, line)) / 2.0).round() as i32;
+        let y = (top + line_height * i as f32).round() as i32;
+
+        match style.outline {
+            Some(outline) => {
+                for dy in -radius..=radius {
+                    for dx in -radius..=radius {
+                        if (dx, dy) != (0, 0) && dx * dx + dy * dy <= radius * radius + 1 {
+                            draw_text_mut(img, outline, x + dx, y + dy, scale, font, line);
+                        }
+                    }
+                }
+            }
+            None => draw_text_mut(img, SHADOW, x + 1, y + 1, scale, font, line),
+        }
+        draw_text_mut(img, style.color, x, y, scale, font, line);
+    }
+}
+
+/// Draw `text` fitted into one `(x, y, width, height)` area of `img`, placed
+/// within it at `style.position`.
+pub fn draw_text_in(
+    img: &mut RgbaImage,
+    font: &impl Font,
+    text: &str,
+    style: &TextStyle,
+    area: (u32, u32, u32, u32),
+) {
+    let (x, y, w, h) = area;
+    if w == 0 || h == 0 {
+        return;
+    }
+    let mut region = image::imageops::crop_imm(img, x, y, w, h).to_image();
+    draw_text(&mut region, font, text, style);
+    image::imageops::replace(img, &region, x as i64, y as i64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ab_glyph::FontRef;
+
+    fn font() -> FontRef<'static> {
+        FontRef::try_from_slice(include_bytes!("../assets/DejaVuSans-Bold.ttf")).unwrap()
+    }
+
+    #[test]
+    fn width_is_measured_per_glyph() {
+        let font = font();
+        let scale = PxScale::from(20.0);
+        // Proportional font: "i" is narrower than "W"
+        assert!(text_width(&font, scale, "iiii") < text_width(&font, scale, "WWWW"));
+        assert_eq!(text_width(&font, scale, ""), 0.0);
+    }
+
+    #[test]
+    fn text_wraps_then_shrinks_to_fit() {
+        let font = font();
+        let layout = fit(&font, "Git Pull", 66.0, 66.0, 14.0);
+        assert_eq!(layout.lines, ["Git Pull"]);
+        assert_eq!(layout.font_size, 14.0);
+
+        let layout = fit(&font, "Start stream now", 66.0, 66.0, 14.0);
+        assert!(layout.lines.len() > 1);
+        assert!(layout
+            .lines
+            .iter()
+            .all(|l| text_width(&font, PxScale::from(layout.font_size), l) <= 66.0));
+
+        // A long word shrinks instead of breaking
+        let layout = fit(&font, "Screenshot", 66.0, 66.0, 20.0);
+        assert_eq!(layout.lines, ["Screenshot"]);
+        assert!(layout.font_size < 20.0);
+    }
+
+    #[test]
+    fn overflow_is_cut_on_char_boundaries() {
+        let font = font();
+        let text = "Größenänderung für äußerst überlange Beschriftungen mit Umlauten 🎉🎉";
+        let layout = fit(&font, text, 40.0, 20.0, 14.0);
+        assert_eq!(layout.font_size, MIN_FONT_SIZE);
+        assert!(layout.lines.last().unwrap().ends_with(ELLIPSIS));
+        assert!(layout.lines.len() <= 2);
+    }
+
+    #[test]
+    fn position_places_the_block() {
+        let font = font();
+        let rows = |position| {
+            let mut img = RgbaImage::from_pixel(72, 72, Rgba([0, 0, 0, 255]));
+            let style = TextStyle {
+                color: Rgba([255, 255, 255, 255]),
+                outline: None,
+                font_size: 12.0,
+                position,
+            };
+            draw_text(&mut img, &font, "Hi", &style);
+            let rows: Vec<u32> = (0..72)
+                .filter(|&y| (0..72).any(|x| img.get_pixel(x, y)[0] == 255))
+                .collect();
+            (*rows.first().unwrap(), *rows.last().unwrap())
+        };
+        let (top, middle, bottom) = (
+            rows(LabelPosition::Top),
+            rows(LabelPosition::Middle),
+            rows(LabelPosition::Bottom),
+        );
+        assert!(top.1 < 24);
+        assert!(middle.0 > 24 && middle.1 < 48);
+        assert!(bottom.0 > 48);
+    }
+
+    #[test]
+    fn outline_surrounds_the_text() {
+        let font = font();
+        let mut img = RgbaImage::from_pixel(72, 72, Rgba([0, 0, 255, 255]));
+        let style = TextStyle {
+            color: Rgba([255, 255, 255, 255]),
+            outline: Some(Rgba([255, 0, 0, 255])),
+            font_size: 20.0,
+            position: LabelPosition::Middle,
+        };
+        draw_text(&mut img, &font, "I", &style);
+        assert!(img.pixels().any(|p| *p == Rgba([255, 0, 0, 255])));
+        assert!(img.pixels().any(|p| *p == Rgba([255, 255, 255, 255])));
+    }
+}
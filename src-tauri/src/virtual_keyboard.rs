@@ -0,0 +1,459 @@
+//! Virtual keyboard used by the SendHotkey and TypeText capabilities.
+//!
+//! Key events go to a kernel uinput device rather than through a display
+//! server, so they reach Wayland and X11 sessions alike. The user needs write
+//! access to `/dev/uinput` (usually via the `input` group or a udev rule).
+//!
+//! Events are sent through a swappable `KeyboardBackend`; the uinput device is
+//! only created the first time something is typed, and tests install a backend
+//! that records events instead.
+
+use anyhow::{bail, Context, Result};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::os::fd::AsRawFd;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// A key going down or up, by Linux input keycode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyEvent {
+    pub code: u16,
+    pub pressed: bool,
+}
+
+/// Destination for key events.
+pub trait KeyboardBackend: Send {
+    fn send(&mut self, events: &[KeyEvent]) -> Result<()>;
+}
+
+static BACKEND: Mutex<Option<Box<dyn KeyboardBackend>>> = Mutex::new(None);
+
+/// Replace the backend key events are sent to.
+#[allow(dead_code)] // Used by tests to capture events
+pub fn set_backend(backend: Box<dyn KeyboardBackend>) {
+    *BACKEND.lock().unwrap_or_else(|e| e.into_inner()) = Some(backend);
+}
+
+fn send(events: &[KeyEvent]) -> Result<()> {
+    let mut backend = BACKEND.lock().unwrap_or_else(|e| e.into_inner());
+    if backend.is_none() {
+        *backend = Some(Box::new(UinputKeyboard::new()?));
+    }
+    let result = backend
+        .as_mut()
+        .expect("backend was just created")
+        .send(events);
+    if result.is_err() {
+        // The device may be gone (e.g., uinput reloaded); recreate it next time
+        *backend = None;
+    }
+    result
+}
+
+/// Press a combination like `ctrl+shift+t`, then release it in reverse order.
+pub fn send_hotkey(keys: &str) -> Result<()> {
+    send(&hotkey_events(keys)?)
+}
+
+/// Type text as if entered on a US keyboard layout.
+pub fn type_text(text: &str) -> Result<()> {
+    send(&text_events(text)?)
+}
+
+fn hotkey_events(keys: &str) -> Result<Vec<KeyEvent>> {
+    let codes = keys
+        .split('+')
+        .map(|name| {
+            key_code(name.trim())
+                .with_context(|| format!("Unknown key '{}' in '{}'", name.trim(), keys))
+        })
+        .collect::<Result<Vec<u16>>>()?;
+
+    let mut events: Vec<KeyEvent> = codes
+        .iter()
+        .map(|&code| KeyEvent {
+            code,
+            pressed: true,
+        })
+        .collect();
+    events.extend(codes.iter().rev().map(|&code| KeyEvent {
+        code,
+        pressed: false,
+    }));
+    Ok(events)
+}
+
+fn text_events(text: &str) -> Result<Vec<KeyEvent>> {
+    let mut events = Vec::new();
+    for c in text.chars() {
+        let Some((code, shift)) = char_key(c) else {
+            bail!(
+                "Can't type '{}': only US keyboard characters are supported",
+                c
+            );
+        };
+        if shift {
+            events.push(KeyEvent {
+                code: KEY_LEFTSHIFT,
+                pressed: true,
+            });
+        }
+        events.push(KeyEvent {
+            code,
+            pressed: true,
+        });
+        events.push(KeyEvent {
+            code,
+            pressed: false,
+        });
+        if shift {
+            events.push(KeyEvent {
+                code: KEY_LEFTSHIFT,
+                pressed: false,
+            });
+        }
+    }
+    Ok(events)
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Keycodes (linux/input-event-codes.h)
+// ─────────────────────────────────────────────────────────────────
+
+const KEY_LEFTCTRL: u16 = 29;
+const KEY_LEFTSHIFT: u16 = 42;
+const KEY_LEFTALT: u16 = 56;
+const KEY_LEFTMETA: u16 = 125;
+
+/// Keycodes of `a` to `z`
+const LETTERS: [u16; 26] = [
+    30, 48, 46, 32, 18, 33, 34, 35, 23, 36, 37, 38, 50, 49, 24, 25, 16, 19, 31, 20, 22, 47, 17, 45,
+    21, 44,
+];
+
+/// Keycode for a key name in a hotkey (case-insensitive).
+fn key_code(name: &str) -> Option<u16> {
+    let name = name.to_ascii_lowercase();
+    let code = match name.as_str() {
+        "ctrl" | "control" => KEY_LEFTCTRL,
+        "shift" => KEY_LEFTSHIFT,
+        "alt" => KEY_LEFTALT,
+        "altgr" => 100,
+        "super" | "meta" | "win" | "cmd" => KEY_LEFTMETA,
+        "esc" | "escape" => 1,
+        "backspace" => 14,
+        "tab" => 15,
+        "enter" | "return" => 28,
+        "space" => 57,
+        "capslock" => 58,
+        "printscreen" | "print" => 99,
+        "pause" => 119,
+        "menu" => 127,
+        "home" => 102,
+        "up" => 103,
+        "pageup" => 104,
+        "left" => 105,
+        "right" => 106,
+        "end" => 107,
+        "down" => 108,
+        "pagedown" => 109,
+        "insert" => 110,
+        "delete" | "del" => 111,
+        "mute" => 113,
+        "volumedown" => 114,
+        "volumeup" => 115,
+        "nextsong" => 163,
+        "playpause" => 164,
+        "previoussong" => 165,
+        "stopcd" => 166,
+        _ => {
+            if let Some(number) = name.strip_prefix('f').and_then(|n| n.parse::<u16>().ok()) {
+                return match number {
+                    1..=10 => Some(58 + number),
+                    11 | 12 => Some(76 + number),
+                    13..=24 => Some(170 + number),
+                    _ => None,
+                };
+            }
+            let mut chars = name.chars();
+            return match (chars.next(), chars.next()) {
+                (Some(c), None) => char_key(c)
+                    .filter(|(_, shift)| !shift)
+                    .map(|(code, _)| code),
+                _ => None,
+            };
+        }
+    };
+    Some(code)
+}
+
+/// Keycode for a character on a US layout, and whether it needs shift.
+fn char_key(c: char) -> Option<(u16, bool)> {
+    let key = match c {
+        'a'..='z' => (LETTERS[c as usize - 'a' as usize], false),
+        'A'..='Z' => (LETTERS[c as usize - 'A' as usize], true),
+        '1'..='9' => (c as u16 - '1' as u16 + 2, false),
+        '0' => (11, false),
+        '!' => (2, true),
+        '@' => (3, true),
+        '#' => (4, true),
+        '$' => (5, true),
+        '%' => (6, true),
+        '^' => (7, true),
+        '&' => (8, true),
+        '*' => (9, true),
+        '(' => (10, true),
+        ')' => (11, true),
+        '-' => (12, false),
+        '_' => (12, true),
+        '=' => (13, false),
+        '+' => (13, true),
+        '[' => (26, false),
+        '{' => (26, true),
+        ']' => (27, false),
+        '}' => (27, true),
+        ';' => (39, false),
+        ':' => (39, true),
+        '\'' => (40, false),
+        '"' => (40, true),
+        '`' => (41, false),
+        '~' => (41, true),
+        '\\' => (43, false),
+        '|' => (43, true),
+        ',' => (51, false),
+        '<' => (51, true),
+        '.' => (52, false),
+        '>' => (52, true),
+        '/' => (53, false),
+        '?' => (53, true),
+        ' ' => (57, false),
+        '\t' => (15, false),
+        '\n' => (28, false),
+        _ => return None,
+    };
+    Some(key)
+}
+
+// ─────────────────────────────────────────────────────────────────
+// uinput device
+// ─────────────────────────────────────────────────────────────────
+
+const EV_SYN: u16 = 0x00;
+const EV_KEY: u16 = 0x01;
+const SYN_REPORT: u16 = 0;
+const BUS_VIRTUAL: u16 = 0x06;
+/// Highest keycode the device advertises (KEY_MICMUTE)
+const MAX_KEY: u16 = 248;
+
+// ioctl numbers from linux/uinput.h
+const UI_DEV_CREATE: u64 = 0x5501;
+const UI_DEV_DESTROY: u64 = 0x5502;
+const UI_DEV_SETUP: u64 = 0x405c_5503;
+const UI_SET_EVBIT: u64 = 0x4004_5564;
+const UI_SET_KEYBIT: u64 = 0x4004_5565;
+
+/// Time for the compositor or X server to pick up a new input device;
+/// events sent before then are dropped.
+const DEVICE_SETTLE_TIME: Duration = Duration::from_millis(200);
+
+/// A virtual keyboard backed by `/dev/uinput`.
+pub struct UinputKeyboard {
+    file: File,
+}
+
+impl UinputKeyboard {
+    pub fn new() -> Result<Self> {
+        let file = OpenOptions::new()
+            .write(true)
+            .open("/dev/uinput")
+            .context("Failed to open /dev/uinput (is the user allowed to write to it?)")?;
+        let keyboard = Self { file };
+
+        keyboard.ioctl(UI_SET_EVBIT, EV_KEY as libc::c_ulong)?;
+        for code in 1..=MAX_KEY {
+            keyboard.ioctl(UI_SET_KEYBIT, code as libc::c_ulong)?;
+        }
+
+        // SAFETY: uinput_setup is plain old data, so all-zero is a valid value
+        let mut setup: libc::uinput_setup = unsafe { std::mem::zeroed() };
+        setup.id.bustype = BUS_VIRTUAL;
+        let name = format!("{} Virtual Keyboard", crate::app_constants::APP_NAME);
+        // Leave the last byte as the terminating NUL
+        for (dst, src) in setup
+            .name
+            .iter_mut()
+            .take(libc::UINPUT_MAX_NAME_SIZE - 1)
+            .zip(name.bytes())
+        {
+            *dst = src as libc::c_char;
+        }
+        keyboard.ioctl(
+            UI_DEV_SETUP,
+            &setup as *const libc::uinput_setup as libc::c_ulong,
+        )?;
+        keyboard.ioctl(UI_DEV_CREATE, 0)?;
+
+        std::thread::sleep(DEVICE_SETTLE_TIME);
+        Ok(keyboard)
+    }
+
+    fn ioctl(&self, request: u64, arg: libc::c_ulong) -> Result<()> {
+        // SAFETY: the fd is open for the lifetime of self, and every request
+        // passes either an integer or a pointer to a live uinput_setup
+        let result = unsafe { libc::ioctl(self.file.as_raw_fd(), request as _, arg) };
+        if result < 0 {
+            bail!(
+                "uinput ioctl {:#x} failed: {}",
+                request,
+                std::io::Error::last_os_error()
+            );
+        }
+        Ok(())
+    }
+
+    fn write_event(&mut self, kind: u16, code: u16, value: i32) -> Result<()> {
+        // SAFETY: input_event is plain old data; the kernel fills in the time
+        let mut event: libc::input_event = unsafe { std::mem::zeroed() };
+        event.type_ = kind;
+        event.code = code;
+        event.value = value;
+        // SAFETY: reading the bytes of a fully initialised repr(C) struct
+        let bytes = unsafe {
+            std::slice::from_raw_parts(
+                &event as *const libc::input_event as *const u8,
+                std::mem::size_of::<libc::input_event>(),
+            )
+        };
+        self.file
+            .write_all(bytes)
+            .context("Failed to write to uinput device")
+    }
+}
+
+impl KeyboardBackend for UinputKeyboard {
+    fn send(&mut self, events: &[KeyEvent]) -> Result<()> {
+        send_each(events, |event| {
+            self.write_event(EV_KEY, event.code, event.pressed as i32)?;
+            self.write_event(EV_SYN, SYN_REPORT, 0)
+        })
+    }
+}
+
+/// Send `events` one at a time. If one fails, every key still held
+/// (including one whose press may have half gone through) is released before
+/// the error is returned, so modifiers aren't left stuck down.
+fn send_each(events: &[KeyEvent], mut write: impl FnMut(KeyEvent) -> Result<()>) -> Result<()> {
+    let mut held = Vec::new();
+    for &event in events {
+        if event.pressed {
+            held.push(event.code);
+        }
+        if let Err(e) = write(event) {
+            for &code in held.iter().rev() {
+                let _ = write(KeyEvent {
+                    code,
+                    pressed: false,
+                });
+            }
+            return Err(e);
+        }
+        if !event.pressed {
+            held.retain(|&code| code != event.code);
+        }
+    }
+    Ok(())
+}
+
+impl Drop for UinputKeyboard {
+    fn drop(&mut self) {
+        let _ = self.ioctl(UI_DEV_DESTROY, 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(code: u16) -> KeyEvent {
+        KeyEvent {
+            code,
+            pressed: true,
+        }
+    }
+
+    fn release(code: u16) -> KeyEvent {
+        KeyEvent {
+            code,
+            pressed: false,
+        }
+    }
+
+    #[test]
+    fn hotkey_releases_in_reverse_order() {
+        let events = hotkey_events("Ctrl+Shift+T").unwrap();
+        assert_eq!(
+            events,
+            vec![
+                press(29),
+                press(42),
+                press(20),
+                release(20),
+                release(42),
+                release(29)
+            ]
+        );
+    }
+
+    #[test]
+    fn failed_write_releases_held_keys() {
+        let mut written = Vec::new();
+        let result = send_each(&hotkey_events("ctrl+shift+t").unwrap(), |event| {
+            if event == press(20) {
+                bail!("device gone");
+            }
+            written.push(event);
+            Ok(())
+        });
+
+        assert!(result.is_err());
+        assert_eq!(
+            written,
+            vec![press(29), press(42), release(20), release(42), release(29)]
+        );
+    }
+
+    #[test]
+    fn hotkey_names() {
+        assert_eq!(key_code("super"), Some(KEY_LEFTMETA));
+        assert_eq!(key_code("F1"), Some(59));
+        assert_eq!(key_code("f12"), Some(88));
+        assert_eq!(key_code("F24"), Some(194));
+        assert_eq!(key_code("5"), Some(6));
+        assert_eq!(key_code("/"), Some(53));
+        assert_eq!(key_code("F25"), None);
+        assert!(hotkey_events("ctrl+nope").is_err());
+        assert!(hotkey_events("ctrl+").is_err());
+    }
+
+    #[test]
+    fn text_uses_shift_for_capitals_and_symbols() {
+        let events = text_events("Hi!").unwrap();
+        assert_eq!(
+            events,
+            vec![
+                press(KEY_LEFTSHIFT),
+                press(35),
+                release(35),
+                release(KEY_LEFTSHIFT),
+                press(23),
+                release(23),
+                press(KEY_LEFTSHIFT),
+                press(2),
+                release(2),
+                release(KEY_LEFTSHIFT),
+            ]
+        );
+        assert!(text_events("café").is_err());
+    }
+}
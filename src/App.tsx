@@ -12,20 +12,30 @@ import {
   InputRef,
   Capability,
   Binding,
+  KeyStyle,
   CapabilityInfo,
   ButtonEvent,
   EncoderEvent,
   TouchSwipeEvent,
   SystemState,
   ConnectionStatusEvent,
+  DeviceEvent,
   PageChangeEvent,
+  CurrentPage,
+  Page,
   inputsMatch,
+  isOnPage,
   createDefaultCapability,
+  CommandExitEvent,
+  describeCommandFailure,
+  ActionResultEvent,
+  describeActionFailure,
 } from "./types";
 import "./App.css";
 
 export default function App() {
-  const [device, setDevice] = useState<DeviceInfo | null>(null);
+  const [devices, setDevices] = useState<DeviceInfo[]>([]);
+  const [selectedSerial, setSelectedSerial] = useState<string | null>(null);
   const [bindings, setBindings] = useState<Binding[]>([]);
   const [capabilities, setCapabilities] = useState<CapabilityInfo[]>([]);
   const [selectedInput, setSelectedInput] = useState<InputRef | null>(null);
@@ -35,9 +45,15 @@ export default function App() {
   const [error, setError] = useState<string | null>(null);
   const [isConnected, setIsConnected] = useState<boolean>(true);
   const [currentPage, setCurrentPage] = useState<number>(0);
-  const [pageCount, setPageCount] = useState<number>(1);
+  const [currentFolder, setCurrentFolder] = useState<string | null>(null);
+  const [breadcrumb, setBreadcrumb] = useState<string[]>([]);
+  const [pages, setPages] = useState<Page[]>([]);
   const [currentView, setCurrentView] = useState<ViewType>("device");
 
+  // The deck being edited (the first connected one until another is picked)
+  const device = devices.find((d) => d.serial === selectedSerial) ?? devices[0] ?? null;
+  const serial = device?.serial ?? null;
+
   // Refresh capabilities (called when plugins are toggled)
   const refreshCapabilities = useCallback(async () => {
     try {
@@ -48,25 +64,28 @@ export default function App() {
     }
   }, []);
 
+  // Refresh the page list of the open folder
+  const refreshPages = useCallback(async () => {
+    try {
+      setPages(await invoke<Page[]>("get_pages", { device: serial }));
+    } catch (e) {
+      console.error("Failed to refresh pages:", e);
+    }
+  }, [serial]);
+
   // Load initial data
   useEffect(() => {
     const loadData = async () => {
       try {
-        const [deviceInfo, bindingsList, capsList, state, page, pages] = await Promise.all([
-          invoke<DeviceInfo | null>("get_device_info"),
-          invoke<Binding[]>("get_bindings"),
+        const [devicesList, capsList, state] = await Promise.all([
+          invoke<DeviceInfo[]>("get_devices"),
           invoke<CapabilityInfo[]>("get_capabilities"),
           invoke<SystemState>("get_system_state"),
-          invoke<number>("get_current_page"),
-          invoke<number>("get_page_count"),
         ]);
 
-        setDevice(deviceInfo);
-        setBindings(bindingsList);
+        setDevices(devicesList);
         setCapabilities(capsList);
         setSystemState(state);
-        setCurrentPage(page);
-        setPageCount(pages);
       } catch (e) {
         setError(`Failed to load: ${e}`);
       }
@@ -75,6 +94,30 @@ export default function App() {
     loadData();
   }, []);
 
+  // Load the selected deck's bindings and pages
+  useEffect(() => {
+    const loadDeck = async () => {
+      try {
+        const [bindingsList, page, pages] = await Promise.all([
+          invoke<Binding[]>("get_bindings", { device: serial }),
+          invoke<CurrentPage>("get_current_page", { device: serial }),
+          invoke<Page[]>("get_pages", { device: serial }),
+        ]);
+
+        setBindings(bindingsList);
+        setCurrentPage(page.page);
+        setCurrentFolder(page.folder);
+        setBreadcrumb(page.breadcrumb);
+        setPages(pages);
+        setSelectedInput(null);
+      } catch (e) {
+        setError(`Failed to load: ${e}`);
+      }
+    };
+
+    loadDeck();
+  }, [serial]);
+
   // Listen for system state changes
   useEffect(() => {
     const unlistenState = listen<SystemState>("state:change", (e) => {
@@ -86,25 +129,47 @@ export default function App() {
     };
   }, []);
 
+  // Surface failed RunCommand bindings
+  useEffect(() => {
+    const unlistenCommand = listen<CommandExitEvent>("command:exit", (e) => {
+      const failure = describeCommandFailure(e.payload);
+      if (failure) {
+        setError(failure);
+      }
+    });
+
+    return () => {
+      unlistenCommand.then((f) => f());
+    };
+  }, []);
+
+  // Surface actions that failed on the deck
+  useEffect(() => {
+    const unlistenResult = listen<ActionResultEvent>("action:result", (e) => {
+      const failure = describeActionFailure(e.payload);
+      if (failure) {
+        setError(failure);
+      }
+    });
+
+    return () => {
+      unlistenResult.then((f) => f());
+    };
+  }, []);
+
   // Listen for connection status changes
   useEffect(() => {
     const unlistenConnection = listen<ConnectionStatusEvent>(
       "streamdeck:connection",
-      async (e) => {
-        setIsConnected(e.payload.connected);
-
-        if (e.payload.connected) {
-          // Device reconnected - refresh device info
-          try {
-            const deviceInfo = await invoke<DeviceInfo | null>("get_device_info");
-            setDevice(deviceInfo);
-            setError(null);
-          } catch (err) {
-            setError(`Failed to get device info: ${err}`);
-          }
-        } else {
-          // Device disconnected
-          setDevice(null);
+      async () => {
+        // A deck came or went - refresh the device list
+        try {
+          const devicesList = await invoke<DeviceInfo[]>("get_devices");
+          setDevices(devicesList);
+          setIsConnected(devicesList.length > 0);
+          setError(null);
+        } catch (err) {
+          setError(`Failed to get device info: ${err}`);
         }
       }
     );
@@ -117,18 +182,22 @@ export default function App() {
   // Listen for page changes
   useEffect(() => {
     const unlistenPage = listen<PageChangeEvent>("streamdeck:page", (e) => {
+      if (e.payload.device !== serial) return;
       setCurrentPage(e.payload.page);
-      setPageCount(e.payload.page_count);
+      setCurrentFolder(e.payload.folder);
+      setBreadcrumb(e.payload.breadcrumb);
+      refreshPages();
     });
 
     return () => {
       unlistenPage.then((f) => f());
     };
-  }, []);
+  }, [serial, refreshPages]);
 
   // Listen for Stream Deck events
   useEffect(() => {
-    const unlistenButton = listen<ButtonEvent>("streamdeck:button", (e) => {
+    const unlistenButton = listen<DeviceEvent<ButtonEvent>>("streamdeck:button", (e) => {
+      if (e.payload.device !== serial) return;
       const key = `Button:${e.payload.index}`;
       setActiveInputs((prev) => {
         const next = new Set(prev);
@@ -141,7 +210,8 @@ export default function App() {
       });
     });
 
-    const unlistenEncoder = listen<EncoderEvent>("streamdeck:encoder", (e) => {
+    const unlistenEncoder = listen<DeviceEvent<EncoderEvent>>("streamdeck:encoder", (e) => {
+      if (e.payload.device !== serial) return;
       // Flash the encoder briefly on rotation
       const key = `Encoder:${e.payload.index}`;
       setActiveInputs((prev) => new Set(prev).add(key));
@@ -154,9 +224,10 @@ export default function App() {
       }, 150);
     });
 
-    const unlistenEncoderPress = listen<ButtonEvent>(
+    const unlistenEncoderPress = listen<DeviceEvent<ButtonEvent>>(
       "streamdeck:encoder-press",
       (e) => {
+        if (e.payload.device !== serial) return;
         const key = `EncoderPress:${e.payload.index}`;
         setActiveInputs((prev) => {
           const next = new Set(prev);
@@ -170,7 +241,8 @@ export default function App() {
       }
     );
 
-    const unlistenSwipe = listen<TouchSwipeEvent>("streamdeck:swipe", () => {
+    const unlistenSwipe = listen<DeviceEvent<TouchSwipeEvent>>("streamdeck:swipe", (e) => {
+      if (e.payload.device !== serial) return;
       // Flash the touch strip briefly on swipe
       const key = "swipe";
       setActiveInputs((prev) => new Set(prev).add(key));
@@ -189,7 +261,7 @@ export default function App() {
       unlistenEncoderPress.then((f) => f());
       unlistenSwipe.then((f) => f());
     };
-  }, []);
+  }, [serial]);
 
   // Handle setting a binding
   const handleSetBinding = useCallback(
@@ -203,13 +275,18 @@ export default function App() {
       showLabel?: boolean,
       page?: number,
       iconColor?: string,
-      iconColorAlt?: string
+      iconColorAlt?: string,
+      momentary?: boolean,
+      style?: KeyStyle
     ) => {
       try {
         const params = {
           input,
           capability,
           page: page ?? currentPage,
+          folder: currentFolder,
+          device: serial,
+          momentary: momentary ?? null,
           icon: icon ?? null,
           label: label ?? null,
           button_image: buttonImage ?? null,
@@ -217,15 +294,22 @@ export default function App() {
           show_label: showLabel ?? null,
           icon_color: iconColor ?? null,
           icon_color_alt: iconColorAlt ?? null,
+          background_color: style?.background_color ?? null,
+          text_color: style?.text_color ?? null,
+          font_size: style?.font_size ?? null,
+          label_position: style?.label_position ?? null,
+          outline_color: style?.outline_color ?? null,
+          gauge: style?.gauge ?? null,
+          states: style?.states ?? {},
         };
         await invoke("set_binding", { params });
-        // Refresh bindings and page count
+        // Refresh bindings and pages
         const [updated, pages] = await Promise.all([
-          invoke<Binding[]>("get_bindings"),
-          invoke<number>("get_page_count"),
+          invoke<Binding[]>("get_bindings", { device: serial }),
+          invoke<Page[]>("get_pages", { device: serial }),
         ]);
         setBindings(updated);
-        setPageCount(pages);
+        setPages(pages);
         // Auto-save to disk
         await invoke("save_bindings");
         setError(null);
@@ -233,38 +317,88 @@ export default function App() {
         setError(`Failed to set binding: ${e}`);
       }
     },
-    [currentPage]
+    [currentPage, currentFolder, serial]
   );
 
   // Handle removing a binding
   const handleRemoveBinding = useCallback(async (input: InputRef, page?: number) => {
     try {
       const targetPage = page ?? currentPage;
-      await invoke("remove_binding", { input, page: targetPage });
+      await invoke("remove_binding", { input, page: targetPage, folder: currentFolder, device: serial });
 
       // For encoders, also remove the paired binding (rotation <-> press)
       if (input.type === "Encoder") {
         const pressInput: InputRef = { type: "EncoderPress", index: input.index };
-        await invoke("remove_binding", { input: pressInput, page: targetPage });
+        await invoke("remove_binding", { input: pressInput, page: targetPage, folder: currentFolder, device: serial });
       } else if (input.type === "EncoderPress") {
         const rotateInput: InputRef = { type: "Encoder", index: input.index };
-        await invoke("remove_binding", { input: rotateInput, page: targetPage });
+        await invoke("remove_binding", { input: rotateInput, page: targetPage, folder: currentFolder, device: serial });
       }
 
-      // Refresh bindings and page count
+      // Refresh bindings and pages
       const [updated, pages] = await Promise.all([
-        invoke<Binding[]>("get_bindings"),
-        invoke<number>("get_page_count"),
+        invoke<Binding[]>("get_bindings", { device: serial }),
+        invoke<Page[]>("get_pages", { device: serial }),
       ]);
       setBindings(updated);
-      setPageCount(pages);
+      setPages(pages);
       // Auto-save to disk
       await invoke("save_bindings");
       setError(null);
     } catch (e) {
       setError(`Failed to remove binding: ${e}`);
     }
-  }, [currentPage]);
+  }, [currentPage, currentFolder, serial]);
+
+  // Run a page table command, then refresh and save
+  const runPageCommand = useCallback(async (command: string, args: Record<string, unknown>) => {
+    try {
+      await invoke(command, { ...args, device: serial });
+      const [updated, pages, page] = await Promise.all([
+        invoke<Binding[]>("get_bindings", { device: serial }),
+        invoke<Page[]>("get_pages", { device: serial }),
+        invoke<CurrentPage>("get_current_page", { device: serial }),
+      ]);
+      setBindings(updated);
+      setPages(pages);
+      setCurrentPage(page.page);
+      // Auto-save to disk
+      await invoke("save_bindings");
+      setError(null);
+    } catch (e) {
+      setError(`Failed to update pages: ${e}`);
+    }
+  }, [serial]);
+
+  const handleSelectPage = useCallback((page: number) => {
+    runPageCommand("set_current_page", { page });
+  }, [runPageCommand]);
+
+  const handleCreatePage = useCallback(() => {
+    runPageCommand("create_page", { name: "" });
+  }, [runPageCommand]);
+
+  const handleRenamePage = useCallback((page: number, name: string) => {
+    runPageCommand("rename_page", { page, name });
+  }, [runPageCommand]);
+
+  const handleDuplicatePage = useCallback((page: number) => {
+    runPageCommand("duplicate_page", { page });
+  }, [runPageCommand]);
+
+  const handleDeletePage = useCallback((page: number) => {
+    runPageCommand("delete_page", { page });
+  }, [runPageCommand]);
+
+  // Move a page one step left (-1) or right (+1)
+  const handleMovePage = useCallback((page: number, direction: number) => {
+    const ids = pages.map((p) => p.id);
+    const from = ids.indexOf(page);
+    const to = from + direction;
+    if (from < 0 || to < 0 || to >= ids.length) return;
+    [ids[from], ids[to]] = [ids[to], ids[from]];
+    runPageCommand("reorder_pages", { pages: ids });
+  }, [pages, runPageCommand]);
 
   // Handle capability selection from browser (for click-to-assign flow)
   const handleCapabilitySelect = useCallback((capabilityId: string) => {
@@ -290,7 +424,9 @@ export default function App() {
     const isSupported = (
       (input.type === "Button" && capInfo.supports_button) ||
       (input.type === "Encoder" && capInfo.supports_encoder) ||
-      (input.type === "EncoderPress" && capInfo.supports_encoder_press)
+      (input.type === "EncoderPress" && capInfo.supports_encoder_press) ||
+      ((input.type === "TouchTap" || input.type === "TouchLongPress") && capInfo.supports_encoder_press) ||
+      ((input.type === "Chord" || input.type === "Pedal") && capInfo.supports_button)
     );
     if (!isSupported) return;
 
@@ -306,7 +442,7 @@ export default function App() {
   const handleCopyBinding = useCallback((fromInput: InputRef, toInput: InputRef) => {
     // Find the source binding on current page
     const sourceBinding = bindings.find(
-      (b) => inputsMatch(b.input, fromInput) && b.page === currentPage
+      (b) => inputsMatch(b.input, fromInput) && isOnPage(b, currentPage, currentFolder)
     );
     if (!sourceBinding) return;
 
@@ -321,7 +457,9 @@ export default function App() {
       sourceBinding.show_label,
       currentPage,
       sourceBinding.icon_color,
-      sourceBinding.icon_color_alt
+      sourceBinding.icon_color_alt,
+      sourceBinding.momentary,
+      sourceBinding
     );
 
     // For encoders, also copy the paired binding (rotation <-> press)
@@ -330,7 +468,7 @@ export default function App() {
       const pressBinding = bindings.find(
         (b) => b.input.type === "EncoderPress" &&
                (b.input as { index: number }).index === fromInput.index &&
-               b.page === currentPage
+               isOnPage(b, currentPage, currentFolder)
       );
       if (pressBinding) {
         const toPressInput: InputRef = { type: "EncoderPress", index: toInput.index };
@@ -352,7 +490,7 @@ export default function App() {
       const rotateBinding = bindings.find(
         (b) => b.input.type === "Encoder" &&
                (b.input as { index: number }).index === (fromInput as { type: "EncoderPress"; index: number }).index &&
-               b.page === currentPage
+               isOnPage(b, currentPage, currentFolder)
       );
       if (rotateBinding) {
         const toRotateInput: InputRef = { type: "Encoder", index: (toInput as { type: "EncoderPress"; index: number }).index };
@@ -372,7 +510,7 @@ export default function App() {
     }
 
     setSelectedInput(toInput);
-  }, [bindings, currentPage, handleSetBinding]);
+  }, [bindings, currentPage, currentFolder, handleSetBinding]);
 
   if (error && !device && !isConnected) {
     return (
@@ -401,6 +539,20 @@ export default function App() {
       <div className="app">
         <header className="app-header">
           <h1>ArchDeck</h1>
+          {devices.length > 1 && (
+            <nav className="device-tabs">
+              {devices.map((d) => (
+                <button
+                  key={d.serial}
+                  className={`device-tab ${d.serial === serial ? "active" : ""}`}
+                  onClick={() => setSelectedSerial(d.serial)}
+                  title={d.serial}
+                >
+                  {d.model}
+                </button>
+              ))}
+            </nav>
+          )}
         </header>
 
         {error && <div className="error-banner">{error}</div>}
@@ -427,7 +579,15 @@ export default function App() {
                     activeInputs={activeInputs}
                     systemState={systemState}
                     currentPage={currentPage}
-                    pageCount={pageCount}
+                    currentFolder={currentFolder}
+                    breadcrumb={breadcrumb}
+                    pages={pages}
+                    onSelectPage={handleSelectPage}
+                    onCreatePage={handleCreatePage}
+                    onRenamePage={handleRenamePage}
+                    onDuplicatePage={handleDuplicatePage}
+                    onDeletePage={handleDeletePage}
+                    onMovePage={handleMovePage}
                     onSelectInput={setSelectedInput}
                     onDrop={handleDrop}
                     onCopyBinding={handleCopyBinding}
@@ -440,6 +600,7 @@ export default function App() {
                     bindings={bindings}
                     capabilities={capabilities}
                     currentPage={currentPage}
+                    currentFolder={currentFolder}
                     onSetBinding={handleSetBinding}
                     onRemoveBinding={handleRemoveBinding}
                   />
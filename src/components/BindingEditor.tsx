@@ -5,8 +5,16 @@ import {
   Capability,
   CapabilityInfo,
   Binding,
+  Gauge,
+  KeyStyle,
+  LabelPosition,
+  StateStyle,
   inputsMatch,
+  isOnPage,
   getInputDisplayName,
+  parseMacroSteps,
+  parseEnvVars,
+  formatEnvVars,
 } from "../types";
 import IconBrowser from "./IconBrowser";
 import { isSvgUrl, colorizeSvgForPreview } from "../utils/svg";
@@ -16,6 +24,7 @@ interface BindingEditorProps {
   bindings: Binding[];
   capabilities: CapabilityInfo[];
   currentPage: number;
+  currentFolder: string | null;
   onSetBinding: (
     input: InputRef,
     capability: Capability,
@@ -26,7 +35,9 @@ interface BindingEditorProps {
     showLabel?: boolean,
     page?: number,
     iconColor?: string,
-    iconColorAlt?: string
+    iconColorAlt?: string,
+    momentary?: boolean,
+    style?: KeyStyle
   ) => void;
   onRemoveBinding: (input: InputRef, page?: number) => void;
 }
@@ -36,6 +47,7 @@ export default function BindingEditor({
   bindings,
   capabilities,
   currentPage,
+  currentFolder,
   onSetBinding,
   onRemoveBinding,
 }: BindingEditorProps) {
@@ -43,23 +55,50 @@ export default function BindingEditor({
   const [step, setStep] = useState<number>(0.02);
   const [command, setCommand] = useState<string>("");
   const [url, setUrl] = useState<string>("https://");
+  const [hotkey, setHotkey] = useState<string>("ctrl+c");
+  const [text, setText] = useState<string>("");
+  const [folderName, setFolderName] = useState<string>("Folder");
+  const [targetPage, setTargetPage] = useState<number>(0);
+  const [displayInterval, setDisplayInterval] = useState<number>(5000);
+  const [displayTimeout, setDisplayTimeout] = useState<number>(2000);
+  const [displayMaxLength, setDisplayMaxLength] = useState<number>(10);
+  const [macroSteps, setMacroSteps] = useState<string>("[]");
   const [customLabel, setCustomLabel] = useState<string>("");
   const [buttonImage, setButtonImage] = useState<string>("");
   const [buttonImageAlt, setButtonImageAlt] = useState<string>("");
   const [showLabel, setShowLabel] = useState<boolean>(false);
+  const [momentary, setMomentary] = useState<boolean>(false);
   const [showIconBrowser, setShowIconBrowser] = useState<boolean>(false);
   const [iconBrowserTarget, setIconBrowserTarget] = useState<"default" | "alt">("default");
   const [keyLightIp, setKeyLightIp] = useState<string>("192.168.1.100");
   const [commandToggle, setCommandToggle] = useState<boolean>(false);
+  const [stateCommand, setStateCommand] = useState<string>("");
+  const [stateMatch, setStateMatch] = useState<string>("");
+  const [onCommand, setOnCommand] = useState<string>("");
+  const [offCommand, setOffCommand] = useState<string>("");
+  const [commandCwd, setCommandCwd] = useState<string>("");
+  const [commandEnv, setCommandEnv] = useState<string>("");
+  const [commandTimeout, setCommandTimeout] = useState<number>(0);
+  const [killOnRelease, setKillOnRelease] = useState<boolean>(false);
   const [iconColor, setIconColor] = useState<string>("#ffffff");
   const [iconColorAlt, setIconColorAlt] = useState<string>("#ffffff");
+  const [emoji, setEmoji] = useState<string>("");
+  const [solidBackground, setSolidBackground] = useState<boolean>(false);
+  const [backgroundColor, setBackgroundColor] = useState<string>("#1e1e1e");
+  const [textColor, setTextColor] = useState<string>("#ffffff");
+  const [fontSize, setFontSize] = useState<number>(0);
+  const [labelPosition, setLabelPosition] = useState<LabelPosition | "">("");
+  const [outline, setOutline] = useState<boolean>(false);
+  const [outlineColor, setOutlineColor] = useState<string>("#000000");
+  const [gauge, setGauge] = useState<Gauge | "">("");
+  const [states, setStates] = useState<Record<string, StateStyle>>({});
   // Preview URLs (colorized SVG data URLs for UI display)
   const [previewUrl, setPreviewUrl] = useState<string>("");
   const [previewUrlAlt, setPreviewUrlAlt] = useState<string>("");
 
   // Get current binding for selected input on current page
   const currentBinding = selectedInput
-    ? bindings.find((b) => inputsMatch(b.input, selectedInput) && b.page === currentPage)
+    ? bindings.find((b) => inputsMatch(b.input, selectedInput) && isOnPage(b, currentPage, currentFolder))
     : undefined;
 
   // Check if this input type supports hardware images
@@ -71,6 +110,13 @@ export default function BindingEditor({
     selectedInput?.type === "EncoderPress" ||
     selectedInput?.type === "Encoder";
 
+  // Inputs with a release edge can undo their action when let go
+  const supportsMomentary =
+    selectedInput?.type === "Button" ||
+    selectedInput?.type === "EncoderPress" ||
+    selectedInput?.type === "Pedal";
+  const isPedal = selectedInput?.type === "Pedal";
+
   // Filter capabilities based on input type
   const availableCapabilities = selectedInput
     ? capabilities.filter((cap) => {
@@ -83,6 +129,12 @@ export default function BindingEditor({
             return cap.supports_encoder_press;
           case "Swipe":
             return false; // No capabilities for swipe yet
+          case "TouchTap":
+          case "TouchLongPress":
+            return cap.supports_encoder_press; // Acts on the encoder below
+          case "Chord":
+          case "Pedal":
+            return cap.supports_button;
         }
       })
     : [];
@@ -96,8 +148,19 @@ export default function BindingEditor({
         setButtonImage(currentBinding.button_image || "");
         setButtonImageAlt(currentBinding.button_image_alt || "");
         setShowLabel(currentBinding.show_label || false);
+        setMomentary(currentBinding.momentary ?? currentBinding.input.type === "Pedal");
         setIconColor(currentBinding.icon_color || "#ffffff");
         setIconColorAlt(currentBinding.icon_color_alt || "#ffffff");
+        setEmoji(currentBinding.icon || "");
+        setSolidBackground(!!currentBinding.background_color);
+        setBackgroundColor(currentBinding.background_color || "#1e1e1e");
+        setTextColor(currentBinding.text_color || "#ffffff");
+        setFontSize(currentBinding.font_size ?? 0);
+        setLabelPosition(currentBinding.label_position ?? "");
+        setOutline(!!currentBinding.outline_color);
+        setOutlineColor(currentBinding.outline_color || "#000000");
+        setGauge(currentBinding.gauge ?? "");
+        setStates(currentBinding.states ?? {});
 
         // Generate preview URLs for SVG icons
         const imgUrl = currentBinding.button_image || "";
@@ -131,6 +194,20 @@ export default function BindingEditor({
         if (currentBinding.capability.type === "RunCommand") {
           setCommand(currentBinding.capability.command);
           setCommandToggle(currentBinding.capability.toggle || false);
+          setStateCommand(currentBinding.capability.state_command || "");
+          setStateMatch(currentBinding.capability.state_match || "");
+          setOnCommand(currentBinding.capability.on_command || "");
+          setOffCommand(currentBinding.capability.off_command || "");
+          setCommandCwd(currentBinding.capability.cwd || "");
+          setCommandEnv(formatEnvVars(currentBinding.capability.env));
+          setCommandTimeout(currentBinding.capability.timeout_ms ?? 0);
+          setKillOnRelease(currentBinding.capability.kill_on_release || false);
+        }
+        if (currentBinding.capability.type === "CommandDisplay") {
+          setCommand(currentBinding.capability.command);
+          setDisplayInterval(currentBinding.capability.interval_ms ?? 5000);
+          setDisplayTimeout(currentBinding.capability.timeout_ms ?? 2000);
+          setDisplayMaxLength(currentBinding.capability.max_length ?? 10);
         }
         if (currentBinding.capability.type === "LaunchApp") {
           setCommand(currentBinding.capability.command);
@@ -138,6 +215,21 @@ export default function BindingEditor({
         if (currentBinding.capability.type === "OpenURL") {
           setUrl(currentBinding.capability.url);
         }
+        if (currentBinding.capability.type === "SendHotkey") {
+          setHotkey(currentBinding.capability.keys);
+        }
+        if (currentBinding.capability.type === "TypeText") {
+          setText(currentBinding.capability.text);
+        }
+        if (currentBinding.capability.type === "OpenFolder") {
+          setFolderName(currentBinding.capability.name);
+        }
+        if (currentBinding.capability.type === "GoToPage") {
+          setTargetPage(currentBinding.capability.page);
+        }
+        if (currentBinding.capability.type === "Macro") {
+          setMacroSteps(JSON.stringify(currentBinding.capability.steps, null, 2));
+        }
         if (currentBinding.capability.type === "ElgatoKeyLight") {
           setKeyLightIp(currentBinding.capability.ip);
           setSelectedCapabilityId("ElgatoKeyLight");
@@ -147,14 +239,41 @@ export default function BindingEditor({
         setStep(0.02);
         setCommand("");
         setUrl("https://");
+        setHotkey("ctrl+c");
+        setText("");
+        setFolderName("Folder");
+        setTargetPage(0);
+        setDisplayInterval(5000);
+        setDisplayTimeout(2000);
+        setDisplayMaxLength(10);
+        setMacroSteps("[]");
         setCustomLabel("");
         setButtonImage("");
         setButtonImageAlt("");
         setShowLabel(false);
+        setMomentary(selectedInput?.type === "Pedal");
         setKeyLightIp("192.168.1.100");
         setCommandToggle(false);
+        setStateCommand("");
+        setStateMatch("");
+        setOnCommand("");
+        setOffCommand("");
+        setCommandCwd("");
+        setCommandEnv("");
+        setCommandTimeout(0);
+        setKillOnRelease(false);
         setIconColor("#ffffff");
         setIconColorAlt("#ffffff");
+        setEmoji("");
+        setSolidBackground(false);
+        setBackgroundColor("#1e1e1e");
+        setTextColor("#ffffff");
+        setFontSize(0);
+        setLabelPosition("");
+        setOutline(false);
+        setOutlineColor("#000000");
+        setGauge("");
+        setStates({});
         setPreviewUrl("");
         setPreviewUrlAlt("");
       }
@@ -248,9 +367,34 @@ export default function BindingEditor({
       case "MediaStop":
         capability = { type: "MediaStop" };
         break;
-      case "RunCommand":
+      case "RunCommand": {
+        if (!command.trim() && !(onCommand.trim() && offCommand.trim())) return;
+        const env = parseEnvVars(commandEnv);
+        if (!env) return;
+        capability = {
+          type: "RunCommand",
+          command: command.trim(),
+          toggle: commandToggle,
+          state_command: stateCommand.trim() || undefined,
+          state_match: (stateCommand.trim() && stateMatch.trim()) || undefined,
+          on_command: onCommand.trim() || undefined,
+          off_command: offCommand.trim() || undefined,
+          cwd: commandCwd.trim() || undefined,
+          env: Object.keys(env).length > 0 ? env : undefined,
+          timeout_ms: commandTimeout > 0 ? commandTimeout : undefined,
+          kill_on_release: killOnRelease || undefined,
+        };
+        break;
+      }
+      case "CommandDisplay":
         if (!command.trim()) return;
-        capability = { type: "RunCommand", command: command.trim(), toggle: commandToggle };
+        capability = {
+          type: "CommandDisplay",
+          command: command.trim(),
+          interval_ms: displayInterval,
+          timeout_ms: displayTimeout,
+          max_length: displayMaxLength,
+        };
         break;
       case "LaunchApp":
         if (!command.trim()) return;
@@ -260,6 +404,36 @@ export default function BindingEditor({
         if (!url.trim() || url === "https://") return;
         capability = { type: "OpenURL", url: url.trim() };
         break;
+      case "SendHotkey":
+        if (!hotkey.trim()) return;
+        capability = { type: "SendHotkey", keys: hotkey.trim() };
+        break;
+      case "TypeText":
+        if (!text) return;
+        capability = { type: "TypeText", text };
+        break;
+      case "OpenFolder":
+        if (!folderName.trim()) return;
+        capability = { type: "OpenFolder", name: folderName.trim() };
+        break;
+      case "NextPage":
+        capability = { type: "NextPage" };
+        break;
+      case "PreviousPage":
+        capability = { type: "PreviousPage" };
+        break;
+      case "GoToPage":
+        capability = { type: "GoToPage", page: targetPage };
+        break;
+      case "PageByEncoder":
+        capability = { type: "PageByEncoder" };
+        break;
+      case "Macro": {
+        const steps = parseMacroSteps(macroSteps);
+        if (!steps || steps.length === 0) return;
+        capability = { type: "Macro", steps };
+        break;
+      }
       case "ElgatoKeyLight":
         if (!keyLightIp.trim()) return;
         // Combined capability - action determined by input type at runtime
@@ -269,18 +443,31 @@ export default function BindingEditor({
         return;
     }
 
-    // Pass label only if customized (no icon picker anymore)
-    const icon = undefined;
+    // Pass label and emoji only if customized
+    const icon = emoji.trim() || undefined;
     const label = customLabel || undefined;
     // For button image and show label, pass the actual values (not using || which breaks false)
     const image = buttonImage.trim() || undefined;
     const imageAlt = buttonImageAlt.trim() || undefined;
     const showLabelOnButton = showLabel;
-    // Pass icon colors only if image is an SVG
-    const color = image && isSvgUrl(image) ? iconColor : undefined;
-    const colorAlt = imageAlt && isSvgUrl(imageAlt) ? iconColorAlt : undefined;
+    // Pass icon colors only if image is an SVG or they tint a gauge
+    const tintsGauge = supportsGauge && !!gauge;
+    const color = (image && isSvgUrl(image)) || tintsGauge ? iconColor : undefined;
+    const colorAlt = (imageAlt && isSvgUrl(imageAlt)) || tintsGauge ? iconColorAlt : undefined;
+    // Only store momentary when it differs from the input's default
+    const hold = supportsMomentary && momentary !== isPedal ? momentary : undefined;
+    // Only store styling that differs from the defaults
+    const style: KeyStyle = {
+      background_color: solidBackground ? backgroundColor : undefined,
+      text_color: textColor !== "#ffffff" ? textColor : undefined,
+      font_size: fontSize > 0 ? fontSize : undefined,
+      label_position: labelPosition || undefined,
+      outline_color: outline ? outlineColor : undefined,
+      gauge: supportsGauge && gauge ? gauge : undefined,
+      states,
+    };
 
-    onSetBinding(selectedInput, capability, icon, label, image, imageAlt, showLabelOnButton, currentPage, color, colorAlt);
+    onSetBinding(selectedInput, capability, icon, label, image, imageAlt, showLabelOnButton, currentPage, color, colorAlt, hold, style);
 
     // For unified capabilities on encoders, automatically create both rotation and press bindings
     // This applies to SystemAudio, Microphone, and ElgatoKeyLight
@@ -293,11 +480,11 @@ export default function BindingEditor({
       if (selectedInput.type === "Encoder") {
         // Also create EncoderPress binding
         const pressInput: InputRef = { type: "EncoderPress", index: selectedInput.index };
-        onSetBinding(pressInput, capability, icon, label, image, imageAlt, showLabelOnButton, currentPage, color, colorAlt);
+        onSetBinding(pressInput, capability, icon, label, image, imageAlt, showLabelOnButton, currentPage, color, colorAlt, hold, style);
       } else if (selectedInput.type === "EncoderPress") {
         // Also create Encoder binding
         const rotateInput: InputRef = { type: "Encoder", index: selectedInput.index };
-        onSetBinding(rotateInput, capability, icon, label, image, imageAlt, showLabelOnButton, currentPage, color, colorAlt);
+        onSetBinding(rotateInput, capability, icon, label, image, imageAlt, showLabelOnButton, currentPage, color, colorAlt, hold, style);
       }
     }
   };
@@ -312,6 +499,16 @@ export default function BindingEditor({
     setShowLabel(false);
     setIconColor("#ffffff");
     setIconColorAlt("#ffffff");
+    setEmoji("");
+    setSolidBackground(false);
+    setBackgroundColor("#1e1e1e");
+    setTextColor("#ffffff");
+    setFontSize(0);
+    setLabelPosition("");
+    setOutline(false);
+    setOutlineColor("#000000");
+    setGauge("");
+    setStates({});
     setPreviewUrl("");
     setPreviewUrlAlt("");
   };
@@ -349,11 +546,74 @@ export default function BindingEditor({
     selectedCapabilityId === "MicMute" ||
     selectedCapabilityId === "MediaPlayPause" ||
     selectedCapabilityId === "ElgatoKeyLight" ||
-    (selectedCapabilityId === "RunCommand" && commandToggle);
+    (selectedCapabilityId === "RunCommand" && (commandToggle || !!stateCommand.trim()));
 
   // Check if this is a Key Light capability
   const isKeyLightCapability = selectedCapabilityId === "ElgatoKeyLight";
 
+  // Capabilities that report a value for gauges
+  const supportsGauge =
+    selectedCapabilityId === "SystemAudio" ||
+    selectedCapabilityId === "Mute" ||
+    selectedCapabilityId === "Microphone" ||
+    selectedCapabilityId === "MicMute" ||
+    selectedCapabilityId === "OBSAudio" ||
+    selectedCapabilityId === "ElgatoKeyLight";
+
+  // Named states the capability reports, each of which can have its own look
+  const getStateKeys = (): string[] => {
+    if (
+      selectedCapabilityId === "SystemAudio" ||
+      selectedCapabilityId === "Mute" ||
+      selectedCapabilityId === "Microphone" ||
+      selectedCapabilityId === "MicMute"
+    ) {
+      return ["muted", "unmuted"];
+    }
+    if (selectedCapabilityId === "MediaPlayPause") {
+      return ["playing", "paused", "stopped"];
+    }
+    if (selectedCapabilityId === "ElgatoKeyLight") {
+      return ["on", "off"];
+    }
+    if (selectedCapabilityId === "RunCommand" && (commandToggle || stateCommand.trim())) {
+      return ["on", "off"];
+    }
+    if (selectedCapabilityId === "OBSRecord") {
+      return ["idle", "recording", "paused"];
+    }
+    if (selectedCapabilityId === "OBSStream") {
+      return ["offline", "live"];
+    }
+    if (
+      selectedCapabilityId === "OBSScene" ||
+      selectedCapabilityId === "OBSSourceVisibility" ||
+      selectedCapabilityId === "OBSStudioMode" ||
+      selectedCapabilityId === "OBSReplayBuffer" ||
+      selectedCapabilityId === "OBSVirtualCam"
+    ) {
+      return ["active"];
+    }
+    return [];
+  };
+
+  // Suggested states plus any others already configured
+  const stateKeys = [...new Set([...getStateKeys(), ...Object.keys(states)])];
+
+  // Set one field of a state's look, dropping empty fields and states
+  const updateState = (key: string, field: keyof StateStyle, value: string) => {
+    setStates((prev) => {
+      const style: StateStyle = { ...prev[key], [field]: value || undefined };
+      const next = { ...prev };
+      if (Object.values(style).some((v) => v)) {
+        next[key] = style;
+      } else {
+        delete next[key];
+      }
+      return next;
+    });
+  };
+
   // Get description for alternate image based on capability
   const getAltImageDescription = (): string => {
     if (selectedCapabilityId === "SystemAudio" || selectedCapabilityId === "Mute") {
@@ -368,6 +628,9 @@ export default function BindingEditor({
     if (selectedCapabilityId === "ElgatoKeyLight") {
       return "Image shown when light is on";
     }
+    if (selectedCapabilityId === "RunCommand" && stateCommand.trim()) {
+      return "Image shown while the state command reports active";
+    }
     if (selectedCapabilityId === "RunCommand" && commandToggle) {
       return "Image shown when toggled active";
     }
@@ -388,7 +651,7 @@ export default function BindingEditor({
     if (selectedCapabilityId === "ElgatoKeyLight") {
       return "Light On Image";
     }
-    if (selectedCapabilityId === "RunCommand" && commandToggle) {
+    if (selectedCapabilityId === "RunCommand" && (commandToggle || stateCommand.trim())) {
       return "Active Image";
     }
     return "Alternate Image";
@@ -481,6 +744,155 @@ export default function BindingEditor({
               Alternate between default and active image on each press (e.g., for start/stop dictation)
             </p>
           </div>
+          <div className="editor-field">
+            <label htmlFor="state-command-input">State Command</label>
+            <input
+              id="state-command-input"
+              type="text"
+              value={stateCommand}
+              onChange={(e) => setStateCommand(e.target.value)}
+              placeholder="e.g., systemctl is-active --quiet wg-quick@wg0"
+            />
+            <p className="field-description">
+              Optional. Checked every few seconds; the key is active when it exits 0
+            </p>
+          </div>
+          {stateCommand.trim() && (
+            <div className="editor-field">
+              <label htmlFor="state-match-input">Active Output</label>
+              <input
+                id="state-match-input"
+                type="text"
+                value={stateMatch}
+                onChange={(e) => setStateMatch(e.target.value)}
+                placeholder="e.g., connected"
+              />
+              <p className="field-description">
+                Optional. Active when the state command prints this instead
+              </p>
+            </div>
+          )}
+          <div className="editor-field">
+            <label htmlFor="on-command-input">On Command</label>
+            <input
+              id="on-command-input"
+              type="text"
+              value={onCommand}
+              onChange={(e) => setOnCommand(e.target.value)}
+              placeholder="e.g., systemctl start wg-quick@wg0"
+            />
+            <p className="field-description">Optional. Run instead of the command when inactive</p>
+          </div>
+          <div className="editor-field">
+            <label htmlFor="off-command-input">Off Command</label>
+            <input
+              id="off-command-input"
+              type="text"
+              value={offCommand}
+              onChange={(e) => setOffCommand(e.target.value)}
+              placeholder="e.g., systemctl stop wg-quick@wg0"
+            />
+            <p className="field-description">Optional. Run instead of the command when active</p>
+          </div>
+          <div className="editor-field">
+            <label htmlFor="cwd-input">Working Directory</label>
+            <input
+              id="cwd-input"
+              type="text"
+              value={commandCwd}
+              onChange={(e) => setCommandCwd(e.target.value)}
+              placeholder="e.g., ~/projects/site"
+            />
+          </div>
+          <div className="editor-field">
+            <label htmlFor="env-input">Environment</label>
+            <textarea
+              id="env-input"
+              rows={2}
+              value={commandEnv}
+              onChange={(e) => setCommandEnv(e.target.value)}
+              placeholder="KEY=value"
+            />
+            <p className="field-description">
+              {parseEnvVars(commandEnv) ? "One KEY=value per line" : "Each line needs a KEY=value"}
+            </p>
+          </div>
+          <div className="editor-field">
+            <label htmlFor="command-timeout-input">Timeout (ms)</label>
+            <input
+              id="command-timeout-input"
+              type="number"
+              min="0"
+              step="500"
+              value={commandTimeout}
+              onChange={(e) => setCommandTimeout(Math.max(0, parseInt(e.target.value) || 0))}
+            />
+            <p className="field-description">Stop the command after this long (0 = no limit)</p>
+          </div>
+          <div className="editor-field checkbox-field">
+            <label className="checkbox-label" htmlFor="kill-on-release-checkbox">
+              <input
+                id="kill-on-release-checkbox"
+                type="checkbox"
+                checked={killOnRelease}
+                onChange={(e) => setKillOnRelease(e.target.checked)}
+              />
+              Stop on Release
+            </label>
+            <p className="field-description">Stop the command when the key is let go (e.g., push-to-record)</p>
+          </div>
+        </>
+      )}
+
+      {selectedCapabilityId === "CommandDisplay" && (
+        <>
+          <div className="editor-field">
+            <label htmlFor="display-command-input">Command</label>
+            <input
+              id="display-command-input"
+              type="text"
+              value={command}
+              onChange={(e) => setCommand(e.target.value)}
+              placeholder="e.g., git -C ~/project branch --show-current"
+            />
+            <p className="field-description">The first line it prints is shown on the key</p>
+          </div>
+          <div className="editor-field">
+            <label htmlFor="display-interval-input">Refresh Every (ms)</label>
+            <input
+              id="display-interval-input"
+              type="number"
+              min="0"
+              step="500"
+              value={displayInterval}
+              onChange={(e) => setDisplayInterval(Math.max(0, parseInt(e.target.value) || 0))}
+            />
+            <p className="field-description">0 = only refresh when pressed</p>
+          </div>
+          <div className="editor-field">
+            <label htmlFor="display-timeout-input">Timeout (ms)</label>
+            <input
+              id="display-timeout-input"
+              type="number"
+              min="100"
+              step="100"
+              value={displayTimeout}
+              onChange={(e) => setDisplayTimeout(Math.max(100, parseInt(e.target.value) || 2000))}
+            />
+            <p className="field-description">Slower commands are stopped and show "?"</p>
+          </div>
+          <div className="editor-field">
+            <label htmlFor="display-max-length-input">Max Length</label>
+            <input
+              id="display-max-length-input"
+              type="number"
+              min="1"
+              step="1"
+              value={displayMaxLength}
+              onChange={(e) => setDisplayMaxLength(Math.max(1, parseInt(e.target.value) || 10))}
+            />
+            <p className="field-description">Longer output is cut off with "…"</p>
+          </div>
         </>
       )}
 
@@ -512,6 +924,81 @@ export default function BindingEditor({
         </div>
       )}
 
+      {selectedCapabilityId === "SendHotkey" && (
+        <div className="editor-field">
+          <label htmlFor="hotkey-input">Keys</label>
+          <input
+            id="hotkey-input"
+            type="text"
+            value={hotkey}
+            onChange={(e) => setHotkey(e.target.value)}
+            placeholder="e.g., ctrl+shift+t"
+          />
+          <p className="field-description">Keys joined with +, pressed together</p>
+        </div>
+      )}
+
+      {selectedCapabilityId === "TypeText" && (
+        <div className="editor-field">
+          <label htmlFor="type-text-input">Text</label>
+          <textarea
+            id="type-text-input"
+            rows={3}
+            value={text}
+            onChange={(e) => setText(e.target.value)}
+            placeholder="Text to type"
+          />
+          <p className="field-description">Typed as on a US keyboard layout</p>
+        </div>
+      )}
+
+      {selectedCapabilityId === "OpenFolder" && (
+        <div className="editor-field">
+          <label htmlFor="folder-input">Folder</label>
+          <input
+            id="folder-input"
+            type="text"
+            value={folderName}
+            onChange={(e) => setFolderName(e.target.value)}
+            placeholder="e.g., Streaming"
+          />
+          <p className="field-description">Button 1 becomes a back key inside the folder</p>
+        </div>
+      )}
+
+      {selectedCapabilityId === "GoToPage" && (
+        <div className="editor-field">
+          <label htmlFor="target-page-input">Page</label>
+          <input
+            id="target-page-input"
+            type="number"
+            min="1"
+            step="1"
+            value={targetPage + 1}
+            onChange={(e) => setTargetPage(Math.max(0, (parseInt(e.target.value) || 1) - 1))}
+          />
+          <p className="field-description">Page to jump to (as shown under the deck)</p>
+        </div>
+      )}
+
+      {selectedCapabilityId === "Macro" && (
+        <div className="editor-field">
+          <label htmlFor="macro-steps-input">Steps</label>
+          <textarea
+            id="macro-steps-input"
+            rows={8}
+            value={macroSteps}
+            onChange={(e) => setMacroSteps(e.target.value)}
+            placeholder='[{"capability": {"type": "MicMute"}}, {"capability": {"type": "MediaPlayPause"}, "delay_ms": 500}]'
+          />
+          <p className="field-description">
+            {parseMacroSteps(macroSteps)
+              ? "JSON list of steps, run in order; delay_ms waits before a step"
+              : "Not a valid list of steps"}
+          </p>
+        </div>
+      )}
+
       {isKeyLightCapability && (
         <div className="editor-field">
           <label htmlFor="keylight-ip-input">Key Light IP Address</label>
@@ -528,19 +1015,37 @@ export default function BindingEditor({
         </div>
       )}
 
+      {selectedCapabilityId && supportsMomentary && (
+        <div className="editor-field checkbox-field">
+          <label className="checkbox-label" htmlFor="momentary-checkbox">
+            <input
+              id="momentary-checkbox"
+              type="checkbox"
+              checked={momentary}
+              onChange={(e) => setMomentary(e.target.checked)}
+            />
+            Only while held
+          </label>
+          <p className="field-description">
+            Undo the action on release (e.g., push-to-talk). Works with toggles and on/off or start/stop actions
+          </p>
+        </div>
+      )}
+
       {selectedCapabilityId && (
         <>
           <div className="editor-field">
             <label htmlFor="label-input">Custom Label</label>
-            <input
+            <textarea
               id="label-input"
-              type="text"
+              rows={2}
               value={customLabel}
               onChange={(e) => setCustomLabel(e.target.value)}
               placeholder="Optional custom text"
             />
             <p className="field-description">
-              Leave empty to use default name
+              Leave empty to use default name. Each line is drawn on its own line on the key. Live values can be
+              inserted with {"{volume}"}, {"{mic.volume}"}, {"{obs.scene}"}, {"{obs.volume}"}, {"{keylight.brightness}"} or {"{time:%H:%M}"}
             </p>
           </div>
 
@@ -603,6 +1108,153 @@ export default function BindingEditor({
                 </p>
               </div>
 
+              {/* Value gauges over keys and on the LCD strip */}
+              {supportsGauge && (
+                <div className="editor-field">
+                  <label htmlFor="gauge-select">Gauge</label>
+                  <select
+                    id="gauge-select"
+                    value={gauge}
+                    onChange={(e) => setGauge(e.target.value as Gauge | "")}
+                  >
+                    <option value="">None</option>
+                    <option value="Arc">Arc</option>
+                    <option value="Bar">Bar</option>
+                    <option value="Ring">Ring</option>
+                    <option value="Fill">Fill</option>
+                  </select>
+                  {gauge && (
+                    <div className="color-picker-inline">
+                      <label htmlFor="gauge-color">Color:</label>
+                      <input
+                        id="gauge-color"
+                        type="color"
+                        value={iconColor}
+                        onChange={(e) => setIconColor(e.target.value)}
+                      />
+                      <label htmlFor="gauge-color-alt">Active:</label>
+                      <input
+                        id="gauge-color-alt"
+                        type="color"
+                        value={iconColorAlt}
+                        onChange={(e) => setIconColorAlt(e.target.value)}
+                      />
+                    </div>
+                  )}
+                  <p className="field-description">
+                    {selectedInput?.type === "Button"
+                      ? "Drawn over the key image. The active color applies while muted or on"
+                      : "Replaces the LCD image, with the readout and the label as a title"}
+                  </p>
+                </div>
+              )}
+
+              {/* Text-only and solid-colour keys */}
+              {selectedInput?.type === "Button" && (
+                <>
+                  <div className="editor-field">
+                    <label htmlFor="emoji-input">Emoji</label>
+                    <input
+                      id="emoji-input"
+                      type="text"
+                      value={emoji}
+                      onChange={(e) => setEmoji(e.target.value)}
+                      placeholder="e.g., 🎙️"
+                    />
+                    <p className="field-description">
+                      Drawn on keys without an image (needs an emoji font such as Noto Color Emoji)
+                    </p>
+                  </div>
+
+                  <div className="editor-field checkbox-field">
+                    <label className="checkbox-label" htmlFor="solid-background-checkbox">
+                      <input
+                        id="solid-background-checkbox"
+                        type="checkbox"
+                        checked={solidBackground}
+                        onChange={(e) => setSolidBackground(e.target.checked)}
+                      />
+                      Solid background
+                    </label>
+                    {solidBackground && (
+                      <div className="color-picker-inline">
+                        <label htmlFor="background-color">Color:</label>
+                        <input
+                          id="background-color"
+                          type="color"
+                          value={backgroundColor}
+                          onChange={(e) => setBackgroundColor(e.target.value)}
+                        />
+                      </div>
+                    )}
+                  </div>
+
+                  <div className="editor-field">
+                    <label htmlFor="text-color">Text Color</label>
+                    <input
+                      id="text-color"
+                      type="color"
+                      value={textColor}
+                      onChange={(e) => setTextColor(e.target.value)}
+                    />
+                  </div>
+
+                  <div className="editor-field">
+                    <label htmlFor="font-size-input">Font Size (px)</label>
+                    <input
+                      id="font-size-input"
+                      type="number"
+                      min="0"
+                      value={fontSize}
+                      onChange={(e) => setFontSize(parseInt(e.target.value) || 0)}
+                    />
+                    <p className="field-description">
+                      0 sizes the text to the key. Text that doesn't fit wraps and shrinks
+                    </p>
+                  </div>
+
+                  <div className="editor-field">
+                    <label htmlFor="label-position-select">Label Position</label>
+                    <select
+                      id="label-position-select"
+                      value={labelPosition}
+                      onChange={(e) => setLabelPosition(e.target.value as LabelPosition | "")}
+                    >
+                      <option value="">Auto</option>
+                      <option value="Top">Top</option>
+                      <option value="Middle">Middle</option>
+                      <option value="Bottom">Bottom</option>
+                    </select>
+                    <p className="field-description">
+                      Auto puts the label below an image or emoji and centres it otherwise
+                    </p>
+                  </div>
+
+                  <div className="editor-field checkbox-field">
+                    <label className="checkbox-label" htmlFor="outline-checkbox">
+                      <input
+                        id="outline-checkbox"
+                        type="checkbox"
+                        checked={outline}
+                        onChange={(e) => setOutline(e.target.checked)}
+                      />
+                      Outline text
+                    </label>
+                    {outline && (
+                      <div className="color-picker-inline">
+                        <label htmlFor="outline-color">Color:</label>
+                        <input
+                          id="outline-color"
+                          type="color"
+                          value={outlineColor}
+                          onChange={(e) => setOutlineColor(e.target.value)}
+                        />
+                      </div>
+                    )}
+                  </div>
+                </>
+              )}
+
               {/* Alternate image for stateful capabilities */}
               {supportsStateImages && (
                 <div className="editor-field">
@@ -646,6 +1298,46 @@ export default function BindingEditor({
                   </p>
                 </div>
               )}
+
+              {/* Image, color and label per named state */}
+              {stateKeys.length > 0 && (
+                <div className="editor-field">
+                  <label>States</label>
+                  {stateKeys.map((key) => (
+                    <div key={key} className="state-style">
+                      <span className="state-key">{key}</span>
+                      <input
+                        type="text"
+                        value={states[key]?.button_image ?? ""}
+                        onChange={(e) => updateState(key, "button_image", e.target.value)}
+                        placeholder="Image path or URL"
+                      />
+                      <input
+                        type="text"
+                        value={states[key]?.label ?? ""}
+                        onChange={(e) => updateState(key, "label", e.target.value)}
+                        placeholder="Label"
+                      />
+                      <input
+                        type="checkbox"
+                        title="Icon color"
+                        checked={!!states[key]?.icon_color}
+                        onChange={(e) => updateState(key, "icon_color", e.target.checked ? iconColor : "")}
+                      />
+                      {states[key]?.icon_color && (
+                        <input
+                          type="color"
+                          value={states[key]?.icon_color}
+                          onChange={(e) => updateState(key, "icon_color", e.target.value)}
+                        />
+                      )}
+                    </div>
+                  ))}
+                  <p className="field-description">
+                    Replace the image, icon color or label while the binding is in that state
+                  </p>
+                </div>
+              )}
             </>
           )}
         </>
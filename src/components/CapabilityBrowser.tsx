@@ -15,6 +15,16 @@ import {
   Lightbulb,
   Music,
   Zap,
+  Folder,
+  Compass,
+  ChevronRight,
+  ChevronLeft,
+  Hash,
+  GalleryHorizontal,
+  ListOrdered,
+  Keyboard,
+  Type,
+  MonitorDot,
   LucideIcon,
 } from "lucide-react";
 import { CapabilityInfo } from "../types";
@@ -52,7 +62,11 @@ const MODULES: Record<string, { icon: LucideIcon; capabilities: string[] }> = {
   },
   Commands: {
     icon: Zap,
-    capabilities: ["RunCommand", "LaunchApp", "OpenURL"],
+    capabilities: ["RunCommand", "CommandDisplay", "LaunchApp", "OpenURL", "SendHotkey", "TypeText", "Macro"],
+  },
+  Navigation: {
+    icon: Compass,
+    capabilities: ["OpenFolder", "NextPage", "PreviousPage", "GoToPage", "PageByEncoder"],
   },
 };
 
@@ -71,8 +85,17 @@ const CAPABILITY_ICONS: Record<string, LucideIcon> = {
   MediaPrevious: SkipBack,
   MediaStop: Square,
   RunCommand: Terminal,
+  CommandDisplay: MonitorDot,
   LaunchApp: AppWindow,
   OpenURL: Globe,
+  SendHotkey: Keyboard,
+  TypeText: Type,
+  Macro: ListOrdered,
+  OpenFolder: Folder,
+  NextPage: ChevronRight,
+  PreviousPage: ChevronLeft,
+  GoToPage: Hash,
+  PageByEncoder: GalleryHorizontal,
   ElgatoKeyLight: Lightbulb,
 };
 
@@ -6,10 +6,15 @@ import {
   Binding,
   SystemState,
   inputsMatch,
+  isOnPage,
   getCapabilityDisplayName,
+  getPageTitle,
+  Page,
   buttonRef,
   encoderRef,
   encoderPressRef,
+  pedalRef,
+  PEDAL_POSITIONS,
 } from "../types";
 import { getCapabilityIcon } from "./CapabilityBrowser";
 import { ReactNode } from "react";
@@ -29,7 +34,15 @@ interface DeviceLayoutProps {
   activeInputs: Set<string>;
   systemState: SystemState;
   currentPage: number;
-  pageCount: number;
+  currentFolder: string | null;
+  breadcrumb: string[];
+  pages: Page[];
+  onSelectPage?: (page: number) => void;
+  onCreatePage?: () => void;
+  onRenamePage?: (page: number, name: string) => void;
+  onDuplicatePage?: (page: number) => void;
+  onDeletePage?: (page: number) => void;
+  onMovePage?: (page: number, direction: number) => void;
   onSelectInput: (input: InputRef) => void;
   onDrop?: (input: InputRef, capabilityId: string) => void;
   onCopyBinding?: (fromInput: InputRef, toInput: InputRef) => void;
@@ -57,6 +70,10 @@ function getEffectiveImage(binding: Binding, state: SystemState): string | undef
 // Serialize InputRef for Set membership
 function inputKey(input: InputRef): string {
   if (input.type === "Swipe") return "swipe";
+  if (input.type === "TouchTap" || input.type === "TouchLongPress") return `${input.type}:${input.section}`;
+  if (input.type === "Chord") return `Chord:${input.indices.join("+")}`;
+  // Pedals report presses as buttons
+  if (input.type === "Pedal") return `Button:${PEDAL_POSITIONS.indexOf(input.position)}`;
   return `${input.type}:${input.index}`;
 }
 
@@ -67,16 +84,26 @@ export default function DeviceLayout({
   activeInputs,
   systemState,
   currentPage,
-  pageCount,
+  currentFolder,
+  breadcrumb,
+  pages,
+  onSelectPage,
+  onCreatePage,
+  onRenamePage,
+  onDuplicatePage,
+  onDeletePage,
+  onMovePage,
   onSelectInput,
   onDrop,
   onCopyBinding,
 }: DeviceLayoutProps) {
   // Track which input is being dragged over
   const [dragOverInput, setDragOverInput] = useState<string | null>(null);
+  // Page name being edited (null = not renaming)
+  const [pageNameDraft, setPageNameDraft] = useState<string | null>(null);
 
   // Filter bindings to current page
-  const pageBindings = bindings.filter((b) => b.page === currentPage);
+  const pageBindings = bindings.filter((b) => isOnPage(b, currentPage, currentFolder));
 
   // Find binding for a given input (on current page)
   const getBinding = (input: InputRef): Binding | undefined => {
@@ -157,11 +184,11 @@ export default function DeviceLayout({
     return { icon, label };
   };
 
-  // Render button grid
+  // Render button grid (or the three pedals of a Stream Deck Pedal)
   const renderButtons = () => {
     const buttons = [];
     for (let i = 0; i < device.button_count; i++) {
-      const input = buttonRef(i);
+      const input = device.has_display ? buttonRef(i) : pedalRef(i);
       const binding = getBinding(input);
       const display = getBindingDisplay(binding);
       const selected = isSelected(input);
@@ -182,7 +209,7 @@ export default function DeviceLayout({
           onDragLeave={handleDragLeave}
           onDrop={(e) => handleDrop(e, input)}
         >
-          <span className="button-index">{i + 1}</span>
+          <span className="button-index">{input.type === "Pedal" ? input.position : i + 1}</span>
           {hasButtonImage ? (
             <>
               <img
@@ -328,21 +355,61 @@ export default function DeviceLayout({
 
   // Render page indicator
   const renderPageIndicator = () => {
-    const dots = [];
-    for (let i = 0; i < pageCount; i++) {
-      dots.push(
-        <span
-          key={`page-${i}`}
-          className={`page-dot ${i === currentPage ? "active" : ""}`}
-          title={`Page ${i + 1}`}
-        />
-      );
-    }
+    const position = pages.findIndex((p) => p.id === currentPage);
+    const page = position >= 0 ? pages[position] : undefined;
+    // The deck can sit on the empty page after the last one
+    const title = page ? getPageTitle(page, position) : "New page";
+
+    const dots = pages.map((p, i) => (
+      <span
+        key={`page-${p.id}`}
+        className={`page-dot ${p.id === currentPage ? "active" : ""}`}
+        title={getPageTitle(p, i)}
+        onClick={() => onSelectPage?.(p.id)}
+      />
+    ));
+
+    const commitRename = () => {
+      if (page && pageNameDraft !== null) {
+        onRenamePage?.(page.id, pageNameDraft.trim());
+      }
+      setPageNameDraft(null);
+    };
 
     return (
       <div className="page-indicator">
-        <span className="page-label">Page {currentPage + 1} of {pageCount}</span>
+        {breadcrumb.length > 0 && (
+          <span className="page-breadcrumb">Home › {breadcrumb.join(" › ")}</span>
+        )}
+        {pageNameDraft !== null ? (
+          <input
+            className="page-name-input"
+            value={pageNameDraft}
+            autoFocus
+            onChange={(e) => setPageNameDraft(e.target.value)}
+            onBlur={commitRename}
+            onKeyDown={(e) => {
+              if (e.key === "Enter") commitRename();
+              if (e.key === "Escape") setPageNameDraft(null);
+            }}
+          />
+        ) : (
+          <span
+            className="page-label"
+            title={page ? "Double-click to rename" : undefined}
+            onDoubleClick={() => page && setPageNameDraft(page.name)}
+          >
+            {title} ({position >= 0 ? position + 1 : pages.length + 1} of {pages.length})
+          </span>
+        )}
         <div className="page-dots">{dots}</div>
+        <div className="page-actions">
+          <button disabled={!page || position === 0} onClick={() => page && onMovePage?.(page.id, -1)} title="Move page left">‹</button>
+          <button onClick={() => onCreatePage?.()} title="Add page">+</button>
+          <button disabled={!page} onClick={() => page && onDuplicatePage?.(page.id)} title="Duplicate page">⧉</button>
+          <button disabled={!page} onClick={() => page && onDeletePage?.(page.id)} title="Delete page and its bindings">🗑</button>
+          <button disabled={!page || position === pages.length - 1} onClick={() => page && onMovePage?.(page.id, 1)} title="Move page right">›</button>
+        </div>
         <span className="page-hint">Swipe touch strip to change pages</span>
       </div>
     );
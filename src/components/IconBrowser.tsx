@@ -56,7 +56,7 @@ export default function IconBrowser({ isOpen, onClose, onSelect }: IconBrowserPr
     try {
       const file = await open({
         multiple: false,
-        filters: [{ name: "Images", extensions: ["png", "jpg", "jpeg", "gif", "webp", "svg"] }],
+        filters: [{ name: "Images", extensions: ["png", "apng", "jpg", "jpeg", "gif", "webp", "svg"] }],
       });
       if (file) {
         onSelect(file);
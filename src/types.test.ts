@@ -3,9 +3,17 @@ import {
   buttonRef,
   encoderRef,
   encoderPressRef,
+  pedalRef,
   inputsMatch,
   getInputDisplayName,
   getCapabilityDisplayName,
+  parseMacroSteps,
+  parseEnvVars,
+  formatEnvVars,
+  describeCommandFailure,
+  describeActionFailure,
+  type CommandExitEvent,
+  type ActionResultEvent,
   type InputRef,
   type Capability,
 } from "./types";
@@ -62,6 +70,20 @@ describe("inputsMatch", () => {
     expect(inputsMatch(swipe1, swipe2)).toBe(true);
   });
 
+  it("matches Chord inputs regardless of order", () => {
+    const a: InputRef = { type: "Chord", indices: [4, 0] };
+    const b: InputRef = { type: "Chord", indices: [0, 4] };
+    const c: InputRef = { type: "Chord", indices: [0, 3] };
+    expect(inputsMatch(a, b)).toBe(true);
+    expect(inputsMatch(a, c)).toBe(false);
+  });
+
+  it("matches Pedal inputs by position", () => {
+    expect(inputsMatch(pedalRef(1), { type: "Pedal", position: "Center" })).toBe(true);
+    expect(inputsMatch(pedalRef(0), pedalRef(2))).toBe(false);
+    expect(inputsMatch(pedalRef(0), buttonRef(0))).toBe(false);
+  });
+
   it("matches identical Encoder refs", () => {
     expect(inputsMatch(encoderRef(2), encoderRef(2))).toBe(true);
   });
@@ -90,6 +112,19 @@ describe("getInputDisplayName", () => {
   it("returns correct name for Swipe", () => {
     expect(getInputDisplayName({ type: "Swipe" })).toBe("Swipe");
   });
+
+  it("returns correct name for touch strip inputs", () => {
+    expect(getInputDisplayName({ type: "TouchTap", section: 0 })).toBe("Touch 1 Tap");
+    expect(getInputDisplayName({ type: "TouchLongPress", section: 3 })).toBe("Touch 4 Long Press");
+  });
+
+  it("returns correct name for Chord", () => {
+    expect(getInputDisplayName({ type: "Chord", indices: [0, 4] })).toBe("Buttons 1 + 5");
+  });
+
+  it("returns correct name for Pedal", () => {
+    expect(getInputDisplayName(pedalRef(2))).toBe("Right Pedal");
+  });
 });
 
 describe("getCapabilityDisplayName", () => {
@@ -128,6 +163,11 @@ describe("getCapabilityDisplayName", () => {
     expect(getCapabilityDisplayName(cap)).toBe("Command");
   });
 
+  it("returns correct name for CommandDisplay", () => {
+    const cap: Capability = { type: "CommandDisplay", command: "date +%H:%M" };
+    expect(getCapabilityDisplayName(cap)).toBe("Output");
+  });
+
   it("returns correct name for LaunchApp", () => {
     const cap: Capability = { type: "LaunchApp", command: "firefox" };
     expect(getCapabilityDisplayName(cap)).toBe("App");
@@ -138,8 +178,91 @@ describe("getCapabilityDisplayName", () => {
     expect(getCapabilityDisplayName(cap)).toBe("URL");
   });
 
+  it("shows the keys for SendHotkey", () => {
+    const cap: Capability = { type: "SendHotkey", keys: "ctrl+shift+t" };
+    expect(getCapabilityDisplayName(cap)).toBe("ctrl+shift+t");
+  });
+
   it("returns correct name for ElgatoKeyLight", () => {
     const cap: Capability = { type: "ElgatoKeyLight", ip: "192.168.1.100", port: 9123, action: "Toggle" };
     expect(getCapabilityDisplayName(cap)).toBe("Key Light");
   });
 });
+
+describe("parseMacroSteps", () => {
+  it("parses steps with optional delays", () => {
+    const steps = parseMacroSteps(
+      '[{"capability": {"type": "MicMute"}}, {"capability": {"type": "MediaNext"}, "delay_ms": 200}]'
+    );
+    expect(steps).toEqual([
+      { capability: { type: "MicMute" } },
+      { capability: { type: "MediaNext" }, delay_ms: 200 },
+    ]);
+  });
+
+  it("rejects invalid JSON and malformed steps", () => {
+    expect(parseMacroSteps("not json")).toBeNull();
+    expect(parseMacroSteps('{"capability": {"type": "Mute"}}')).toBeNull();
+    expect(parseMacroSteps('[{"delay_ms": 100}]')).toBeNull();
+    expect(parseMacroSteps('[{"capability": {"type": "Mute"}, "delay_ms": -1}]')).toBeNull();
+  });
+});
+
+describe("parseEnvVars", () => {
+  it("parses KEY=value lines and round-trips", () => {
+    const env = parseEnvVars("PROJECT=deck\n\nURL=http://x?a=b\n");
+    expect(env).toEqual({ PROJECT: "deck", URL: "http://x?a=b" });
+    expect(parseEnvVars(formatEnvVars(env!))).toEqual(env);
+  });
+
+  it("rejects lines without a name", () => {
+    expect(parseEnvVars("no equals sign")).toBeNull();
+    expect(parseEnvVars("=value")).toBeNull();
+  });
+});
+
+describe("describeCommandFailure", () => {
+  const exit: CommandExitEvent = {
+    command: "make",
+    device: null,
+    input: { type: "Button", index: 0 },
+    page: 0,
+    folder: null,
+    outcome: "exited",
+    success: true,
+    code: 0,
+    error: null,
+  };
+
+  it("ignores successes and kills on release", () => {
+    expect(describeCommandFailure(exit)).toBeNull();
+    expect(describeCommandFailure({ ...exit, outcome: "killed", success: false, code: null })).toBeNull();
+  });
+
+  it("describes failures", () => {
+    expect(describeCommandFailure({ ...exit, success: false, code: 2 })).toBe('Command "make" exited with code 2');
+    expect(describeCommandFailure({ ...exit, outcome: "timed_out", success: false })).toContain("timed out");
+  });
+});
+
+describe("describeActionFailure", () => {
+  const result: ActionResultEvent = {
+    device: "AL12345",
+    input: { type: "Button", index: 2 },
+    page: 0,
+    folder: null,
+    capability: { type: "OBSRecord", host: "localhost", port: 4455, action: "Toggle" },
+    ok: true,
+    error: null,
+  };
+
+  it("ignores successes", () => {
+    expect(describeActionFailure(result)).toBeNull();
+  });
+
+  it("names the key and the error", () => {
+    const message = describeActionFailure({ ...result, ok: false, error: "OBS is not connected" });
+    expect(message).toContain("OBS is not connected");
+    expect(message).toContain(getInputDisplayName(result.input));
+  });
+});
@@ -1,12 +1,14 @@
 // Types mirroring Rust structs for Tauri IPC
 
 export interface DeviceInfo {
+  serial: string;            // Tells several connected decks apart
   model: string;
   button_count: number;
   encoder_count: number;
   rows: number;
   columns: number;
   has_touch_strip: boolean;
+  has_display: boolean;      // False for the Pedal (no key images)
 }
 
 // Input reference types matching Rust's InputRef enum
@@ -14,7 +16,28 @@ export type InputRef =
   | { type: "Button"; index: number }
   | { type: "Encoder"; index: number }
   | { type: "EncoderPress"; index: number }
-  | { type: "Swipe" };
+  | { type: "Swipe" }
+  | { type: "TouchTap"; section: number }
+  | { type: "TouchLongPress"; section: number }
+  | { type: "Chord"; indices: number[] }
+  | { type: "Pedal"; position: PedalPosition };
+
+// Pedals of a Stream Deck Pedal, in the order it reports them (keys 0-2)
+export type PedalPosition = "Left" | "Center" | "Right";
+export const PEDAL_POSITIONS: PedalPosition[] = ["Left", "Center", "Right"];
+
+// Gesture qualifier for button bindings (absent = raw press/release)
+export type Gesture =
+  | { type: "Tap" }
+  | { type: "DoubleTap" }
+  | { type: "LongPress"; threshold_ms?: number }
+  | { type: "HoldRepeat"; delay_ms?: number; interval_ms?: number };
+
+// Encoder acceleration curve, keyed on ticks per 100 ms (absent = raw delta)
+export type Acceleration =
+  | { type: "Linear"; gain?: number; max?: number }
+  | { type: "Exponential"; base?: number; max?: number }
+  | { type: "Table"; points: [number, number][] };
 
 // Key Light action types
 export type KeyLightAction = "Toggle" | "On" | "Off" | "SetBrightness";
@@ -38,9 +61,36 @@ export type Capability =
   | { type: "MediaNext" }
   | { type: "MediaPrevious" }
   | { type: "MediaStop" }
-  | { type: "RunCommand"; command: string; toggle?: boolean }
+  | {
+      type: "RunCommand";
+      command: string;
+      toggle?: boolean;
+      state_command?: string; // Polled; exit code 0 (or state_match output) = active
+      state_match?: string;
+      on_command?: string;    // Run instead of command when inactive
+      off_command?: string;   // Run instead of command when active
+      cwd?: string;
+      env?: Record<string, string>;
+      timeout_ms?: number;    // Kill after this long
+      kill_on_release?: boolean;
+    }
+  | {
+      type: "CommandDisplay";
+      command: string;
+      interval_ms?: number; // 0 = only refresh on press
+      timeout_ms?: number;
+      max_length?: number;
+    }
   | { type: "LaunchApp"; command: string }
   | { type: "OpenURL"; url: string }
+  | { type: "SendHotkey"; keys: string }
+  | { type: "TypeText"; text: string }
+  | { type: "OpenFolder"; name: string }
+  | { type: "NextPage" }
+  | { type: "PreviousPage" }
+  | { type: "GoToPage"; page: number }
+  | { type: "PageByEncoder" }
+  | { type: "Macro"; steps: MacroStep[] }
   | { type: "ElgatoKeyLight"; ip: string; port: number; action: KeyLightAction }
   // OBS Studio capabilities
   | { type: "OBSScene"; host: string; port: number; password?: string; scene: string }
@@ -53,24 +103,86 @@ export type Capability =
   | { type: "OBSVirtualCam"; host: string; port: number; password?: string }
   | { type: "OBSTransition"; host: string; port: number; password?: string };
 
+// One step of a Macro capability
+export interface MacroStep {
+  capability: Capability;
+  delay_ms?: number;         // Pause before the step runs
+}
+
 export interface Binding {
   input: InputRef;
   capability: Capability;
   page: number;              // Which page this binding belongs to (0-indexed)
-  icon?: string;             // Custom emoji or icon name (UI only)
-  label?: string;            // Custom display text (UI only)
+  folder?: string;           // Folder the page belongs to (absent = root pages)
+  device?: string;           // Serial of the deck it's on (absent = every deck)
+  gesture?: Gesture;         // Gesture that triggers this binding (buttons only)
+  acceleration?: Acceleration; // Encoder rotation acceleration curve
+  momentary?: boolean;       // Undo the action on release (absent = pedals only)
+  icon?: string;             // Custom emoji or icon name (emoji drawn on keys without an image)
+  label?: string;            // Custom display text ("\n" starts a new line)
   button_image?: string;     // File path or URL for hardware button (default state)
   button_image_alt?: string; // Alternate image (shown when state is "active", e.g., muted)
   show_label?: boolean;      // Render label on hardware button
   icon_color?: string;       // Color for SVG icons (hex, e.g., "#ffffff")
   icon_color_alt?: string;   // Color for alternate SVG icons
+  background_color?: string; // Key color behind the image or text (hex, default black)
+  text_color?: string;       // Label color on hardware (hex, default white)
+  font_size?: number;        // Label size on hardware in pixels (absent = scaled to the key)
+  label_position?: LabelPosition; // Where the label sits (absent = below an image, centred otherwise)
+  outline_color?: string;    // Outline around label text (hex, absent = drop shadow)
+  gauge?: Gauge;             // Draw the capability's value over the key or on the LCD strip (absent = none)
+  states?: Record<string, StateStyle>; // Look per named state (e.g., "recording"), over the fields above
+}
+
+// How a binding looks in one of the states its plugin reports (unset = binding's own)
+export interface StateStyle {
+  button_image?: string;
+  icon_color?: string;
+  background_color?: string;
+  label?: string;
 }
 
+// Where a label sits on a key
+export type LabelPosition = "Top" | "Middle" | "Bottom";
+
+// How a binding's value (e.g., volume) is drawn over its key or LCD section
+export type Gauge = "Arc" | "Bar" | "Ring" | "Fill";
+
+// Hardware key styling, passed along with a binding
+export type KeyStyle = Pick<
+  Binding,
+  "background_color" | "text_color" | "font_size" | "label_position" | "outline_color" | "gauge" | "states"
+>;
+
 // System state for stateful capabilities
 export interface SystemState {
   is_muted: boolean;
   is_mic_muted: boolean;
   is_playing: boolean;
+  is_paused: boolean;
+}
+
+// A RunCommand child finished (emitted as "command:exit")
+export interface CommandExitEvent {
+  command: string;
+  device: string | null;
+  input: InputRef;
+  page: number;
+  folder: string | null;
+  outcome: "exited" | "timed_out" | "killed" | "failed_to_start";
+  success: boolean;
+  code: number | null;      // null when killed by a signal or not started
+  error: string | null;     // Why it could not be started
+}
+
+export interface ActionResultEvent {
+  device: string;
+  input: InputRef;
+  page: number;
+  folder: string | null;
+  capability: Capability;
+  ok: boolean;
+  error: string | null;
 }
 
 // Capability metadata for UI
@@ -111,9 +223,26 @@ export interface ButtonEvent {
   pressed: boolean;
 }
 
+export type GestureKind = "Tap" | "DoubleTap" | "LongPress" | "HoldRepeat";
+
+export interface GestureEvent {
+  index: number;
+  gesture: GestureKind;
+}
+
+export interface ChordEvent {
+  indices: number[];
+}
+
+export interface TouchPressEvent {
+  section: number;           // Encoder section under the touch point
+  position: [number, number];
+}
+
 export interface EncoderEvent {
   index: number;
   delta: number;
+  ticks_per_100ms: number;   // Turn speed
 }
 
 export interface TouchSwipeEvent {
@@ -121,16 +250,51 @@ export interface TouchSwipeEvent {
   end: [number, number];
 }
 
+// Input event tagged with the serial of the deck it came from
+export type DeviceEvent<T> = T & { device: string };
+
 // Device connection status event
 export interface ConnectionStatusEvent {
+  device: string;            // Serial of the deck
   connected: boolean;
   model: string | null;
 }
 
 // Page change event
 export interface PageChangeEvent {
+  device: string;            // Serial of the deck that changed page
   page: number;
   page_count: number;
+  folder: string | null;     // Open folder (null = root pages)
+  breadcrumb: string[];      // Open folder names, outermost first
+}
+
+// Current location returned by get_current_page
+export interface CurrentPage {
+  page: number;
+  folder: string | null;
+  breadcrumb: string[];
+}
+
+// Named page from the page table (bindings reference it by id)
+export interface Page {
+  id: number;
+  name: string;
+  folder?: string | null;
+  device?: string | null;    // Serial of the deck it's on (absent = every deck)
+  background?: string | null;
+  lcd_background?: string | null; // Image across the Stream Deck Plus touch strip
+  order: number;
+}
+
+// Title for a page at a position in the page list
+export function getPageTitle(page: Page | undefined, position: number): string {
+  return page?.name || `Page ${position + 1}`;
+}
+
+// Check whether a binding belongs to a page of a folder (null = root)
+export function isOnPage(binding: Binding, page: number, folder: string | null): boolean {
+  return binding.page === page && (binding.folder ?? null) === folder;
 }
 
 // Helper to create InputRef
@@ -146,10 +310,24 @@ export function encoderPressRef(index: number): InputRef {
   return { type: "EncoderPress", index };
 }
 
+export function pedalRef(index: number): InputRef {
+  return { type: "Pedal", position: PEDAL_POSITIONS[index] };
+}
+
 // Helper to compare InputRefs
 export function inputsMatch(a: InputRef, b: InputRef): boolean {
   if (a.type !== b.type) return false;
   if (a.type === "Swipe") return true;
+  if (a.type === "TouchTap" || a.type === "TouchLongPress") {
+    return (b as { section: number }).section === a.section;
+  }
+  if (a.type === "Chord" && b.type === "Chord") {
+    const sorted = (indices: number[]) => [...new Set(indices)].sort((x, y) => x - y).join(",");
+    return sorted(a.indices) === sorted(b.indices);
+  }
+  if (a.type === "Pedal" && b.type === "Pedal") {
+    return a.position === b.position;
+  }
   return (a as { index: number }).index === (b as { index: number }).index;
 }
 
@@ -164,6 +342,14 @@ export function getInputDisplayName(input: InputRef): string {
       return `Encoder ${input.index + 1} Press`;
     case "Swipe":
       return "Swipe";
+    case "TouchTap":
+      return `Touch ${input.section + 1} Tap`;
+    case "TouchLongPress":
+      return `Touch ${input.section + 1} Long Press`;
+    case "Chord":
+      return `Buttons ${input.indices.map((i) => i + 1).join(" + ")}`;
+    case "Pedal":
+      return `${input.position} Pedal`;
   }
 }
 
@@ -199,10 +385,28 @@ export function createDefaultCapability(capabilityId: string): Capability | null
       return { type: "MediaStop" };
     case "RunCommand":
       return { type: "RunCommand", command: "", toggle: false };
+    case "CommandDisplay":
+      return { type: "CommandDisplay", command: "", interval_ms: 5000, timeout_ms: 2000, max_length: 10 };
     case "LaunchApp":
       return { type: "LaunchApp", command: "" };
     case "OpenURL":
       return { type: "OpenURL", url: "https://" };
+    case "SendHotkey":
+      return { type: "SendHotkey", keys: "ctrl+c" };
+    case "TypeText":
+      return { type: "TypeText", text: "" };
+    case "OpenFolder":
+      return { type: "OpenFolder", name: "Folder" };
+    case "NextPage":
+      return { type: "NextPage" };
+    case "PreviousPage":
+      return { type: "PreviousPage" };
+    case "GoToPage":
+      return { type: "GoToPage", page: 0 };
+    case "PageByEncoder":
+      return { type: "PageByEncoder" };
+    case "Macro":
+      return { type: "Macro", steps: [] };
     case "ElgatoKeyLight":
       return { type: "ElgatoKeyLight", ip: "192.168.1.100", port: 9123, action: "Toggle" };
     // OBS capabilities
@@ -258,10 +462,28 @@ export function getCapabilityDisplayName(cap: Capability): string {
       return "Stop";
     case "RunCommand":
       return "Command";
+    case "CommandDisplay":
+      return "Output";
     case "LaunchApp":
       return "App";
     case "OpenURL":
       return "URL";
+    case "SendHotkey":
+      return cap.keys;
+    case "TypeText":
+      return "Type";
+    case "OpenFolder":
+      return `📁 ${cap.name}`;
+    case "NextPage":
+      return "Next Page";
+    case "PreviousPage":
+      return "Prev Page";
+    case "GoToPage":
+      return `Page ${cap.page + 1}`;
+    case "PageByEncoder":
+      return "Pages";
+    case "Macro":
+      return "Macro";
     case "ElgatoKeyLight":
       return "Key Light";
     // OBS capabilities
@@ -285,3 +507,64 @@ export function getCapabilityDisplayName(cap: Capability): string {
       return "Transition";
   }
 }
+
+// Parse macro steps written as JSON, or null if they aren't a list of steps
+export function parseMacroSteps(text: string): MacroStep[] | null {
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(text);
+  } catch {
+    return null;
+  }
+  if (!Array.isArray(parsed)) return null;
+  const valid = parsed.every(
+    (step) =>
+      typeof step === "object" &&
+      step !== null &&
+      typeof step.capability?.type === "string" &&
+      (step.delay_ms === undefined || (Number.isInteger(step.delay_ms) && step.delay_ms >= 0))
+  );
+  return valid ? (parsed as MacroStep[]) : null;
+}
+
+// Parse environment variables written one KEY=value per line, or null if a
+// line has no "=" or an empty name
+export function parseEnvVars(text: string): Record<string, string> | null {
+  const env: Record<string, string> = {};
+  for (const line of text.split("\n")) {
+    if (!line.trim()) continue;
+    const eq = line.indexOf("=");
+    const name = line.slice(0, eq).trim();
+    if (eq < 0 || !name) return null;
+    env[name] = line.slice(eq + 1);
+  }
+  return env;
+}
+
+// Format environment variables as KEY=value lines
+export function formatEnvVars(env: Record<string, string> | undefined): string {
+  return Object.entries(env ?? {})
+    .map(([name, value]) => `${name}=${value}`)
+    .join("\n");
+}
+
+// Describe a failed command exit for display, or null if it didn't fail
+export function describeCommandFailure(exit: CommandExitEvent): string | null {
+  switch (exit.outcome) {
+    case "exited":
+      return exit.success ? null : `Command "${exit.command}" exited with code ${exit.code ?? "?"}`;
+    case "timed_out":
+      return `Command "${exit.command}" timed out and was stopped`;
+    case "killed":
+      return null;
+    case "failed_to_start":
+      return `Command "${exit.command}" could not be started: ${exit.error ?? "unknown error"}`;
+  }
+}
+
+export function describeActionFailure(result: ActionResultEvent): string | null {
+  if (result.ok) {
+    return null;
+  }
+  return `${getCapabilityDisplayName(result.capability)} (${getInputDisplayName(result.input)}): ${result.error ?? "failed"}`;
+}